@@ -27,6 +27,8 @@ async fn main() -> anyhow::Result<()> {
         // Add `DecompressionLayer` to make request headers interesting.
         .layer(DecompressionLayer::new())
         .option_layer(config.auth_layer()?)
+        // To link apiserver traces to these spans, add a `kube::client::middleware::TraceContextLayer`
+        // that reads the span context from your OpenTelemetry integration.
         .layer(
             // Attribute names follow [Semantic Conventions].
             // [Semantic Conventions]: https://github.com/open-telemetry/opentelemetry-specification/blob/main/specification/trace/semantic_conventions/http.md#http-client
@@ -1,11 +1,13 @@
+use futures::TryStreamExt;
 use k8s_openapi::api::core::v1::Pod;
 use kube::{
     Client,
-    api::{Api, ListParams, ResourceExt},
+    api::{Api, ExpiredContinue, ListParams, ResourceExt},
 };
 use tracing::*;
 
-// This example shows how to do pagination with the raw `Api` only.
+// This example shows how to do pagination with the raw `Api` only,
+// first by following continue tokens manually, then through `Api::list_stream`.
 // In many realistic setups that need a continual, paginated, safe list-watch;
 // the `watcher` is an easier abstraction that has configurable pagination built in.
 
@@ -28,6 +30,13 @@ async fn main() -> anyhow::Result<()> {
         }
     }
 
+    info!("Streaming pages");
+    let lp = ListParams::default().limit(PAGE_SIZE);
+    let mut pods = std::pin::pin!(api.list_stream(&lp, ExpiredContinue::Fail));
+    while let Some(p) = pods.try_next().await? {
+        info!("Found Pod: {}", p.name_any());
+    }
+
     Ok(())
 }
 
@@ -0,0 +1,444 @@
+use std::{
+    fmt::{self, Debug},
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use either::Either;
+use futures::{StreamExt, stream};
+use kube_core::{Resource, ResourceExt, request};
+use serde::{Serialize, de::DeserializeOwned};
+
+use crate::{
+    Error, Result,
+    api::{Api, DeleteParams, Patch, PatchParams},
+    client::retry::RetryPolicy,
+    error::Status,
+};
+
+/// Options for the bulk operations of [`Api`], such as [`Api::apply_many`]
+///
+/// Requests still go through the middleware of the [`Client`](crate::Client), including its rate limits and retries.
+/// The concurrency only bounds how many of them are in flight at once.
+#[derive(Clone)]
+pub struct BulkParams {
+    concurrency: usize,
+    fail_fast: bool,
+    retry: Option<RetryPolicy>,
+}
+
+impl Default for BulkParams {
+    fn default() -> Self {
+        Self {
+            concurrency: 16,
+            fail_fast: false,
+            retry: None,
+        }
+    }
+}
+
+impl Debug for BulkParams {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BulkParams")
+            .field("concurrency", &self.concurrency)
+            .field("fail_fast", &self.fail_fast)
+            .field("retry", &self.retry.is_some())
+            .finish()
+    }
+}
+
+impl BulkParams {
+    /// Create the default options, running 16 operations at once without stopping on errors
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the maximum number of operations in flight at once
+    ///
+    /// A value of 0 is treated as 1.
+    #[must_use]
+    pub fn concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency;
+        self
+    }
+
+    /// Stop starting new operations after the first failure
+    ///
+    /// Operations already in flight are completed, and the items that were never started are reported as skipped.
+    #[must_use]
+    pub fn fail_fast(mut self, fail_fast: bool) -> Self {
+        self.fail_fast = fail_fast;
+        self
+    }
+
+    /// Retry each failed operation with a [`RetryPolicy`]
+    ///
+    /// This retries the same failures as the policy does as client middleware, when it ran out of retries or the
+    /// client was built without it. Transport errors, 503 and 504 are only retried for [`Api::apply_many`], and for
+    /// [`Api::delete_many`] with [`DeleteParams::preconditions`], since repeating an arbitrary patch or an
+    /// unconditional delete may not be safe; other operations only retry requests throttled with 429.
+    #[must_use]
+    pub fn retry(mut self, policy: RetryPolicy) -> Self {
+        self.retry = Some(policy);
+        self
+    }
+}
+
+/// The result of an operation on one item of a bulk operation
+#[derive(Debug)]
+pub struct BulkItem<T> {
+    /// Name of the object
+    pub name: String,
+    /// The object returned by the operation, or the error it failed with
+    pub result: Result<T>,
+}
+
+/// The results of a bulk operation, in the order of its input
+#[derive(Debug)]
+pub struct BulkResult<T> {
+    /// Results of the operations that were started
+    pub items: Vec<BulkItem<T>>,
+    /// Names of the objects whose operation was never started, after an earlier failure with [`BulkParams::fail_fast`]
+    pub skipped: Vec<String>,
+}
+
+impl<T> BulkResult<T> {
+    /// Counts of the succeeded, failed and skipped operations
+    pub fn summary(&self) -> BulkSummary {
+        let failed = self.items.iter().filter(|item| item.result.is_err()).count();
+        BulkSummary {
+            succeeded: self.items.len() - failed,
+            failed,
+            skipped: self.skipped.len(),
+        }
+    }
+
+    /// Whether every operation was started and succeeded
+    pub fn is_success(&self) -> bool {
+        self.skipped.is_empty() && self.items.iter().all(|item| item.result.is_ok())
+    }
+
+    /// The names and errors of the failed operations
+    pub fn errors(&self) -> impl Iterator<Item = (&str, &Error)> {
+        self.items
+            .iter()
+            .filter_map(|item| item.result.as_ref().err().map(|err| (item.name.as_str(), err)))
+    }
+}
+
+/// Counts of the outcomes of a bulk operation, returned by [`BulkResult::summary`]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct BulkSummary {
+    /// Number of operations that succeeded
+    pub succeeded: usize,
+    /// Number of operations that failed
+    pub failed: usize,
+    /// Number of operations that were never started
+    pub skipped: usize,
+}
+
+impl fmt::Display for BulkSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} succeeded, {} failed, {} skipped",
+            self.succeeded, self.failed, self.skipped
+        )
+    }
+}
+
+impl<K> Api<K>
+where
+    K: Resource + Clone + DeserializeOwned + Serialize + Debug,
+{
+    /// Server-side apply many objects, with bounded concurrency
+    ///
+    /// Each object is applied to the object of the same name, so they must all have a name.
+    /// `pp` should be [`PatchParams::apply`] with a field manager, like for [`Patch::Apply`].
+    ///
+    /// ```no_run
+    /// use kube::api::{Api, BulkParams, PatchParams};
+    /// use k8s_openapi::api::core::v1::ConfigMap;
+    /// # async fn wrapper(cms: Api<ConfigMap>, objects: Vec<ConfigMap>) -> Result<(), Box<dyn std::error::Error>> {
+    /// let bp = BulkParams::new().concurrency(32).fail_fast(true);
+    /// let result = cms.apply_many(objects, &PatchParams::apply("migration"), &bp).await;
+    /// println!("{}", result.summary());
+    /// for (name, err) in result.errors() {
+    ///     eprintln!("failed to apply {name}: {err}");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn apply_many(
+        &self,
+        objects: impl IntoIterator<Item = K>,
+        pp: &PatchParams,
+        bp: &BulkParams,
+    ) -> BulkResult<K> {
+        let objects = objects.into_iter().map(|object| (object.name_any(), object)).collect();
+        run(objects, bp, true, |name, object: K| async move {
+            if object.meta().name.is_none() {
+                return Err(Error::BuildRequest(request::Error::Validation(
+                    "objects applied in bulk must have a name".into(),
+                )));
+            }
+            self.patch(&name, pp, &Patch::Apply(object)).await
+        })
+        .await
+    }
+
+    /// Patch many named objects, with bounded concurrency
+    ///
+    /// Unlike for [`Api::apply_many`], only requests throttled with 429, which the apiserver rejects before handling,
+    /// are retried by [`BulkParams::retry`], since a patch may not have the same result when repeated.
+    pub async fn patch_many<P: Serialize + Clone + Debug>(
+        &self,
+        patches: impl IntoIterator<Item = (String, Patch<P>)>,
+        pp: &PatchParams,
+        bp: &BulkParams,
+    ) -> BulkResult<K> {
+        let patches = patches.into_iter().collect();
+        run(patches, bp, false, |name, patch| async move { self.patch(&name, pp, &patch).await }).await
+    }
+
+    /// Delete many named objects, with bounded concurrency
+    ///
+    /// Like for [`Api::delete`], each result is either the object being deleted, or the status of a completed deletion.
+    /// Like for the retry middleware, only deletes with preconditions are retried by [`BulkParams::retry`]
+    /// after transport errors, 503 and 504, since the object may have been deleted and recreated in the meantime.
+    pub async fn delete_many(
+        &self,
+        names: impl IntoIterator<Item = impl Into<String>>,
+        dp: &DeleteParams,
+        bp: &BulkParams,
+    ) -> BulkResult<Either<K, Status>> {
+        let names = names.into_iter().map(|name| (name.into(), ())).collect();
+        let idempotent = dp
+            .preconditions
+            .as_ref()
+            .is_some_and(|preconditions| preconditions.uid.is_some() || preconditions.resource_version.is_some());
+        run(names, bp, idempotent, |name, ()| async move { self.delete(&name, dp).await }).await
+    }
+}
+
+/// Run `op` on every named item with the concurrency, failure and retry handling of `bp`
+///
+/// `op` is given owned copies of the name and item, so that its future doesn't borrow from this one.
+async fn run<A: Clone, T, F: Future<Output = Result<T>>>(
+    items: Vec<(String, A)>,
+    bp: &BulkParams,
+    idempotent: bool,
+    op: impl Fn(String, A) -> F,
+) -> BulkResult<T> {
+    let stopped = AtomicBool::new(false);
+    let (stopped, op) = (&stopped, &op);
+    let mut outcomes: Vec<_> = stream::iter(items.into_iter().enumerate())
+        .map(|(index, (name, item))| async move {
+            if stopped.load(Ordering::Relaxed) {
+                return (index, name, None);
+            }
+            let mut retry = bp.retry.clone();
+            let result = loop {
+                match op(name.clone(), item.clone()).await {
+                    Err(err) => match retry.as_mut().and_then(|policy| policy.retry_error(&err, idempotent)) {
+                        Some(backoff) => backoff.await,
+                        None => break Err(err),
+                    },
+                    result => break result,
+                }
+            };
+            if result.is_err() && bp.fail_fast {
+                stopped.store(true, Ordering::Relaxed);
+            }
+            (index, name, Some(result))
+        })
+        .buffer_unordered(bp.concurrency.max(1))
+        .collect()
+        .await;
+    outcomes.sort_unstable_by_key(|(index, ..)| *index);
+
+    let mut result = BulkResult {
+        items: Vec::with_capacity(outcomes.len()),
+        skipped: Vec::new(),
+    };
+    for (_, name, outcome) in outcomes {
+        match outcome {
+            Some(outcome) => result.items.push(BulkItem { name, result: outcome }),
+            None => result.skipped.push(name),
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::{pin::pin, time::Duration};
+
+    use http::{Request, Response, StatusCode};
+    use k8s_openapi::api::core::v1::ConfigMap;
+    use serde_json::json;
+    use tower_test::mock;
+
+    use crate::{Client, api::Preconditions, client::Body};
+
+    fn configmap(name: &str) -> serde_json::Value {
+        json!({ "apiVersion": "v1", "kind": "ConfigMap", "metadata": { "name": name } })
+    }
+
+    fn status(code: u16, reason: &str) -> Response<Body> {
+        let status = json!({ "kind": "Status", "status": "Failure", "reason": reason, "code": code });
+        Response::builder()
+            .status(code)
+            .body(Body::from(serde_json::to_vec(&status).unwrap()))
+            .unwrap()
+    }
+
+    fn name_of(request: &Request<Body>) -> String {
+        request.uri().path().rsplit('/').next().unwrap().to_string()
+    }
+
+    #[tokio::test]
+    async fn apply_many_reports_results_in_input_order() {
+        let (mock_service, handle) = mock::pair::<Request<Body>, Response<Body>>();
+        let spawned = tokio::spawn(async move {
+            let mut handle = pin!(handle);
+            for _ in 0..3 {
+                let (request, send) = handle.next_request().await.expect("service not called");
+                assert_eq!(request.method(), http::Method::PATCH);
+                let name = name_of(&request);
+                if name == "b" {
+                    send.send_response(status(422, "Invalid"));
+                } else {
+                    send.send_response(Response::new(Body::from(
+                        serde_json::to_vec(&configmap(&name)).unwrap(),
+                    )));
+                }
+            }
+        });
+
+        let api: Api<ConfigMap> = Api::default_namespaced(Client::new(mock_service, "default"));
+        let objects = ["a", "b", "c"].map(|name| serde_json::from_value(configmap(name)).unwrap());
+        let result = api
+            .apply_many(objects, &PatchParams::apply("bulk"), &BulkParams::new().concurrency(2))
+            .await;
+        let names: Vec<_> = result.items.iter().map(|item| item.name.as_str()).collect();
+        assert_eq!(names, ["a", "b", "c"]);
+        assert_eq!(result.summary(), BulkSummary {
+            succeeded: 2,
+            failed: 1,
+            skipped: 0,
+        });
+        assert_eq!(result.summary().to_string(), "2 succeeded, 1 failed, 0 skipped");
+        assert!(matches!(result.errors().collect::<Vec<_>>()[..], [("b", Error::Api(_))]));
+        spawned.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn delete_many_stops_on_first_error() {
+        let (mock_service, handle) = mock::pair::<Request<Body>, Response<Body>>();
+        let spawned = tokio::spawn(async move {
+            let mut handle = pin!(handle);
+            let (request, send) = handle.next_request().await.expect("service not called");
+            assert_eq!(request.method(), http::Method::DELETE);
+            assert_eq!(name_of(&request), "a");
+            send.send_response(status(403, "Forbidden"));
+        });
+
+        let api: Api<ConfigMap> = Api::default_namespaced(Client::new(mock_service, "default"));
+        // Bulk operations can be spawned, since their futures are Send
+        let result = tokio::spawn(async move {
+            let bp = BulkParams::new().concurrency(1).fail_fast(true);
+            api.delete_many(["a", "b", "c"], &DeleteParams::default(), &bp).await
+        })
+        .await
+        .unwrap();
+        assert_eq!(result.items.len(), 1);
+        assert_eq!(result.skipped, ["b", "c"]);
+        assert!(!result.is_success());
+        spawned.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn patch_many_retries_throttled_requests() {
+        let (mock_service, handle) = mock::pair::<Request<Body>, Response<Body>>();
+        let spawned = tokio::spawn(async move {
+            let mut handle = pin!(handle);
+            let (_, send) = handle.next_request().await.expect("service not called");
+            send.send_response(status(StatusCode::TOO_MANY_REQUESTS.as_u16(), "TooManyRequests"));
+            let (request, send) = handle.next_request().await.expect("service not called");
+            assert_eq!(name_of(&request), "a");
+            send.send_response(Response::new(Body::from(
+                serde_json::to_vec(&configmap("a")).unwrap(),
+            )));
+        });
+
+        let api: Api<ConfigMap> = Api::default_namespaced(Client::new(mock_service, "default"));
+        let retry = RetryPolicy::new(Duration::from_millis(1), Duration::from_millis(1), 1, false).unwrap();
+        let patches = [("a".to_string(), Patch::Merge(json!({ "data": { "k": "v" } })))];
+        let result = api
+            .patch_many(patches, &PatchParams::default(), &BulkParams::new().retry(retry))
+            .await;
+        assert!(result.is_success());
+        spawned.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn delete_many_only_retries_gateway_timeouts_with_preconditions() {
+        let (mock_service, handle) = mock::pair::<Request<Body>, Response<Body>>();
+        let spawned = tokio::spawn(async move {
+            let mut handle = pin!(handle);
+            let has_preconditions = |request: &Request<Body>| {
+                let body = request.body().bytes().unwrap();
+                serde_json::from_slice::<serde_json::Value>(body).unwrap()["preconditions"].is_object()
+            };
+            // The unconditional delete is not retried, so the next request is the delete with preconditions
+            let (request, send) = handle.next_request().await.expect("service not called");
+            assert!(!has_preconditions(&request));
+            send.send_response(status(StatusCode::GATEWAY_TIMEOUT.as_u16(), "Timeout"));
+            let deleted = Response::new(Body::from(serde_json::to_vec(&configmap("a")).unwrap()));
+            for response in [status(StatusCode::GATEWAY_TIMEOUT.as_u16(), "Timeout"), deleted] {
+                let (request, send) = handle.next_request().await.expect("service not called");
+                assert!(has_preconditions(&request), "unconditional delete was retried");
+                send.send_response(response);
+            }
+        });
+
+        let api: Api<ConfigMap> = Api::default_namespaced(Client::new(mock_service, "default"));
+        let retry = RetryPolicy::new(Duration::from_millis(1), Duration::from_millis(1), 1, false).unwrap();
+        let bp = BulkParams::new().retry(retry);
+        let result = api.delete_many(["a"], &DeleteParams::default(), &bp).await;
+        assert_eq!(result.summary().failed, 1);
+        let dp = DeleteParams {
+            preconditions: Some(Preconditions {
+                uid: Some("uid".into()),
+                resource_version: None,
+            }),
+            ..DeleteParams::default()
+        };
+        let result = api.delete_many(["a"], &dp, &bp).await;
+        assert!(result.is_success());
+        spawned.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn patch_many_does_not_retry_gateway_timeouts() {
+        let (mock_service, handle) = mock::pair::<Request<Body>, Response<Body>>();
+        let spawned = tokio::spawn(async move {
+            let mut handle = pin!(handle);
+            let (_, send) = handle.next_request().await.expect("service not called");
+            send.send_response(status(StatusCode::GATEWAY_TIMEOUT.as_u16(), "Timeout"));
+            let retried = tokio::time::timeout(Duration::from_millis(100), handle.next_request()).await;
+            assert!(retried.is_err(), "patch was retried");
+        });
+
+        let api: Api<ConfigMap> = Api::default_namespaced(Client::new(mock_service, "default"));
+        let retry = RetryPolicy::new(Duration::from_millis(1), Duration::from_millis(1), 1, false).unwrap();
+        let patches = [("a".to_string(), Patch::Merge(json!({ "data": { "k": "v" } })))];
+        let result = api
+            .patch_many(patches, &PatchParams::default(), &BulkParams::new().retry(retry))
+            .await;
+        assert_eq!(result.summary().failed, 1);
+        spawned.await.unwrap();
+    }
+}
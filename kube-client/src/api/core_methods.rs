@@ -3,7 +3,14 @@ use futures::Stream;
 use serde::{Serialize, de::DeserializeOwned};
 use std::fmt::Debug;
 
-use crate::{Error, Result, api::Api};
+use crate::{
+    Error, Result,
+    api::Api,
+    client::{
+        ObjectListStream,
+        warning::{self, WithWarnings},
+    },
+};
 use kube_core::{WatchEvent, metadata::PartialObjectMeta, object::ObjectList, params::*, response::Status};
 
 /// PUSH/PUT/POST/GET abstractions
@@ -227,7 +234,39 @@ where
         }
         .map_err(Error::BuildRequest)?;
         req.extensions_mut().insert("list");
-        self.client.request::<ObjectList<K>>(req).await
+        self.client.request_list::<K>(req).await?.into_list().await
+    }
+
+    /// Get a list of resources, deserializing them one at a time as the response arrives
+    ///
+    /// Unlike [`Api::list`], the response is never held in memory at once, only the object being decoded.
+    /// This makes a large difference in peak memory for large lists, or pages with a high [`ListParams::limit`].
+    ///
+    /// ```no_run
+    /// use kube::api::{Api, ListParams, ResourceExt};
+    /// use k8s_openapi::api::core::v1::Pod;
+    /// use futures::TryStreamExt;
+    ///
+    /// # async fn wrapper() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client: kube::Client = todo!();
+    /// let pods: Api<Pod> = Api::all(client);
+    /// let mut list = pods.list_items(&ListParams::default()).await?;
+    /// while let Some(p) = list.try_next().await? {
+    ///     println!("Found Pod: {}", p.name_any());
+    /// }
+    /// println!("Listed at resource version {:?}", list.metadata().resource_version);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn list_items(&self, lp: &ListParams) -> Result<ObjectListStream<K>> {
+        let mut req = if self.metadata_api {
+            self.request.list_metadata(lp)
+        } else {
+            self.request.list(lp)
+        }
+        .map_err(Error::BuildRequest)?;
+        req.extensions_mut().insert("list");
+        self.client.request_list::<K>(req).await
     }
 
     /// Get a list of resources that contains only their metadata as
@@ -254,7 +293,20 @@ where
     pub async fn list_metadata(&self, lp: &ListParams) -> Result<ObjectList<PartialObjectMeta<K>>> {
         let mut req = self.request.list_metadata(lp).map_err(Error::BuildRequest)?;
         req.extensions_mut().insert("list_metadata");
-        self.client.request::<ObjectList<PartialObjectMeta<K>>>(req).await
+        self.client
+            .request_list::<PartialObjectMeta<K>>(req)
+            .await?
+            .into_list()
+            .await
+    }
+
+    /// Get a list of resources that contains only their metadata, deserializing them one at a time as the response arrives
+    ///
+    /// See [`Api::list_items`].
+    pub async fn list_metadata_items(&self, lp: &ListParams) -> Result<ObjectListStream<PartialObjectMeta<K>>> {
+        let mut req = self.request.list_metadata(lp).map_err(Error::BuildRequest)?;
+        req.extensions_mut().insert("list_metadata");
+        self.client.request_list::<PartialObjectMeta<K>>(req).await
     }
 
     /// Create a resource
@@ -286,6 +338,18 @@ where
         self.client.request::<K>(req).await
     }
 
+    /// Create a resource, returning the warnings sent by the apiserver along with it
+    ///
+    /// The warnings are also passed to the [`WarningHandler`](crate::client::warning::WarningHandler) of the client.
+    /// See [`Api::create`] for details.
+    pub async fn create_with_warnings(&self, pp: &PostParams, data: &K) -> Result<WithWarnings<K>>
+    where
+        K: Serialize,
+    {
+        let (value, warnings) = warning::capture(self.create(pp, data)).await;
+        Ok(WithWarnings { value: value?, warnings })
+    }
+
     /// Delete a named resource
     ///
     /// When you get a `K` via `Left`, your delete has started.
@@ -401,6 +465,20 @@ where
         self.client.request::<K>(req).await
     }
 
+    /// Patch a subset of a resource's properties, returning the warnings sent by the apiserver along with it
+    ///
+    /// The warnings are also passed to the [`WarningHandler`](crate::client::warning::WarningHandler) of the client.
+    /// See [`Api::patch`] for details.
+    pub async fn patch_with_warnings<P: Serialize + Debug>(
+        &self,
+        name: &str,
+        pp: &PatchParams,
+        patch: &Patch<P>,
+    ) -> Result<WithWarnings<K>> {
+        let (value, warnings) = warning::capture(self.patch(name, pp, patch)).await;
+        Ok(WithWarnings { value: value?, warnings })
+    }
+
     /// Patch a metadata subset of a resource's properties from [`PartialObjectMeta`]
     ///
     /// Takes a [`Patch`] along with [`PatchParams`] for the call.
@@ -0,0 +1,352 @@
+use std::fmt::{self, Debug};
+
+use serde::{Serialize, de::DeserializeOwned};
+use serde_json::Value;
+
+use crate::{
+    Error, Result,
+    api::{Api, Patch, PatchParams},
+};
+
+/// Fields of `metadata` that change on every write, left out of a [`Diff`]
+const NOISY_METADATA: [&str; 3] = ["managedFields", "resourceVersion", "generation"];
+
+/// Lines of context around each change in [`Diff::unified`]
+const CONTEXT_LINES: usize = 3;
+
+/// Largest table of changed lines (old × new) compared line by line in [`Diff::unified`]
+///
+/// Past this, the changed region is shown as removed and added as a whole to keep time and memory bounded.
+const MAX_DIFF_CELLS: usize = 1 << 22;
+
+/// Difference between a live object and the result of patching it, returned by [`Api::diff`]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Diff {
+    /// Name of the object
+    pub name: String,
+    /// The live object, or `None` if the patch would create it
+    pub live: Option<Value>,
+    /// The object as it would be after the patch
+    pub merged: Value,
+    /// JSON patch turning `live` into `merged`
+    pub patch: json_patch::Patch,
+}
+
+impl Diff {
+    fn new(name: &str, mut live: Option<Value>, mut merged: Value) -> Self {
+        live.iter_mut().chain([&mut merged]).for_each(strip_noise);
+        let patch = json_patch::diff(live.as_ref().unwrap_or(&Value::Null), &merged);
+        Self {
+            name: name.to_owned(),
+            live,
+            merged,
+            patch,
+        }
+    }
+
+    /// Whether the patch would not change the object
+    pub fn is_empty(&self) -> bool {
+        self.patch.0.is_empty()
+    }
+
+    /// Render the diff of the YAML of both objects in the unified format, like `kubectl diff`
+    ///
+    /// Returns an empty string if the patch would not change the object.
+    pub fn unified(&self) -> String {
+        if self.is_empty() {
+            return String::new();
+        }
+        let live = self.live.as_ref().map(to_yaml).unwrap_or_default();
+        let merged = to_yaml(&self.merged);
+        let mut out = format!("--- live/{name}\n+++ merged/{name}\n", name = self.name);
+        unified(&mut out, &live.lines().collect::<Vec<_>>(), &merged.lines().collect::<Vec<_>>());
+        out
+    }
+}
+
+impl fmt::Display for Diff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.unified())
+    }
+}
+
+fn strip_noise(object: &mut Value) {
+    if let Some(Value::Object(metadata)) = object.get_mut("metadata") {
+        for field in NOISY_METADATA {
+            metadata.remove(field);
+        }
+    }
+}
+
+fn to_yaml(value: &Value) -> String {
+    serde_saphyr::to_string(value)
+        .or_else(|_| serde_json::to_string_pretty(value))
+        .unwrap_or_default()
+}
+
+/// A line of a line-by-line diff
+#[derive(Clone, Copy, PartialEq)]
+enum Line<'a> {
+    Same(&'a str),
+    Removed(&'a str),
+    Added(&'a str),
+}
+
+/// Diff two texts line by line, through their longest common subsequence
+///
+/// Changed regions larger than [`MAX_DIFF_CELLS`] are replaced as a whole instead.
+fn diff_lines<'a>(old: &[&'a str], new: &[&'a str]) -> Vec<Line<'a>> {
+    // Trim the common prefix and suffix, which is most of the text for typical changes
+    let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let (a, b) = (&old[prefix..old.len() - suffix], &new[prefix..new.len() - suffix]);
+    let mut lines: Vec<_> = old[..prefix].iter().map(|line| Line::Same(line)).collect();
+
+    if a.len().saturating_mul(b.len()) > MAX_DIFF_CELLS {
+        lines.extend(a.iter().map(|line| Line::Removed(line)));
+        lines.extend(b.iter().map(|line| Line::Added(line)));
+        lines.extend(old[old.len() - suffix..].iter().map(|line| Line::Same(line)));
+        return lines;
+    }
+
+    // lcs[i][j] is the length of the longest common subsequence of a[i..] and b[j..]
+    let mut lcs = vec![vec![0u32; b.len() + 1]; a.len() + 1];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            lcs[i][j] = if a[i] == b[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() || j < b.len() {
+        if i < a.len() && j < b.len() && a[i] == b[j] {
+            lines.push(Line::Same(a[i]));
+            (i, j) = (i + 1, j + 1);
+        } else if i < a.len() && (j == b.len() || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            lines.push(Line::Removed(a[i]));
+            i += 1;
+        } else {
+            lines.push(Line::Added(b[j]));
+            j += 1;
+        }
+    }
+    lines.extend(old[old.len() - suffix..].iter().map(|line| Line::Same(line)));
+    lines
+}
+
+/// Write the hunks of the unified diff between `old` and `new` to `out`
+fn unified(out: &mut String, old: &[&str], new: &[&str]) {
+    let lines = diff_lines(old, new);
+    let changed: Vec<usize> = (0..lines.len())
+        .filter(|&i| !matches!(lines[i], Line::Same(_)))
+        .collect();
+
+    let mut changes = changed.iter().peekable();
+    while let Some(&first) = changes.next() {
+        // Extend the hunk while the next change is within reach of its context
+        let mut last = first;
+        while let Some(&&next) = changes.peek() {
+            if next - last > 2 * CONTEXT_LINES {
+                break;
+            }
+            last = next;
+            changes.next();
+        }
+        let start = first.saturating_sub(CONTEXT_LINES);
+        let end = (last + CONTEXT_LINES + 1).min(lines.len());
+
+        // Line numbers of the hunk start in both texts
+        let count = |lines: &[Line], removed: bool| {
+            lines
+                .iter()
+                .filter(|line| match line {
+                    Line::Same(_) => true,
+                    Line::Removed(_) => removed,
+                    Line::Added(_) => !removed,
+                })
+                .count()
+        };
+        let (old_start, new_start) = (count(&lines[..start], true), count(&lines[..start], false));
+        let (old_len, new_len) = (count(&lines[start..end], true), count(&lines[start..end], false));
+        out.push_str(&format!(
+            "@@ -{},{old_len} +{},{new_len} @@\n",
+            old_start + usize::from(old_len > 0),
+            new_start + usize::from(new_len > 0)
+        ));
+        for line in &lines[start..end] {
+            let (marker, text) = match line {
+                Line::Same(text) => (' ', text),
+                Line::Removed(text) => ('-', text),
+                Line::Added(text) => ('+', text),
+            };
+            out.push(marker);
+            out.push_str(text);
+            out.push('\n');
+        }
+    }
+}
+
+impl<K> Api<K>
+where
+    K: Clone + DeserializeOwned + Serialize + Debug,
+{
+    /// Diff the live object against the result of applying a patch, like `kubectl diff`
+    ///
+    /// The patch is sent as a dry run, so nothing is persisted, and the result is compared to the live object.
+    /// Fields that change on every write (`managedFields`, `resourceVersion` and `generation`) are left out.
+    ///
+    /// ```no_run
+    /// use kube::api::{Api, DynamicObject, Patch, PatchParams};
+    /// # async fn wrapper(api: Api<DynamicObject>, manifest: DynamicObject) -> Result<(), Box<dyn std::error::Error>> {
+    /// let diff = api.diff("web", &Patch::Apply(&manifest), &PatchParams::apply("gitops")).await?;
+    /// if !diff.is_empty() {
+    ///     print!("{diff}");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn diff<P: Serialize + Debug>(
+        &self,
+        name: &str,
+        patch: &Patch<P>,
+        pp: &PatchParams,
+    ) -> Result<Diff> {
+        let live = self.get_opt(name).await?;
+        let pp = PatchParams {
+            dry_run: true,
+            ..pp.clone()
+        };
+        let merged = self.patch(name, &pp, patch).await?;
+        let live = live
+            .map(|live| serde_json::to_value(live).map_err(Error::SerdeError))
+            .transpose()?;
+        let merged = serde_json::to_value(merged).map_err(Error::SerdeError)?;
+        Ok(Diff::new(name, live, merged))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::pin::pin;
+
+    use http::{Request, Response, StatusCode};
+    use k8s_openapi::api::core::v1::ConfigMap;
+    use serde_json::json;
+    use tower_test::mock;
+
+    use crate::{Client, client::Body};
+
+    #[test]
+    fn unified_diff_hunks() {
+        let old: Vec<String> = (1..=20).map(|i| format!("line {i}")).collect();
+        let mut new = old.clone();
+        new[1] = "changed 2".into();
+        new.insert(15, "added".into());
+        let old: Vec<&str> = old.iter().map(String::as_str).collect();
+        let new: Vec<&str> = new.iter().map(String::as_str).collect();
+
+        let mut out = String::new();
+        unified(&mut out, &old, &new);
+        assert_eq!(
+            out,
+            "@@ -1,5 +1,5 @@\n line 1\n-line 2\n+changed 2\n line 3\n line 4\n line 5\n\
+             @@ -13,6 +13,7 @@\n line 13\n line 14\n line 15\n+added\n line 16\n line 17\n line 18\n"
+        );
+    }
+
+    #[test]
+    fn large_changes_are_replaced_whole() {
+        let old: Vec<String> = (0..3000).map(|i| format!("old {i}")).collect();
+        let new: Vec<String> = (0..3000).map(|i| format!("new {i}")).collect();
+        let old: Vec<&str> = ["head"].into_iter().chain(old.iter().map(String::as_str)).collect();
+        let new: Vec<&str> = ["head"].into_iter().chain(new.iter().map(String::as_str)).collect();
+
+        let lines = diff_lines(&old, &new);
+        assert_eq!(lines.len(), 6001);
+        assert!(lines[0] == Line::Same("head"));
+        assert!(lines[1..3001].iter().all(|line| matches!(line, Line::Removed(_))));
+        assert!(lines[3001..].iter().all(|line| matches!(line, Line::Added(_))));
+    }
+
+    fn configmap(data: serde_json::Value, resource_version: &str) -> serde_json::Value {
+        json!({
+            "apiVersion": "v1",
+            "kind": "ConfigMap",
+            "metadata": {
+                "name": "cm",
+                "resourceVersion": resource_version,
+                "managedFields": [{ "manager": "gitops", "operation": "Apply" }],
+            },
+            "data": data,
+        })
+    }
+
+    #[tokio::test]
+    async fn diffs_dry_run_apply_against_live_object() {
+        let (mock_service, handle) = mock::pair::<Request<Body>, Response<Body>>();
+        let spawned = tokio::spawn(async move {
+            let mut handle = pin!(handle);
+            let (request, send) = handle.next_request().await.expect("service not called");
+            assert_eq!(request.method(), http::Method::GET);
+            let live = configmap(json!({ "a": "1", "b": "2" }), "1");
+            send.send_response(Response::new(Body::from(serde_json::to_vec(&live).unwrap())));
+
+            let (request, send) = handle.next_request().await.expect("service not called");
+            assert_eq!(request.method(), http::Method::PATCH);
+            assert!(request.uri().query().unwrap().contains("dryRun=All"));
+            let merged = configmap(json!({ "a": "1", "b": "3" }), "2");
+            send.send_response(Response::new(Body::from(serde_json::to_vec(&merged).unwrap())));
+        });
+
+        let api: Api<ConfigMap> = Api::default_namespaced(Client::new(mock_service, "default"));
+        let patch = Patch::Apply(json!({ "data": { "b": "3" } }));
+        let diff = api.diff("cm", &patch, &PatchParams::apply("gitops")).await.unwrap();
+        assert_eq!(
+            serde_json::to_value(&diff.patch).unwrap(),
+            json!([{ "op": "replace", "path": "/data/b", "value": "3" }])
+        );
+        assert_eq!(
+            diff.unified(),
+            "--- live/cm\n+++ merged/cm\n@@ -1,7 +1,7 @@\n apiVersion: v1\n data:\n   a: \"1\"\n-  b: \"2\"\n+  b: \"3\"\n kind: ConfigMap\n metadata:\n   name: cm\n"
+        );
+        spawned.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn diffs_creation() {
+        let (mock_service, handle) = mock::pair::<Request<Body>, Response<Body>>();
+        let spawned = tokio::spawn(async move {
+            let mut handle = pin!(handle);
+            let (_, send) = handle.next_request().await.expect("service not called");
+            let status = json!({ "kind": "Status", "status": "Failure", "reason": "NotFound", "code": 404 });
+            send.send_response(
+                Response::builder()
+                    .status(StatusCode::NOT_FOUND)
+                    .body(Body::from(serde_json::to_vec(&status).unwrap()))
+                    .unwrap(),
+            );
+            let (_, send) = handle.next_request().await.expect("service not called");
+            let merged = configmap(json!({ "a": "1" }), "1");
+            send.send_response(Response::new(Body::from(serde_json::to_vec(&merged).unwrap())));
+        });
+
+        let api: Api<ConfigMap> = Api::default_namespaced(Client::new(mock_service, "default"));
+        let patch = Patch::Apply(json!({ "data": { "a": "1" } }));
+        let diff = api.diff("cm", &patch, &PatchParams::apply("gitops")).await.unwrap();
+        assert!(diff.live.is_none());
+        assert!(diff.merged["metadata"].get("managedFields").is_none());
+        assert!(diff.unified().lines().skip(3).all(|line| line.starts_with('+')));
+        spawned.await.unwrap();
+    }
+}
@@ -1,10 +1,11 @@
 //! API helpers for get-or-create and get-and-modify patterns
 //!
 //! [`Api::entry`] is the primary entry point for this API.
+//! [`Api::update_with_retry`] wraps the get-and-modify pattern in a retry loop for write conflicts.
 
 // Import used in docs
 #[allow(unused_imports)] use std::collections::HashMap;
-use std::fmt::Debug;
+use std::{fmt::Debug, time::Duration};
 
 use crate::{Api, Error, Result};
 use kube_core::{Resource, params::PostParams};
@@ -177,7 +178,7 @@ impl<K> OccupiedEntry<'_, K> {
     /// client modified the object in the meantime).
     ///
     /// Any retries should be coarse-grained enough to also include the call to [`Api::entry`], so that the latest
-    /// state can be fetched.
+    /// state can be fetched. [`Api::update_with_retry`] does this for updates of existing objects.
     #[tracing::instrument(skip(self))]
     pub async fn commit(&mut self, pp: &PostParams) -> Result<(), CommitError>
     where
@@ -314,19 +315,158 @@ impl<'a, K> VacantEntry<'a, K> {
     }
 }
 
+/// How [`Api::update_with_retry`] and [`Api::update_status_with_retry`] retry on write conflicts
+///
+/// Each retry waits twice as long as the previous one, starting at the minimum delay and capped at the maximum delay.
+/// The default makes up to 5 attempts, waiting between 10ms and 1s, similar to `retry.DefaultRetry` in client-go.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ConflictRetry {
+    max_attempts: u32,
+    min_delay: Duration,
+    max_delay: Duration,
+}
+
+impl Default for ConflictRetry {
+    fn default() -> Self {
+        Self {
+            max_attempts: 5,
+            min_delay: Duration::from_millis(10),
+            max_delay: Duration::from_secs(1),
+        }
+    }
+}
+
+impl ConflictRetry {
+    /// Create the default retry configuration
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the maximum number of attempts, including the first one
+    ///
+    /// A value of 0 is treated as 1.
+    #[must_use]
+    pub fn max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts;
+        self
+    }
+
+    /// Set the delay before the first retry, and the cap on the delay of later retries
+    #[must_use]
+    pub fn backoff(mut self, min_delay: Duration, max_delay: Duration) -> Self {
+        self.min_delay = min_delay;
+        self.max_delay = max_delay.max(min_delay);
+        self
+    }
+
+    /// Delay before the given retry, counting from 0
+    fn delay(&self, retry: u32) -> Duration {
+        self.min_delay
+            .saturating_mul(2u32.saturating_pow(retry))
+            .min(self.max_delay)
+    }
+
+    /// Wait before the given retry if `result` is a conflict that should be retried, counting retries from 0
+    async fn backoff_on_conflict<K>(&self, result: &Result<K>, retry: u32) -> bool {
+        match result {
+            Err(Error::Api(status)) if status.is_conflict() && retry + 1 < self.max_attempts => {
+                tracing::debug!(retry, "write conflict, retrying with the latest object");
+                tokio::time::sleep(self.delay(retry)).await;
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+impl<K: Resource + Clone + DeserializeOwned + Serialize + Debug> Api<K> {
+    /// Fetch an object, let `mutate` modify it, and replace it, retrying from the fetch on write conflicts
+    ///
+    /// This is the equivalent of `retry.RetryOnConflict` in client-go: when another client modified the object
+    /// in the meantime, the replace fails with a `409 Conflict`, so the latest object is fetched and `mutate` is run
+    /// again on it. `mutate` may therefore be called several times, and must not rely on earlier calls.
+    ///
+    /// ```no_run
+    /// use kube::api::{Api, PostParams, entry::ConflictRetry};
+    /// use k8s_openapi::api::core::v1::ConfigMap;
+    /// # async fn wrapper(cms: Api<ConfigMap>) -> Result<(), Box<dyn std::error::Error>> {
+    /// let cm = cms
+    ///     .update_with_retry("settings", &PostParams::default(), &ConflictRetry::default(), |cm| {
+    ///         cm.data.get_or_insert_default().insert("mode".into(), "fast".into());
+    ///     })
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns the conflict error of the last attempt when all attempts conflicted,
+    /// and any other error (such as the object not existing) as soon as it happens.
+    pub async fn update_with_retry(
+        &self,
+        name: &str,
+        pp: &PostParams,
+        retry: &ConflictRetry,
+        mut mutate: impl FnMut(&mut K),
+    ) -> Result<K> {
+        let mut retries = 0;
+        loop {
+            let mut object = self.get(name).await?;
+            mutate(&mut object);
+            let result = self.replace(name, pp, &object).await;
+            if !retry.backoff_on_conflict(&result, retries).await {
+                return result;
+            }
+            retries += 1;
+        }
+    }
+
+    /// Fetch an object, let `mutate` modify its status, and replace the status, retrying from the fetch on write conflicts
+    ///
+    /// See [`Api::update_with_retry`]. Only the status of the object is written, through [`Api::replace_status`].
+    ///
+    /// # Errors
+    ///
+    /// Returns the conflict error of the last attempt when all attempts conflicted,
+    /// and any other error (such as the object not existing) as soon as it happens.
+    pub async fn update_status_with_retry(
+        &self,
+        name: &str,
+        pp: &PostParams,
+        retry: &ConflictRetry,
+        mut mutate: impl FnMut(&mut K),
+    ) -> Result<K> {
+        let mut retries = 0;
+        loop {
+            let mut object = self.get_status(name).await?;
+            mutate(&mut object);
+            let result = self.replace_status(name, pp, &object).await;
+            if !retry.backoff_on_conflict(&result, retries).await {
+                return result;
+            }
+            retries += 1;
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use std::collections::BTreeMap;
+    use std::{collections::BTreeMap, pin::pin, time::Duration};
 
+    use http::{Request, Response, StatusCode};
     use k8s_openapi::api::core::v1::ConfigMap;
     use kube_core::{
         ObjectMeta,
         params::{DeleteParams, PostParams},
     };
 
+    use tower_test::mock;
+
     use crate::{
         Api, Client, Error,
-        api::entry::{CommitError, Entry},
+        api::entry::{CommitError, ConflictRetry, Entry},
+        client::Body,
     };
 
     #[tokio::test]
@@ -590,4 +730,128 @@ mod tests {
         api.delete(object_name, &DeleteParams::default()).await?;
         Ok(())
     }
+
+    fn configmap(resource_version: &str, value: &str) -> serde_json::Value {
+        serde_json::json!({
+            "apiVersion": "v1",
+            "kind": "ConfigMap",
+            "metadata": { "name": "cm", "namespace": "default", "resourceVersion": resource_version },
+            "data": { "key": value },
+        })
+    }
+
+    fn conflict() -> Response<Body> {
+        let status = serde_json::json!({ "kind": "Status", "status": "Failure", "reason": "Conflict", "code": 409 });
+        Response::builder()
+            .status(StatusCode::CONFLICT)
+            .body(Body::from(serde_json::to_vec(&status).unwrap()))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn update_with_retry_refetches_on_conflict() {
+        let (mock_service, handle) = mock::pair::<Request<Body>, Response<Body>>();
+        let spawned = tokio::spawn(async move {
+            let mut handle = pin!(handle);
+            for (resource_version, response) in [("1", None), ("2", Some(configmap("3", "value2")))] {
+                let (request, send) = handle.next_request().await.expect("service not called");
+                assert_eq!(request.method(), http::Method::GET);
+                let live = configmap(resource_version, "value");
+                send.send_response(Response::new(Body::from(serde_json::to_vec(&live).unwrap())));
+
+                let (request, send) = handle.next_request().await.expect("service not called");
+                assert_eq!(request.method(), http::Method::PUT);
+                let body = request.into_body().collect_bytes().await.unwrap();
+                let sent: serde_json::Value = serde_json::from_slice(&body).unwrap();
+                assert_eq!(sent, configmap(resource_version, "value2"));
+                send.send_response(match response {
+                    Some(updated) => Response::new(Body::from(serde_json::to_vec(&updated).unwrap())),
+                    None => conflict(),
+                });
+            }
+        });
+
+        let api = Api::<ConfigMap>::default_namespaced(Client::new(mock_service, "default"));
+        let mut calls = 0;
+        let cm = api
+            .update_with_retry("cm", &PostParams::default(), &ConflictRetry::default(), |cm| {
+                calls += 1;
+                cm.data
+                    .get_or_insert_with(BTreeMap::default)
+                    .insert("key".to_string(), "value2".to_string());
+            })
+            .await
+            .unwrap();
+        assert_eq!(calls, 2);
+        assert_eq!(cm.metadata.resource_version.as_deref(), Some("3"));
+        spawned.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn update_with_retry_can_be_spawned() {
+        let (mock_service, handle) = mock::pair::<Request<Body>, Response<Body>>();
+        let spawned = tokio::spawn(async move {
+            let mut handle = pin!(handle);
+            for path in ["/api/v1/namespaces/default/configmaps/cm", "/api/v1/namespaces/default/configmaps/cm/status"] {
+                for _ in 0..2 {
+                    let (request, send) = handle.next_request().await.expect("service not called");
+                    assert_eq!(request.uri().path(), path);
+                    let live = configmap("1", "value");
+                    send.send_response(Response::new(Body::from(serde_json::to_vec(&live).unwrap())));
+                }
+            }
+        });
+
+        let api = Api::<ConfigMap>::default_namespaced(Client::new(mock_service, "default"));
+        let update = tokio::spawn({
+            let api = api.clone();
+            async move {
+                api.update_with_retry("cm", &PostParams::default(), &ConflictRetry::default(), |_| {})
+                    .await
+            }
+        });
+        update.await.unwrap().unwrap();
+        let update_status = tokio::spawn(async move {
+            api.update_status_with_retry("cm", &PostParams::default(), &ConflictRetry::default(), |_| {})
+                .await
+        });
+        update_status.await.unwrap().unwrap();
+        spawned.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn update_status_with_retry_gives_up_after_max_attempts() {
+        let (mock_service, handle) = mock::pair::<Request<Body>, Response<Body>>();
+        let spawned = tokio::spawn(async move {
+            let mut handle = pin!(handle);
+            for _ in 0..2 {
+                let (request, send) = handle.next_request().await.expect("service not called");
+                assert_eq!(request.uri().path(), "/api/v1/namespaces/default/configmaps/cm/status");
+                let live = configmap("1", "value");
+                send.send_response(Response::new(Body::from(serde_json::to_vec(&live).unwrap())));
+
+                let (request, send) = handle.next_request().await.expect("service not called");
+                assert_eq!(request.method(), http::Method::PUT);
+                assert_eq!(request.uri().path(), "/api/v1/namespaces/default/configmaps/cm/status");
+                send.send_response(conflict());
+            }
+        });
+
+        let api = Api::<ConfigMap>::default_namespaced(Client::new(mock_service, "default"));
+        let retry = ConflictRetry::new()
+            .max_attempts(2)
+            .backoff(Duration::from_millis(1), Duration::from_millis(1));
+        let res = api
+            .update_status_with_retry("cm", &PostParams::default(), &retry, |_| ())
+            .await;
+        assert!(matches!(res, Err(Error::Api(status)) if status.is_conflict()));
+        spawned.await.unwrap();
+    }
+
+    #[test]
+    fn conflict_retry_backoff_doubles_up_to_max_delay() {
+        let retry = ConflictRetry::new().backoff(Duration::from_millis(10), Duration::from_millis(50));
+        let delays: Vec<_> = (0..4).map(|retry_nr| retry.delay(retry_nr).as_millis()).collect();
+        assert_eq!(delays, [10, 20, 40, 50]);
+    }
 }
@@ -0,0 +1,257 @@
+use std::fmt::Debug;
+
+use futures::{Stream, TryStreamExt, stream};
+use serde::de::DeserializeOwned;
+
+use crate::{
+    Error, Result,
+    api::{Api, ListParams, ObjectList, PartialObjectMeta},
+};
+use kube_core::response::Status;
+
+/// How [`Api::list_stream`] handles a continue token that expired before the next page was fetched
+///
+/// Continue tokens expire once the snapshot they point into is compacted away by the apiserver,
+/// typically after 5 minutes, so a slow consumer of a large list may run into this.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ExpiredContinue {
+    /// End the stream with the `410 Gone` error
+    #[default]
+    Fail,
+    /// Continue with the inconsistent continue token sent along with the `410 Gone` error
+    ///
+    /// The remaining pages are listed at the latest resource version, so the stream no longer returns a consistent
+    /// snapshot: objects may have changed, been created, or been deleted since the earlier pages were listed.
+    /// The error is still returned when the apiserver does not send an inconsistent continue token, or when the
+    /// inconsistent continue token expires as well.
+    Inconsistent,
+}
+
+impl<K> Api<K>
+where
+    K: Clone + DeserializeOwned + Debug,
+{
+    /// Lazily list the objects matching `lp`, fetching one page at a time
+    ///
+    /// Pages of [`ListParams::limit`] objects are fetched as the stream is consumed, following the continue tokens of
+    /// the apiserver, so large collections never have to be held in memory at once.
+    /// Without a limit, everything is fetched in a single page.
+    ///
+    /// ```no_run
+    /// use kube::api::{Api, ExpiredContinue, ListParams, ResourceExt};
+    /// use k8s_openapi::api::core::v1::Pod;
+    /// use futures::TryStreamExt;
+    /// # async fn wrapper(pods: Api<Pod>) -> Result<(), Box<dyn std::error::Error>> {
+    /// let lp = ListParams::default().limit(100);
+    /// let mut stream = std::pin::pin!(pods.list_stream(&lp, ExpiredContinue::Fail));
+    /// while let Some(pod) = stream.try_next().await? {
+    ///     println!("Found Pod: {}", pod.name_any());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn list_stream(
+        &self,
+        lp: &ListParams,
+        expired: ExpiredContinue,
+    ) -> impl Stream<Item = Result<K>> + use<K> {
+        let api = self.clone();
+        paginate(lp.clone(), expired, async move |lp| api.list(lp).await)
+    }
+
+    /// Lazily list the metadata of the objects matching `lp`, fetching one page at a time
+    ///
+    /// See [`Api::list_stream`].
+    pub fn list_metadata_stream(
+        &self,
+        lp: &ListParams,
+        expired: ExpiredContinue,
+    ) -> impl Stream<Item = Result<PartialObjectMeta<K>>> + use<K> {
+        let api = self.clone();
+        paginate(lp.clone(), expired, async move |lp| api.list_metadata(lp).await)
+    }
+}
+
+/// Follow the continue tokens of the pages returned by `list`, flattening them into a stream of objects
+fn paginate<T: Clone>(
+    lp: ListParams,
+    expired: ExpiredContinue,
+    list: impl AsyncFn(&ListParams) -> Result<ObjectList<T>>,
+) -> impl Stream<Item = Result<T>> {
+    stream::try_unfold((list, Some(lp)), move |(list, lp)| async move {
+        let Some(mut lp) = lp else {
+            return Ok(None);
+        };
+        let mut restarted = false;
+        loop {
+            match list(&lp).await {
+                Ok(page) => {
+                    lp.continue_token = page.metadata.continue_.filter(|token| !token.is_empty());
+                    let next = lp.continue_token.is_some().then_some(lp);
+                    return Ok(Some((stream::iter(page.items.into_iter().map(Ok)), (list, next))));
+                }
+                Err(Error::Api(status)) => match inconsistent_continue(&status) {
+                    Some(token)
+                        if expired == ExpiredContinue::Inconsistent && lp.continue_token.is_some() && !restarted =>
+                    {
+                        tracing::debug!("continue token expired, continuing as an inconsistent list");
+                        lp.continue_token = Some(token);
+                        restarted = true;
+                    }
+                    _ => return Err(Error::Api(status)),
+                },
+                Err(err) => return Err(err),
+            }
+        }
+    })
+    .try_flatten()
+}
+
+/// The inconsistent continue token the apiserver sends along with an expired continue token error
+fn inconsistent_continue(status: &Status) -> Option<String> {
+    if status.code != 410 {
+        return None;
+    }
+    status
+        .metadata
+        .as_ref()
+        .and_then(|meta| meta.continue_.clone())
+        .filter(|token| !token.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::pin::pin;
+
+    use futures::StreamExt;
+    use http::{Request, Response, StatusCode};
+    use k8s_openapi::api::core::v1::ConfigMap;
+    use serde_json::json;
+    use tower_test::mock;
+
+    use crate::{Client, ResourceExt, client::Body};
+
+    fn page(names: &[&str], continue_token: Option<&str>) -> Response<Body> {
+        let items: Vec<_> = names
+            .iter()
+            .map(|name| json!({ "apiVersion": "v1", "kind": "ConfigMap", "metadata": { "name": name } }))
+            .collect();
+        let list = json!({
+            "apiVersion": "v1",
+            "kind": "ConfigMapList",
+            "metadata": { "continue": continue_token, "resourceVersion": "10" },
+            "items": items,
+        });
+        Response::new(Body::from(serde_json::to_vec(&list).unwrap()))
+    }
+
+    fn expired(continue_token: Option<&str>) -> Response<Body> {
+        let status = json!({
+            "kind": "Status",
+            "status": "Failure",
+            "reason": "Expired",
+            "code": 410,
+            "metadata": { "continue": continue_token },
+        });
+        Response::builder()
+            .status(StatusCode::GONE)
+            .body(Body::from(serde_json::to_vec(&status).unwrap()))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn list_stream_follows_continue_tokens_lazily() {
+        let (mock_service, handle) = mock::pair::<Request<Body>, Response<Body>>();
+        let spawned = tokio::spawn(async move {
+            let mut handle = pin!(handle);
+            let (request, send) = handle.next_request().await.expect("service not called");
+            assert_eq!(request.uri().query(), Some("&labelSelector=app%3Dweb&limit=2"));
+            send.send_response(page(&["a", "b"], Some("page2")));
+            let (request, send) = handle.next_request().await.expect("service not called");
+            assert_eq!(request.uri().query(), Some("&labelSelector=app%3Dweb&limit=2&continue=page2"));
+            send.send_response(page(&["c"], None));
+        });
+
+        let api: Api<ConfigMap> = Api::default_namespaced(Client::new(mock_service, "default"));
+        let lp = ListParams::default().labels("app=web").limit(2);
+        let mut stream = pin!(api.list_stream(&lp, ExpiredContinue::Fail));
+        assert_eq!(stream.next().await.unwrap().unwrap().name_any(), "a");
+        assert_eq!(stream.next().await.unwrap().unwrap().name_any(), "b");
+        assert_eq!(stream.next().await.unwrap().unwrap().name_any(), "c");
+        assert!(stream.next().await.is_none());
+        spawned.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn list_stream_continues_inconsistently_when_token_expires() {
+        let (mock_service, handle) = mock::pair::<Request<Body>, Response<Body>>();
+        let spawned = tokio::spawn(async move {
+            let mut handle = pin!(handle);
+            let (_, send) = handle.next_request().await.expect("service not called");
+            send.send_response(page(&["a"], Some("page2")));
+            let (_, send) = handle.next_request().await.expect("service not called");
+            send.send_response(expired(Some("inconsistent")));
+            let (request, send) = handle.next_request().await.expect("service not called");
+            assert_eq!(request.uri().query(), Some("&limit=1&continue=inconsistent"));
+            send.send_response(page(&["b"], None));
+        });
+
+        let api: Api<ConfigMap> = Api::default_namespaced(Client::new(mock_service, "default"));
+        let lp = ListParams::default().limit(1);
+        let names: Vec<_> = api
+            .list_stream(&lp, ExpiredContinue::Inconsistent)
+            .map(|cm| cm.unwrap().name_any())
+            .collect()
+            .await;
+        assert_eq!(names, ["a", "b"]);
+        spawned.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn list_stream_fails_when_inconsistent_token_expires() {
+        let (mock_service, handle) = mock::pair::<Request<Body>, Response<Body>>();
+        let spawned = tokio::spawn(async move {
+            let mut handle = pin!(handle);
+            let (_, send) = handle.next_request().await.expect("service not called");
+            send.send_response(page(&["a"], Some("page2")));
+            let (_, send) = handle.next_request().await.expect("service not called");
+            send.send_response(expired(Some("inconsistent")));
+            let (request, send) = handle.next_request().await.expect("service not called");
+            assert_eq!(request.uri().query(), Some("&limit=1&continue=inconsistent"));
+            send.send_response(expired(Some("inconsistent2")));
+        });
+
+        let api: Api<ConfigMap> = Api::default_namespaced(Client::new(mock_service, "default"));
+        let lp = ListParams::default().limit(1);
+        let results: Vec<_> = api.list_stream(&lp, ExpiredContinue::Inconsistent).collect().await;
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].as_ref().unwrap().name_any(), "a");
+        assert!(matches!(&results[1], Err(Error::Api(status)) if status.code == 410));
+        spawned.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn list_metadata_stream_fails_when_token_expires() {
+        let (mock_service, handle) = mock::pair::<Request<Body>, Response<Body>>();
+        let spawned = tokio::spawn(async move {
+            let mut handle = pin!(handle);
+            let (_, send) = handle.next_request().await.expect("service not called");
+            send.send_response(page(&["a"], Some("page2")));
+            let (_, send) = handle.next_request().await.expect("service not called");
+            send.send_response(expired(Some("inconsistent")));
+        });
+
+        let api: Api<ConfigMap> = Api::default_namespaced(Client::new(mock_service, "default"));
+        let lp = ListParams::default().limit(1);
+        let results: Vec<_> = api
+            .list_metadata_stream(&lp, ExpiredContinue::Fail)
+            .collect()
+            .await;
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].as_ref().unwrap().name_any(), "a");
+        assert!(matches!(&results[1], Err(Error::Api(status)) if status.code == 410));
+        spawned.await.unwrap();
+    }
+}
@@ -18,6 +18,16 @@ pub use subresource::{Evict, EvictParams, Log, LogParams, ScaleSpec, ScaleStatus
 
 mod util;
 
+#[cfg(feature = "jsonpatch")] mod diff;
+#[cfg(feature = "jsonpatch")]
+#[cfg_attr(docsrs, doc(cfg(feature = "jsonpatch")))]
+pub use diff::Diff;
+
+mod list_stream;
+pub use list_stream::ExpiredContinue;
+mod bulk;
+pub use bulk::{BulkItem, BulkParams, BulkResult, BulkSummary};
+
 pub mod entry;
 
 // Re-exports from kube-core
@@ -54,6 +54,14 @@ impl Body {
         Ok(self.collect().await?.to_bytes())
     }
 
+    /// The contents of a body that is already in memory
+    pub(crate) fn bytes(&self) -> Option<&[u8]> {
+        match &self.kind {
+            Kind::Once(bytes) => Some(bytes.as_deref().unwrap_or_default()),
+            Kind::Wrap(..) => None,
+        }
+    }
+
     /// Tries to clone a [`Body`].
     ///
     /// Returns the cloned `Body` when it's [`Kind::Once`].
@@ -25,11 +25,18 @@ use crate::{Client, Config, Error, Result, client::{ConfigExt, retry::RetryPolic
 /// The suggested implementation type is [`crate::client::Body`].
 pub type DynBody = dyn http_body::Body<Data = Bytes, Error = BoxError> + Send + Unpin;
 
+#[cfg(feature = "http2")]
+const HTTP2_KEEP_ALIVE_INTERVAL: Duration = Duration::from_secs(30);
+#[cfg(feature = "http2")]
+const HTTP2_KEEP_ALIVE_TIMEOUT: Duration = Duration::from_secs(20);
+
 /// Builder for [`Client`] instances with customized [tower](`Service`) middleware.
 pub struct ClientBuilder<Svc> {
     service: Svc,
     default_ns: String,
     valid_until: Option<Timestamp>,
+    #[cfg(feature = "cbor")]
+    prefer_cbor: bool,
 }
 
 impl<Svc> ClientBuilder<Svc> {
@@ -45,6 +52,8 @@ impl<Svc> ClientBuilder<Svc> {
             service,
             default_ns: default_namespace.into(),
             valid_until: None,
+            #[cfg(feature = "cbor")]
+            prefer_cbor: false,
         }
     }
 
@@ -54,21 +63,31 @@ impl<Svc> ClientBuilder<Svc> {
             service: stack,
             default_ns,
             valid_until,
+            #[cfg(feature = "cbor")]
+            prefer_cbor,
         } = self;
         ClientBuilder {
             service: layer.layer(stack),
             default_ns,
             valid_until,
+            #[cfg(feature = "cbor")]
+            prefer_cbor,
         }
     }
 
     /// Sets an expiration timestamp for the client.
     pub fn with_valid_until(self, valid_until: Option<Timestamp>) -> Self {
-        ClientBuilder {
-            service: self.service,
-            default_ns: self.default_ns,
-            valid_until,
-        }
+        ClientBuilder { valid_until, ..self }
+    }
+
+    /// Sets whether the client negotiates the CBOR wire format.
+    ///
+    /// See the [`cbor`](crate::client::cbor) module for details.
+    #[cfg(feature = "cbor")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "cbor")))]
+    #[must_use]
+    pub fn with_cbor(self, prefer_cbor: bool) -> Self {
+        ClientBuilder { prefer_cbor, ..self }
     }
 
     /// Build a [`Client`] instance with the current [`Service`] stack.
@@ -80,7 +99,10 @@ impl<Svc> ClientBuilder<Svc> {
         B: http_body::Body<Data = bytes::Bytes> + Send + 'static,
         B::Error: Into<BoxError>,
     {
-        Client::new(self.service, self.default_ns).with_valid_until(self.valid_until)
+        let client = Client::new(self.service, self.default_ns).with_valid_until(self.valid_until);
+        #[cfg(feature = "cbor")]
+        let client = client.with_cbor(self.prefer_cbor);
+        client
     }
 }
 
@@ -230,7 +252,18 @@ where
         connector.set_read_timeout(config.read_timeout);
         connector.set_write_timeout(config.write_timeout);
 
-        hyper_util::client::legacy::Builder::new(TokioExecutor::new()).build(connector)
+        #[cfg_attr(not(feature = "http2"), allow(unused_mut))]
+        let mut builder = hyper_util::client::legacy::Builder::new(TokioExecutor::new());
+        #[cfg(feature = "http2")]
+        if config.http2 {
+            // Detect dead connections, which would otherwise stall every watch multiplexed over them
+            builder
+                .timer(hyper_util::rt::TokioTimer::new())
+                .http2_keep_alive_interval(HTTP2_KEEP_ALIVE_INTERVAL)
+                .http2_keep_alive_timeout(HTTP2_KEEP_ALIVE_TIMEOUT)
+                .http2_keep_alive_while_idle(true);
+        }
+        builder.build(connector)
     };
 
     let stack = ServiceBuilder::new().layer(config.base_uri_layer()).into_inner();
@@ -249,6 +282,7 @@ where
     let service = ServiceBuilder::new()
         .layer(stack)
         .option_layer(config.default_retry.then_some(RetryLayer::new(RetryPolicy::server_retry())))
+        .option_layer(config.rate_limit_layer())
         .option_layer(auth_layer)
         .layer(config.extra_headers_layer()?)
         .layer(
@@ -313,6 +347,8 @@ where
         default_ns,
     )
     .with_valid_until(expiration);
+    #[cfg(feature = "cbor")]
+    let client = client.with_cbor(config.prefer_cbor);
 
     Ok(client)
 }
@@ -0,0 +1,395 @@
+//! CBOR wire format support
+//!
+//! Kubernetes 1.32+ can serve and accept `application/cbor` for all resource types, including custom resources,
+//! which is smaller and faster to process than JSON.
+//!
+//! When enabled through [`Config::prefer_cbor`](crate::Config::prefer_cbor) or [`Client::with_cbor`](crate::Client::with_cbor),
+//! the client requests CBOR responses (and `application/cbor-seq` watch streams), and sends create and replace bodies as CBOR.
+//! JSON is still accepted as a response format, and if the apiserver rejects CBOR with `406 Not Acceptable` or
+//! `415 Unsupported Media Type`, the request is retried as JSON and CBOR is disabled for the client.
+//! Rejections of media types other than CBOR, such as strategic merge patches of custom resources, are returned
+//! as errors and leave CBOR enabled.
+use std::{
+    io::ErrorKind,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+};
+
+use bytes::{Bytes, BytesMut};
+use http::{HeaderValue, Request, StatusCode, header};
+use kube_core::Status;
+use serde::de::{DeserializeOwned, IgnoredAny};
+use tokio_util::codec::Decoder;
+
+/// Media type of the CBOR wire format
+pub const CBOR_MIME: &str = "application/cbor";
+/// Media type of CBOR encoded watch streams
+pub const CBOR_SEQ_MIME: &str = "application/cbor-seq";
+
+const JSON_MIME: &str = "application/json";
+
+/// Whether CBOR should still be negotiated, shared between clones of a client
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Negotiation(Arc<AtomicBool>);
+
+impl Negotiation {
+    pub(crate) fn new(enabled: bool) -> Self {
+        Self(Arc::new(AtomicBool::new(enabled)))
+    }
+
+    pub(crate) fn enabled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    /// Stop negotiating CBOR, after the apiserver rejected it
+    pub(crate) fn disable(&self) {
+        self.0.store(false, Ordering::Relaxed);
+    }
+}
+
+/// What a request negotiated as CBOR, to tell whether a rejection was about CBOR
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Negotiated {
+    /// The body was sent as CBOR
+    body: bool,
+    /// CBOR was accepted as a response format
+    accept: bool,
+}
+
+impl Negotiated {
+    pub(crate) fn of(request: &Request<Vec<u8>>) -> Self {
+        let has_cbor = |name| {
+            request
+                .headers()
+                .get_all(name)
+                .iter()
+                .filter_map(|value| value.to_str().ok())
+                .any(|value| value.contains(CBOR_MIME))
+        };
+        Self {
+            body: has_cbor(header::CONTENT_TYPE),
+            accept: has_cbor(header::ACCEPT),
+        }
+    }
+
+    /// Whether a response with `code` and the error `status` rejected the CBOR negotiated by the request
+    ///
+    /// Only `415 Unsupported Media Type` for a CBOR body and `406 Not Acceptable` for CBOR responses count,
+    /// and only when the media types the apiserver lists as supported do not include CBOR.
+    pub(crate) fn rejected_by(self, code: StatusCode, status: Option<&Status>) -> bool {
+        let Some(status) = status else {
+            return false;
+        };
+        let negotiated = match code {
+            StatusCode::UNSUPPORTED_MEDIA_TYPE => self.body && status.reason == "UnsupportedMediaType",
+            StatusCode::NOT_ACCEPTABLE => self.accept && status.reason == "NotAcceptable",
+            _ => false,
+        };
+        negotiated && !status.message.contains(CBOR_MIME)
+    }
+}
+
+/// Negotiate CBOR for `request`, returning the original request to use for a JSON retry
+///
+/// `accept` is the CBOR media type to request, and is only set if the request has no `Accept` header.
+/// A JSON body is transcoded to CBOR for create and replace requests, patches are always sent as JSON.
+pub(crate) fn negotiate(request: &mut Request<Vec<u8>>, accept: &'static str) -> Request<Vec<u8>> {
+    let mut original = Request::new(request.body().clone());
+    *original.method_mut() = request.method().clone();
+    *original.uri_mut() = request.uri().clone();
+    *original.version_mut() = request.version();
+    *original.headers_mut() = request.headers().clone();
+
+    if !request.headers().contains_key(header::ACCEPT) {
+        let accept = if accept == CBOR_SEQ_MIME {
+            "application/cbor-seq, application/json"
+        } else {
+            "application/cbor, application/json"
+        };
+        request
+            .headers_mut()
+            .insert(header::ACCEPT, HeaderValue::from_static(accept));
+    }
+
+    let is_json = request
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .is_some_and(|content_type| content_type == JSON_MIME);
+    if is_json
+        && !request.body().is_empty()
+        && let Ok(value) = serde_json::from_slice::<serde_json::Value>(request.body())
+    {
+        let mut body = Vec::with_capacity(request.body().len());
+        if ciborium::into_writer(&value, &mut body).is_ok() {
+            *request.body_mut() = body;
+            request
+                .headers_mut()
+                .insert(header::CONTENT_TYPE, HeaderValue::from_static(CBOR_MIME));
+        }
+    }
+    original
+}
+
+/// Whether a response `Content-Type` is a CBOR media type (including `application/cbor-seq`)
+pub(crate) fn is_cbor(content_type: Option<&HeaderValue>) -> bool {
+    content_type
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with(CBOR_MIME))
+}
+
+/// Decode a CBOR response body
+///
+/// The apiserver encodes `[]byte` fields (such as the `data` of a `Secret`) as CBOR byte strings, while their JSON
+/// encoding (and the only one that `k8s-openapi`'s `ByteString` accepts) is a base64 string,
+/// so byte strings are transcoded to base64 strings before deserializing.
+pub(crate) fn decode<T: DeserializeOwned>(body: &[u8]) -> Result<T, ciborium::de::Error<std::io::Error>> {
+    let value: ciborium::Value = ciborium::from_reader(body)?;
+    base64_byte_strings(value)
+        .deserialized()
+        .map_err(|err| ciborium::de::Error::Semantic(None, err.to_string()))
+}
+
+/// Replace the byte strings in `value` by their base64 encoding, and strip its tags
+fn base64_byte_strings(value: ciborium::Value) -> ciborium::Value {
+    use base64::Engine;
+    use ciborium::Value;
+    match value {
+        Value::Bytes(bytes) => Value::Text(base64::engine::general_purpose::STANDARD.encode(bytes)),
+        Value::Array(items) => Value::Array(items.into_iter().map(base64_byte_strings).collect()),
+        Value::Map(entries) => Value::Map(
+            entries
+                .into_iter()
+                .map(|(key, value)| (key, base64_byte_strings(value)))
+                .collect(),
+        ),
+        Value::Tag(_, value) => base64_byte_strings(*value),
+        other => other,
+    }
+}
+
+/// Decode a CBOR encoded [`Status`]
+pub(crate) fn decode_status(body: &[u8]) -> Option<Status> {
+    decode(body).ok()
+}
+
+/// Decoder splitting an `application/cbor-seq` stream into its data items
+#[derive(Default)]
+pub(crate) struct Sequence;
+
+impl Decoder for Sequence {
+    type Error = std::io::Error;
+    type Item = Bytes;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        if src.is_empty() {
+            return Ok(None);
+        }
+        let mut reader = &src[..];
+        match ciborium::from_reader::<IgnoredAny, _>(&mut reader) {
+            Ok(IgnoredAny) => {
+                let consumed = src.len() - reader.len();
+                Ok(Some(src.split_to(consumed).freeze()))
+            }
+            // The item is incomplete, wait for more data
+            Err(ciborium::de::Error::Io(err)) if err.kind() == ErrorKind::UnexpectedEof => Ok(None),
+            Err(err) => Err(std::io::Error::new(ErrorKind::InvalidData, err.to_string())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::TryStreamExt;
+    use http::Response;
+    use k8s_openapi::api::core::v1::ConfigMap;
+    use kube_core::{WatchEvent, params::PostParams};
+    use serde::Serialize;
+    use serde_json::json;
+    use tower_test::mock;
+
+    use crate::{Api, Client, client::Body};
+
+    fn cbor(value: &impl Serialize) -> Vec<u8> {
+        let mut body = Vec::new();
+        ciborium::into_writer(value, &mut body).unwrap();
+        body
+    }
+
+    fn cbor_response(content_type: &'static str, body: Vec<u8>) -> Response<Body> {
+        Response::builder()
+            .header(header::CONTENT_TYPE, content_type)
+            .body(Body::from(body))
+            .unwrap()
+    }
+
+    fn configmap(name: &str) -> serde_json::Value {
+        json!({ "apiVersion": "v1", "kind": "ConfigMap", "metadata": { "name": name } })
+    }
+
+    #[tokio::test]
+    async fn negotiates_cbor_requests_and_watches() {
+        let (mock_service, handle) = mock::pair::<Request<Body>, Response<Body>>();
+        let client = Client::new(mock_service, "default").with_cbor(true);
+        let api: Api<ConfigMap> = Api::default_namespaced(client);
+
+        let spawned = tokio::spawn(async move {
+            let mut handle = std::pin::pin!(handle);
+
+            let (request, send) = handle.next_request().await.expect("service not called");
+            assert_eq!(request.headers()[header::ACCEPT], "application/cbor, application/json");
+            assert_eq!(request.headers()[header::CONTENT_TYPE], CBOR_MIME);
+            let body = request.into_body().collect_bytes().await.unwrap();
+            let created: serde_json::Value = decode(&body).unwrap();
+            assert_eq!(created["metadata"]["name"], "a");
+            send.send_response(cbor_response(CBOR_MIME, cbor(&created)));
+
+            let (request, send) = handle.next_request().await.expect("service not called");
+            assert_eq!(
+                request.headers()[header::ACCEPT],
+                "application/cbor-seq, application/json"
+            );
+            let mut stream = cbor(&json!({ "type": "ADDED", "object": configmap("b") }));
+            stream.extend(cbor(&json!({ "type": "DELETED", "object": configmap("a") })));
+            send.send_response(cbor_response(CBOR_SEQ_MIME, stream));
+        });
+
+        let cm = serde_json::from_value(configmap("a")).unwrap();
+        let created = api.create(&PostParams::default(), &cm).await.unwrap();
+        assert_eq!(created.metadata.name.as_deref(), Some("a"));
+
+        let events = api
+            .watch(&Default::default(), "0")
+            .await
+            .unwrap()
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+        assert!(matches!(&events[0], WatchEvent::Added(cm) if cm.metadata.name.as_deref() == Some("b")));
+        assert!(matches!(&events[1], WatchEvent::Deleted(cm) if cm.metadata.name.as_deref() == Some("a")));
+        spawned.await.unwrap();
+    }
+
+    fn unsupported_media_type(accepted: &str) -> Response<Body> {
+        let status = json!({
+            "kind": "Status",
+            "status": "Failure",
+            "reason": "UnsupportedMediaType",
+            "code": 415,
+            "message": format!("the body of the request was in an unknown format - accepted media types include: {accepted}"),
+        });
+        Response::builder()
+            .status(StatusCode::UNSUPPORTED_MEDIA_TYPE)
+            .header(header::CONTENT_TYPE, JSON_MIME)
+            .body(Body::from(serde_json::to_vec(&status).unwrap()))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn falls_back_to_json_when_rejected() {
+        let (mock_service, handle) = mock::pair::<Request<Body>, Response<Body>>();
+        let client = Client::new(mock_service, "default").with_cbor(true);
+        let api: Api<ConfigMap> = Api::default_namespaced(client);
+
+        let spawned = tokio::spawn(async move {
+            let mut handle = std::pin::pin!(handle);
+            let (request, send) = handle.next_request().await.expect("service not called");
+            assert_eq!(request.headers()[header::CONTENT_TYPE], CBOR_MIME);
+            send.send_response(unsupported_media_type(
+                "application/json, application/yaml, application/vnd.kubernetes.protobuf",
+            ));
+
+            // Retried, and all further requests are sent as JSON
+            for _ in 0..2 {
+                let (request, send) = handle.next_request().await.expect("service not called");
+                assert_eq!(request.headers()[header::CONTENT_TYPE], JSON_MIME);
+                assert!(request.headers().get(header::ACCEPT).is_none());
+                let body = serde_json::to_vec(&configmap("a")).unwrap();
+                send.send_response(Response::new(Body::from(body)));
+            }
+        });
+
+        let cm: ConfigMap = serde_json::from_value(configmap("a")).unwrap();
+        api.create(&PostParams::default(), &cm).await.unwrap();
+        api.create(&PostParams::default(), &cm).await.unwrap();
+        spawned.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn keeps_cbor_when_other_media_types_are_rejected() {
+        use kube_core::{DynamicObject, GroupVersionKind, discovery::ApiResource, params::{Patch, PatchParams}};
+
+        let (mock_service, handle) = mock::pair::<Request<Body>, Response<Body>>();
+        let client = Client::new(mock_service, "default").with_cbor(true);
+        let resource = ApiResource::from_gvk(&GroupVersionKind::gvk("example.com", "v1", "Widget"));
+        let api: Api<DynamicObject> = Api::default_namespaced_with(client, &resource);
+
+        let spawned = tokio::spawn(async move {
+            let mut handle = std::pin::pin!(handle);
+            // Custom resources do not support strategic merge patches
+            let (request, send) = handle.next_request().await.expect("service not called");
+            assert_eq!(
+                request.headers()[header::CONTENT_TYPE],
+                "application/strategic-merge-patch+json"
+            );
+            send.send_response(unsupported_media_type(
+                "application/json-patch+json, application/merge-patch+json, application/apply-patch+yaml",
+            ));
+
+            // Not resent as JSON, and CBOR is still negotiated
+            let (request, send) = handle.next_request().await.expect("service not called");
+            assert_eq!(request.method(), http::Method::GET);
+            assert_eq!(request.headers()[header::ACCEPT], "application/cbor, application/json");
+            let widget = json!({ "apiVersion": "example.com/v1", "kind": "Widget", "metadata": { "name": "a" } });
+            send.send_response(cbor_response(CBOR_MIME, cbor(&widget)));
+        });
+
+        let patch = Patch::Strategic(json!({ "spec": { "size": 1 } }));
+        let err = api.patch("a", &PatchParams::default(), &patch).await.unwrap_err();
+        assert!(matches!(err, crate::Error::Api(status) if status.code == 415));
+        api.get("a").await.unwrap();
+        spawned.await.unwrap();
+    }
+
+    #[test]
+    fn sequence_waits_for_complete_items() {
+        let mut decoder = Sequence;
+        let first = cbor(&json!({ "type": "ADDED" }));
+        let second = cbor(&json!(1));
+        let mut src = BytesMut::from(&first[..first.len() - 1]);
+        assert!(decoder.decode(&mut src).unwrap().is_none());
+        src.extend_from_slice(&first[first.len() - 1..]);
+        src.extend_from_slice(&second);
+        assert_eq!(decoder.decode(&mut src).unwrap().unwrap(), first);
+        assert_eq!(decoder.decode(&mut src).unwrap().unwrap(), second);
+        assert!(src.is_empty());
+    }
+
+    #[test]
+    fn decodes_byte_strings_as_base64() {
+        use ciborium::Value;
+        let text = |text: &str| Value::Text(text.to_string());
+        let secret = Value::Map(vec![
+            (text("apiVersion"), text("v1")),
+            (text("kind"), text("Secret")),
+            (text("metadata"), Value::Map(vec![(text("name"), text("s"))])),
+            (
+                text("data"),
+                Value::Map(vec![(text("key"), Value::Bytes(b"hello".to_vec()))]),
+            ),
+        ]);
+        let secret: k8s_openapi::api::core::v1::Secret = decode(&cbor(&secret)).unwrap();
+        assert_eq!(secret.data.unwrap()["key"].0, b"hello");
+    }
+
+    #[test]
+    fn decodes_self_described_cbor() {
+        // Kubernetes prefixes CBOR bodies with the self-described CBOR tag
+        let mut body = vec![0xd9, 0xd9, 0xf7];
+        body.extend(cbor(&configmap("a")));
+        let cm: ConfigMap = decode(&body).unwrap();
+        assert_eq!(cm.metadata.name.as_deref(), Some("a"));
+    }
+}
@@ -381,7 +381,7 @@ impl Client {
             .list(lp)
             .map_err(Error::BuildRequest)?;
         req.extensions_mut().insert("list");
-        self.request::<ObjectList<K>>(req).await
+        self.request_list::<K>(req).await?.into_list().await
     }
 }
 
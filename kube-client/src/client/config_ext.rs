@@ -10,7 +10,7 @@ use tower::{filter::AsyncFilterLayer, util::Either};
 #[cfg(any(feature = "rustls-tls", feature = "openssl-tls"))] use super::tls;
 use super::{
     auth::Auth,
-    middleware::{AddAuthorizationLayer, AuthLayer, BaseUriLayer, ExtraHeadersLayer},
+    middleware::{AddAuthorizationLayer, AuthLayer, BaseUriLayer, ExtraHeadersLayer, RateLimitLayer},
 };
 use crate::{Config, Error, Result};
 
@@ -29,6 +29,9 @@ pub trait ConfigExt: private::Sealed {
     /// Layer to add non-authn HTTP headers depending on the config.
     fn extra_headers_layer(&self) -> Result<ExtraHeadersLayer>;
 
+    /// Optional layer to throttle requests to the configured [`qps`](Config::qps) and [`burst`](Config::burst).
+    fn rate_limit_layer(&self) -> Option<RateLimitLayer>;
+
     /// Create [`hyper_rustls::HttpsConnector`] based on config.
     ///
     /// # Example
@@ -333,6 +336,38 @@ mod openssl_tls_server_name_tests {
             .await
             .expect("handshake should succeed when accept_invalid_certs disables verification");
     }
+
+    // With http2 set, h2 is offered through ALPN and the connection is marked as negotiated h2.
+    #[cfg(feature = "http2")]
+    #[tokio::test]
+    async fn http2_is_negotiated_through_alpn() {
+        use hyper_util::client::legacy::connect::Connection as _;
+
+        let (cert, key) = self_signed_cert(San::Ip("127.0.0.1"));
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let mut acceptor = SslAcceptor::mozilla_intermediate(SslMethod::tls()).unwrap();
+        acceptor.set_private_key(&key).unwrap();
+        acceptor.set_certificate(&cert).unwrap();
+        acceptor.set_alpn_select_callback(|_ssl, client| {
+            openssl::ssl::select_next_proto(b"\x02h2", client).ok_or(openssl::ssl::AlpnError::NOACK)
+        });
+        let acceptor = acceptor.build();
+        std::thread::spawn(move || {
+            if let Ok((stream, _)) = listener.accept() {
+                // Keep the connection open until the client has inspected it
+                let _stream = acceptor.accept(stream);
+                std::thread::sleep(std::time::Duration::from_secs(1));
+            }
+        });
+
+        let mut config = config_for(port, &cert, None);
+        config.http2 = true;
+        let uri: http::Uri = config.cluster_url.clone();
+
+        let stream = connector_for(&config).oneshot(uri).await.unwrap();
+        assert!(stream.connected().is_negotiated_h2());
+    }
 }
 
 mod private {
@@ -361,6 +396,12 @@ impl ConfigExt for Config {
         })
     }
 
+    fn rate_limit_layer(&self) -> Option<RateLimitLayer> {
+        self.qps
+            .filter(|qps| *qps > 0.0)
+            .map(|qps| RateLimitLayer::new(qps, self.burst))
+    }
+
     fn extra_headers_layer(&self) -> Result<ExtraHeadersLayer> {
         let mut headers = self.headers.clone();
         if let Some(impersonate_user) = &self.auth_info.impersonate {
@@ -443,7 +484,13 @@ impl ConfigExt for Config {
                     .map_err(Error::RustlsTls)?,
             ));
         }
-        Ok(builder.enable_http1().wrap_connector(connector))
+        let builder = builder.enable_http1();
+        #[cfg(feature = "http2")]
+        if self.http2 {
+            // Offers h2 ahead of http/1.1 through ALPN
+            return Ok(builder.enable_http2().wrap_connector(connector));
+        }
+        Ok(builder.wrap_connector(connector))
     }
 
     #[cfg(feature = "openssl-tls")]
@@ -455,8 +502,15 @@ impl ConfigExt for Config {
 
         // tls_server_name has no hook on the builder; it is applied per-connection in
         // openssl_https_connector_with_connector instead.
-        tls::openssl_tls::ssl_connector_builder(identity.as_ref(), self.root_cert.as_ref())
-            .map_err(|e| Error::OpensslTls(tls::openssl_tls::Error::CreateSslConnector(e)))
+        #[cfg_attr(not(feature = "http2"), allow(unused_mut))]
+        let mut builder = tls::openssl_tls::ssl_connector_builder(identity.as_ref(), self.root_cert.as_ref())
+            .map_err(|e| Error::OpensslTls(tls::openssl_tls::Error::CreateSslConnector(e)))?;
+        #[cfg(feature = "http2")]
+        if self.http2 {
+            tls::openssl_tls::offer_http2(&mut builder)
+                .map_err(|e| Error::OpensslTls(tls::openssl_tls::Error::CreateSslConnector(e)))?;
+        }
+        Ok(builder)
     }
 
     #[cfg(feature = "openssl-tls")]
@@ -0,0 +1,1076 @@
+//! An in-memory fake apiserver for testing
+//!
+//! [`FakeApiServer`] is a [`tower::Service`] that implements a simplified subset of the Kubernetes API
+//! semantics on top of an in-memory object store, so that code using a [`Client`] can be tested
+//! end-to-end without a cluster, and without scripting every response by hand.
+//!
+//! Supported operations:
+//! - get, list, create, replace, delete and delete collection
+//! - merge, JSON, strategic merge (treated as a merge patch) and server-side apply (treated as an
+//!   upsert with merge patch semantics, without field ownership) patches
+//! - watches from a `resourceVersion`, including `sendInitialEvents` streaming lists
+//! - label and field selectors on lists and watches
+//! - the `status` subresource: writes to the main resource keep the stored status, and writes to
+//!   `status` only change the status
+//! - `metadata.generation` bumps on spec changes, `resourceVersion` conflicts on replace,
+//!   `generateName`, and finalizer-blocked deletion
+//! - `dryRun=All` on every write, and `uid` and `resourceVersion` preconditions on deletes
+//!
+//! Resources do not need to be registered up front; any path that looks like a Kubernetes resource
+//! path is accepted, and every resource is assumed to have a `status` subresource. Scope is inferred from
+//! the request path, and watches only consider the state of an object after each change
+//! (objects that stop matching a selector are not reported as deleted).
+//!
+//! ```
+//! use k8s_openapi::api::core::v1::ConfigMap;
+//! use kube_client::{Api, api::{Patch, PatchParams}};
+//! use kube_client::client::fake::FakeApiServer;
+//!
+//! # async fn wrapper() -> Result<(), Box<dyn std::error::Error>> {
+//! let server = FakeApiServer::new().with_yaml(
+//!     "
+//! apiVersion: v1
+//! kind: ConfigMap
+//! metadata:
+//!   name: settings
+//!   namespace: default
+//! data:
+//!   mode: fast
+//! ",
+//! );
+//! let cms: Api<ConfigMap> = Api::default_namespaced(server.client());
+//!
+//! let patch = serde_json::json!({ "data": { "mode": "slow" } });
+//! cms.patch("settings", &PatchParams::default(), &Patch::Merge(patch)).await?;
+//! let cm = cms.get("settings").await?;
+//! assert_eq!(cm.data.unwrap()["mode"], "slow");
+//! # Ok(())
+//! # }
+//! ```
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    convert::Infallible,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+};
+
+use bytes::Bytes;
+use futures::{channel::mpsc, future::BoxFuture};
+use http::{Method, Request, Response, StatusCode, header};
+use http_body::Frame;
+use http_body_util::StreamBody;
+use kube_core::{ApiResource, GroupVersionKind, Resource, Status, WatchEvent};
+use serde::Serialize;
+use serde_json::{Value, json};
+use tower::Service;
+
+use super::{Body, Client};
+
+/// An in-memory fake apiserver that can be used as a [`Client`] backend
+///
+/// Cloning will produce a new reference to the same server state.
+///
+/// See the [module documentation](self) for the supported semantics.
+#[derive(Clone, Default)]
+pub struct FakeApiServer {
+    state: Arc<Mutex<State>>,
+}
+
+impl std::fmt::Debug for FakeApiServer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FakeApiServer").finish_non_exhaustive()
+    }
+}
+
+impl FakeApiServer {
+    /// Create an empty fake apiserver
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Seed the server with a typed object
+    ///
+    /// # Panics
+    ///
+    /// Panics if the object cannot be serialized, or if it has no name.
+    #[must_use]
+    pub fn with_object<K>(self, obj: &K) -> Self
+    where
+        K: Resource + Serialize,
+        K::DynamicType: Default,
+    {
+        let resource = ApiResource::erase::<K>(&K::DynamicType::default());
+        let mut value = serde_json::to_value(obj).expect("object must be serializable");
+        value["apiVersion"] = json!(resource.api_version);
+        value["kind"] = json!(resource.kind);
+        self.seed(&resource, value);
+        self
+    }
+
+    /// Seed the server with objects from a (multi-document) YAML string
+    ///
+    /// The resource for each document is derived from its `apiVersion` and `kind`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the YAML is invalid, or if a document is missing its `apiVersion`, `kind` or name.
+    #[must_use]
+    pub fn with_yaml(self, yaml: &str) -> Self {
+        let docs: Vec<Value> = serde_saphyr::from_multiple(yaml).expect("seed yaml must be valid");
+        for value in docs.into_iter().filter(|doc| !doc.is_null()) {
+            let api_version = value["apiVersion"].as_str().expect("seed object must have apiVersion");
+            let kind = value["kind"].as_str().expect("seed object must have kind");
+            let (group, version) = api_version.split_once('/').unwrap_or(("", api_version));
+            let resource = ApiResource::from_gvk(&GroupVersionKind::gvk(group, version, kind));
+            self.seed(&resource, value);
+        }
+        self
+    }
+
+    fn seed(&self, resource: &ApiResource, value: Value) {
+        let namespace = value["metadata"]["namespace"].as_str().map(String::from);
+        let target = Target {
+            key: ResourceKey::new(&resource.group, &resource.plural),
+            api_version: resource.api_version.clone(),
+            namespace,
+            name: None,
+            subresource: None,
+        };
+        let mut state = self.state.lock().unwrap();
+        state.kinds.insert(target.key.clone(), resource.kind.clone());
+        if let Err(status) = state.create(&target, value) {
+            panic!("failed to seed object: {}", status.message);
+        }
+    }
+
+    /// Expire the watch history
+    ///
+    /// Watches that start from a `resourceVersion` older than the current one will fail with `410 Gone`,
+    /// as if the apiserver had compacted its history.
+    pub fn compact(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.history.clear();
+        state.compacted = state.resource_version;
+    }
+
+    /// A [`Client`] that sends all requests to this server
+    pub fn client(&self) -> Client {
+        Client::new(self.clone(), "default")
+    }
+}
+
+impl Service<Request<Body>> for FakeApiServer {
+    type Error = Infallible;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+    type Response = Response<Body>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, req: Request<Body>) -> Self::Future {
+        let state = self.state.clone();
+        Box::pin(async move {
+            let (parts, body) = req.into_parts();
+            let body = body.collect_bytes().await.unwrap_or_default();
+            let content_type = parts
+                .headers
+                .get(header::CONTENT_TYPE)
+                .and_then(|value| value.to_str().ok())
+                .unwrap_or_default();
+            let query = Query::parse(parts.uri.query());
+            let response = match Target::parse(parts.uri.path()) {
+                Some(target) => {
+                    let mut state = state.lock().unwrap();
+                    state.handle(&parts.method, &target, &query, content_type, &body)
+                }
+                None => Err(status(StatusCode::NOT_FOUND, "NotFound", "the server could not find the requested resource")),
+            };
+            Ok(match response {
+                Ok(Reply::Object(code, value)) => json_response(code, &value),
+                Ok(Reply::Watch(body)) => Response::new(body),
+                Err(status) => json_response(
+                    StatusCode::from_u16(status.code).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR),
+                    &*status,
+                ),
+            })
+        })
+    }
+}
+
+fn json_response(code: StatusCode, value: &impl Serialize) -> Response<Body> {
+    let mut response = Response::new(Body::from(serde_json::to_vec(value).unwrap_or_default()));
+    *response.status_mut() = code;
+    response
+        .headers_mut()
+        .insert(header::CONTENT_TYPE, header::HeaderValue::from_static("application/json"));
+    response
+}
+
+fn status(code: StatusCode, reason: &str, message: &str) -> Box<Status> {
+    Status::failure(message, reason).with_code(code.as_u16()).boxed()
+}
+
+/// A resource, identified independently of its version
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct ResourceKey {
+    group: String,
+    plural: String,
+}
+
+impl ResourceKey {
+    fn new(group: &str, plural: &str) -> Self {
+        Self {
+            group: group.to_string(),
+            plural: plural.to_string(),
+        }
+    }
+}
+
+/// The resource and object that a request path refers to
+struct Target {
+    key: ResourceKey,
+    api_version: String,
+    namespace: Option<String>,
+    name: Option<String>,
+    subresource: Option<String>,
+}
+
+impl Target {
+    fn parse(path: &str) -> Option<Self> {
+        let segments = path.trim_matches('/').split('/').collect::<Vec<_>>();
+        let (group, version, rest) = match segments.as_slice() {
+            ["api", version, rest @ ..] => ("", *version, rest),
+            ["apis", group, version, rest @ ..] => (*group, *version, rest),
+            _ => return None,
+        };
+        let (namespace, rest) = match rest {
+            ["namespaces", namespace, rest @ ..] if !rest.is_empty() => (Some(namespace.to_string()), rest),
+            _ => (None, rest),
+        };
+        let (plural, rest) = rest.split_first()?;
+        Some(Self {
+            key: ResourceKey::new(group, plural),
+            api_version: if group.is_empty() {
+                version.to_string()
+            } else {
+                format!("{group}/{version}")
+            },
+            namespace,
+            name: rest.first().map(ToString::to_string),
+            subresource: rest.get(1).map(ToString::to_string),
+        })
+    }
+
+    fn object_key(&self, name: &str) -> ObjectKey {
+        (self.namespace.clone(), name.to_string())
+    }
+
+    fn is_status(&self) -> bool {
+        self.subresource.as_deref() == Some("status")
+    }
+}
+
+#[derive(Default)]
+struct Query(BTreeMap<String, String>);
+
+impl Query {
+    fn parse(query: Option<&str>) -> Self {
+        Self(form_urlencoded::parse(query.unwrap_or_default().as_bytes()).into_owned().collect())
+    }
+
+    fn get(&self, key: &str) -> Option<&str> {
+        self.0.get(key).map(String::as_str).filter(|value| !value.is_empty())
+    }
+
+    fn flag(&self, key: &str) -> bool {
+        matches!(self.get(key), Some("true" | "1"))
+    }
+}
+
+enum Reply {
+    Object(StatusCode, Value),
+    Watch(Body),
+}
+
+type ObjectKey = (Option<String>, String);
+
+struct HistoryEvent {
+    resource_version: u64,
+    key: ResourceKey,
+    event: WatchEvent<Value>,
+}
+
+struct Watcher {
+    key: ResourceKey,
+    filter: Filter,
+    tx: mpsc::UnboundedSender<Result<Frame<Bytes>, Infallible>>,
+}
+
+#[derive(Default)]
+struct State {
+    objects: BTreeMap<ResourceKey, BTreeMap<ObjectKey, Value>>,
+    /// The kind of each resource that has been seen, used for lists and bookmarks
+    kinds: BTreeMap<ResourceKey, String>,
+    resource_version: u64,
+    history: Vec<HistoryEvent>,
+    /// The newest `resourceVersion` that is no longer in `history`
+    compacted: u64,
+    watchers: Vec<Watcher>,
+    /// Counter used for generating unique names and uids
+    serial: u64,
+    /// Whether the request being handled is a dry run, whose writes are computed but not stored
+    dry_run: bool,
+}
+
+impl State {
+    fn handle(
+        &mut self,
+        method: &Method,
+        target: &Target,
+        query: &Query,
+        content_type: &str,
+        body: &[u8],
+    ) -> Result<Reply, Box<Status>> {
+        let ok = |value| Ok(Reply::Object(StatusCode::OK, value));
+        // Deletes send their options in the body, rather than in the query
+        let delete_options = match method {
+            &Method::DELETE if !body.is_empty() => parse_body(body)?,
+            _ => Value::Null,
+        };
+        self.dry_run = query.get("dryRun") == Some("All")
+            || delete_options["dryRun"]
+                .as_array()
+                .is_some_and(|dry_run| dry_run.iter().any(|mode| mode == "All"));
+        let preconditions = &delete_options["preconditions"];
+        match (method, target.name.as_deref()) {
+            (&Method::GET, Some(name)) => ok(self.get(target, name)?.clone()),
+            (&Method::GET, None) if query.flag("watch") => self.watch(target, query),
+            (&Method::GET, None) => ok(self.list(target, query)?),
+            (&Method::POST, None) => {
+                let value = self.create(target, parse_body(body)?)?;
+                Ok(Reply::Object(StatusCode::CREATED, value))
+            }
+            (&Method::PUT, Some(name)) => ok(self.replace(target, name, parse_body(body)?)?),
+            (&Method::PATCH, Some(name)) => ok(self.patch(target, name, content_type, parse_body(body)?)?),
+            (&Method::DELETE, Some(name)) => ok(self.delete(target, name, preconditions)?),
+            (&Method::DELETE, None) => {
+                let names = self
+                    .matching(target, &Filter::from_query(query)?)
+                    .map(|(key, _)| key.1.clone())
+                    .collect::<Vec<_>>();
+                let items = names
+                    .iter()
+                    .map(|name| self.delete(target, name, preconditions))
+                    .collect::<Result<Vec<_>, _>>()?;
+                ok(self.list_value(target, items))
+            }
+            _ => Err(status(
+                StatusCode::METHOD_NOT_ALLOWED,
+                "MethodNotAllowed",
+                "the server does not allow this method on the requested resource",
+            )),
+        }
+    }
+
+    fn get(&self, target: &Target, name: &str) -> Result<&Value, Box<Status>> {
+        self.objects
+            .get(&target.key)
+            .and_then(|objects| objects.get(&target.object_key(name)))
+            .ok_or_else(|| not_found(target, name))
+    }
+
+    fn matching<'a>(&'a self, target: &'a Target, filter: &'a Filter) -> impl Iterator<Item = (&'a ObjectKey, &'a Value)> {
+        self.objects
+            .get(&target.key)
+            .into_iter()
+            .flatten()
+            .filter(move |((namespace, _), value)| {
+                (target.namespace.is_none() || *namespace == target.namespace) && filter.matches(value)
+            })
+    }
+
+    fn list(&self, target: &Target, query: &Query) -> Result<Value, Box<Status>> {
+        let filter = Filter::from_query(query)?;
+        let items = self.matching(target, &filter).map(|(_, value)| value.clone()).collect();
+        Ok(self.list_value(target, items))
+    }
+
+    fn list_value(&self, target: &Target, items: Vec<Value>) -> Value {
+        let kind = self
+            .kinds
+            .get(&target.key)
+            .map_or_else(|| "List".to_string(), |kind| format!("{kind}List"));
+        json!({
+            "apiVersion": target.api_version,
+            "kind": kind,
+            "metadata": { "resourceVersion": self.resource_version.to_string() },
+            "items": items,
+        })
+    }
+
+    fn watch(&mut self, target: &Target, query: &Query) -> Result<Reply, Box<Status>> {
+        let filter = Filter::from_query(query)?;
+        let (tx, rx) = mpsc::unbounded();
+        let send = |event: &WatchEvent<Value>| {
+            let mut line = serde_json::to_vec(event).unwrap_or_default();
+            line.push(b'\n');
+            let _ = tx.unbounded_send(Ok(Frame::data(Bytes::from(line))));
+        };
+
+        let initial_events = query.flag("sendInitialEvents");
+        match query.get("resourceVersion") {
+            _ if initial_events => {
+                for (_, value) in self.matching(target, &filter) {
+                    send(&WatchEvent::Added(value.clone()));
+                }
+                send(&WatchEvent::Bookmark(serde_json::from_value(self.bookmark(target, true)).unwrap()));
+            }
+            None | Some("0") => {
+                for (_, value) in self.matching(target, &filter) {
+                    send(&WatchEvent::Added(value.clone()));
+                }
+            }
+            Some(resource_version) => {
+                let resource_version = resource_version.parse::<u64>().map_err(|_| {
+                    status(StatusCode::BAD_REQUEST, "BadRequest", "invalid resourceVersion")
+                })?;
+                if resource_version < self.compacted {
+                    let expired = status(
+                        StatusCode::GONE,
+                        "Expired",
+                        &format!("too old resource version: {resource_version} ({})", self.compacted),
+                    );
+                    send(&WatchEvent::Error(expired));
+                    return Ok(Reply::Watch(Body::wrap_body(StreamBody::new(rx))));
+                }
+                for event in &self.history {
+                    if event.resource_version > resource_version
+                        && event.key == target.key
+                        && watched(target, &filter, &event.event)
+                    {
+                        send(&event.event);
+                    }
+                }
+            }
+        }
+
+        self.watchers.push(Watcher {
+            key: target.key.clone(),
+            filter: Filter {
+                namespace: target.namespace.clone(),
+                ..filter
+            },
+            tx,
+        });
+        Ok(Reply::Watch(Body::wrap_body(StreamBody::new(rx))))
+    }
+
+    fn bookmark(&self, target: &Target, initial_events_end: bool) -> Value {
+        let mut annotations = serde_json::Map::new();
+        if initial_events_end {
+            annotations.insert("k8s.io/initial-events-end".to_string(), json!("true"));
+        }
+        json!({
+            "apiVersion": target.api_version,
+            "kind": self.kinds.get(&target.key).cloned().unwrap_or_default(),
+            "metadata": {
+                "resourceVersion": self.resource_version.to_string(),
+                "annotations": annotations,
+            },
+        })
+    }
+
+    fn create(&mut self, target: &Target, mut value: Value) -> Result<Value, Box<Status>> {
+        if !value["metadata"].is_object() {
+            value["metadata"] = json!({});
+        }
+        let name = match (value["metadata"]["name"].as_str(), value["metadata"]["generateName"].as_str()) {
+            (Some(name), _) => name.to_string(),
+            (None, Some(prefix)) => {
+                self.serial += 1;
+                format!("{prefix}{:05x}", self.serial)
+            }
+            (None, None) => {
+                return Err(status(
+                    StatusCode::UNPROCESSABLE_ENTITY,
+                    "Invalid",
+                    "metadata.name: Required value: name or generateName is required",
+                ));
+            }
+        };
+        let object_key = target.object_key(&name);
+        if self
+            .objects
+            .get(&target.key)
+            .is_some_and(|objects| objects.contains_key(&object_key))
+        {
+            return Err(status(
+                StatusCode::CONFLICT,
+                "AlreadyExists",
+                &format!("{} \"{name}\" already exists", target.key.plural),
+            ));
+        }
+        if let Some(kind) = value["kind"].as_str() {
+            self.kinds.insert(target.key.clone(), kind.to_string());
+        }
+
+        self.serial += 1;
+        let meta = &mut value["metadata"];
+        meta["name"] = json!(name);
+        meta["uid"] = json!(format!("00000000-0000-0000-0000-{:012x}", self.serial));
+        meta["creationTimestamp"] = json!(now());
+        meta["generation"] = json!(1);
+        match &target.namespace {
+            Some(namespace) => meta["namespace"] = json!(namespace),
+            None => {
+                if let Some(meta) = meta.as_object_mut() {
+                    meta.remove("namespace");
+                }
+            }
+        }
+        if let Some(meta) = meta.as_object_mut() {
+            meta.remove("deletionTimestamp");
+        }
+        Ok(self.store(target, object_key, value, Change::Added))
+    }
+
+    fn replace(&mut self, target: &Target, name: &str, value: Value) -> Result<Value, Box<Status>> {
+        let old = self.get(target, name)?.clone();
+        if let Some(resource_version) = value["metadata"]["resourceVersion"].as_str()
+            && Some(resource_version) != old["metadata"]["resourceVersion"].as_str()
+        {
+            return Err(status(
+                StatusCode::CONFLICT,
+                "Conflict",
+                &format!(
+                    "Operation cannot be fulfilled on {} \"{name}\": the object has been modified; please apply your changes to the latest version and try again",
+                    target.key.plural
+                ),
+            ));
+        }
+        self.update(target, name, old, value)
+    }
+
+    fn patch(&mut self, target: &Target, name: &str, content_type: &str, patch: Value) -> Result<Value, Box<Status>> {
+        let invalid = |err: &dyn std::fmt::Display| {
+            status(StatusCode::UNPROCESSABLE_ENTITY, "Invalid", &err.to_string())
+        };
+        let old = match self.get(target, name) {
+            Ok(old) => old.clone(),
+            // Server-side apply creates objects that do not exist yet
+            Err(_) if content_type.starts_with("application/apply-patch") && !target.is_status() => {
+                let mut value = patch;
+                value["metadata"]["name"] = json!(name);
+                let value = self.create(target, value)?;
+                return Ok(value);
+            }
+            Err(err) => return Err(err),
+        };
+        let mut value = old.clone();
+        if content_type.starts_with("application/json-patch") {
+            let patch: json_patch::Patch = serde_json::from_value(patch).map_err(|err| invalid(&err))?;
+            json_patch::patch(&mut value, &patch).map_err(|err| invalid(&err))?;
+        } else {
+            // Merge, strategic merge and apply patches are all treated as merge patches
+            json_patch::merge(&mut value, &patch);
+        }
+        self.update(target, name, old, value)
+    }
+
+    /// Replace `old` with `value`, preserving server-managed fields
+    fn update(&mut self, target: &Target, name: &str, old: Value, mut value: Value) -> Result<Value, Box<Status>> {
+        if target.is_status() {
+            // Only the status can be changed through the status subresource
+            let status = value["status"].take();
+            value = old.clone();
+            value["status"] = status;
+        } else {
+            value["status"] = old["status"].clone();
+            if spec_changed(&old, &value) {
+                let generation = old["metadata"]["generation"].as_i64().unwrap_or(1);
+                value["metadata"]["generation"] = json!(generation + 1);
+            } else {
+                value["metadata"]["generation"] = old["metadata"]["generation"].clone();
+            }
+        }
+        if value["status"].is_null()
+            && let Some(obj) = value.as_object_mut()
+        {
+            obj.remove("status");
+        }
+        for field in ["name", "namespace", "uid", "creationTimestamp", "deletionTimestamp"] {
+            match old["metadata"].get(field) {
+                Some(old_value) => value["metadata"][field] = old_value.clone(),
+                None => {
+                    if let Some(meta) = value["metadata"].as_object_mut() {
+                        meta.remove(field);
+                    }
+                }
+            }
+        }
+
+        let object_key = target.object_key(name);
+        if value["metadata"]["deletionTimestamp"].is_string() && finalizers(&value).is_empty() {
+            // The last finalizer was removed from an object that is being deleted
+            return Ok(self.store(target, object_key, value, Change::Deleted));
+        }
+        if value == old {
+            return Ok(old);
+        }
+        Ok(self.store(target, object_key, value, Change::Modified))
+    }
+
+    fn delete(&mut self, target: &Target, name: &str, preconditions: &Value) -> Result<Value, Box<Status>> {
+        let mut value = self.get(target, name)?.clone();
+        for (precondition, field) in [("uid", "UID"), ("resourceVersion", "ResourceVersion")] {
+            if let Some(expected) = preconditions[precondition].as_str()
+                && Some(expected) != value["metadata"][precondition].as_str()
+            {
+                let actual = value["metadata"][precondition].as_str().unwrap_or_default();
+                return Err(status(
+                    StatusCode::CONFLICT,
+                    "Conflict",
+                    &format!(
+                        "Precondition failed: {field} in precondition: {expected}, {field} in object meta: {actual}"
+                    ),
+                ));
+            }
+        }
+        let object_key = target.object_key(name);
+        if finalizers(&value).is_empty() {
+            return Ok(self.store(target, object_key, value, Change::Deleted));
+        }
+        if value["metadata"]["deletionTimestamp"].is_string() {
+            return Ok(value);
+        }
+        value["metadata"]["deletionTimestamp"] = json!(now());
+        Ok(self.store(target, object_key, value, Change::Modified))
+    }
+
+    /// Write an object at a new `resourceVersion`, and notify watchers
+    ///
+    /// Dry runs return the object that would have been written, at its current `resourceVersion`.
+    fn store(&mut self, target: &Target, object_key: ObjectKey, mut value: Value, change: Change) -> Value {
+        if self.dry_run {
+            let current = self
+                .objects
+                .get(&target.key)
+                .and_then(|objects| objects.get(&object_key))
+                .map(|current| current["metadata"]["resourceVersion"].clone());
+            match current {
+                Some(resource_version) => value["metadata"]["resourceVersion"] = resource_version,
+                None => {
+                    if let Some(meta) = value["metadata"].as_object_mut() {
+                        meta.remove("resourceVersion");
+                    }
+                }
+            }
+            return value;
+        }
+        self.resource_version += 1;
+        value["metadata"]["resourceVersion"] = json!(self.resource_version.to_string());
+        let objects = self.objects.entry(target.key.clone()).or_default();
+        let event = match change {
+            Change::Added => {
+                objects.insert(object_key, value.clone());
+                WatchEvent::Added(value.clone())
+            }
+            Change::Modified => {
+                objects.insert(object_key, value.clone());
+                WatchEvent::Modified(value.clone())
+            }
+            Change::Deleted => {
+                objects.remove(&object_key);
+                WatchEvent::Deleted(value.clone())
+            }
+        };
+
+        let mut line = serde_json::to_vec(&event).unwrap_or_default();
+        line.push(b'\n');
+        let line = Bytes::from(line);
+        self.watchers.retain(|watcher| {
+            if watcher.key != target.key || !watcher.filter.matches(&value) {
+                return !watcher.tx.is_closed();
+            }
+            watcher.tx.unbounded_send(Ok(Frame::data(line.clone()))).is_ok()
+        });
+        self.history.push(HistoryEvent {
+            resource_version: self.resource_version,
+            key: target.key.clone(),
+            event,
+        });
+        value
+    }
+}
+
+enum Change {
+    Added,
+    Modified,
+    Deleted,
+}
+
+fn parse_body(body: &[u8]) -> Result<Value, Box<Status>> {
+    serde_json::from_slice(body).map_err(|err| {
+        status(
+            StatusCode::BAD_REQUEST,
+            "BadRequest",
+            &format!("invalid request body: {err}"),
+        )
+    })
+}
+
+fn not_found(target: &Target, name: &str) -> Box<Status> {
+    status(
+        StatusCode::NOT_FOUND,
+        "NotFound",
+        &format!("{} \"{name}\" not found", target.key.plural),
+    )
+}
+
+fn now() -> String {
+    jiff::Timestamp::now().strftime("%Y-%m-%dT%H:%M:%SZ").to_string()
+}
+
+fn finalizers(value: &Value) -> &[Value] {
+    value["metadata"]["finalizers"].as_array().map_or(&[], Vec::as_slice)
+}
+
+/// Whether anything but the metadata and status has changed
+fn spec_changed(old: &Value, new: &Value) -> bool {
+    let strip = |value: &Value| {
+        let mut value = value.clone();
+        if let Some(obj) = value.as_object_mut() {
+            obj.remove("metadata");
+            obj.remove("status");
+        }
+        value
+    };
+    strip(old) != strip(new)
+}
+
+fn watched(target: &Target, filter: &Filter, event: &WatchEvent<Value>) -> bool {
+    match event {
+        WatchEvent::Added(value) | WatchEvent::Modified(value) | WatchEvent::Deleted(value) => {
+            (target.namespace.is_none()
+                || value["metadata"]["namespace"].as_str() == target.namespace.as_deref())
+                && filter.matches(value)
+        }
+        WatchEvent::Bookmark(_) | WatchEvent::Error(_) => false,
+    }
+}
+
+/// Label and field selectors (and namespace for watches)
+#[derive(Default)]
+struct Filter {
+    namespace: Option<String>,
+    labels: Vec<LabelRequirement>,
+    fields: Vec<(String, bool, String)>,
+}
+
+enum LabelRequirement {
+    In(String, BTreeSet<String>),
+    NotIn(String, BTreeSet<String>),
+    Exists(String),
+    DoesNotExist(String),
+}
+
+impl Filter {
+    fn from_query(query: &Query) -> Result<Self, Box<Status>> {
+        let invalid = |selector: &str| {
+            status(
+                StatusCode::BAD_REQUEST,
+                "BadRequest",
+                &format!("unable to parse selector: {selector}"),
+            )
+        };
+        let mut filter = Filter::default();
+        if let Some(selector) = query.get("labelSelector") {
+            for requirement in split_selector(selector) {
+                filter
+                    .labels
+                    .push(parse_label_requirement(requirement).ok_or_else(|| invalid(requirement))?);
+            }
+        }
+        if let Some(selector) = query.get("fieldSelector") {
+            for requirement in selector.split(',').map(str::trim).filter(|r| !r.is_empty()) {
+                let (path, equal, value) = if let Some((path, value)) = requirement.split_once("!=") {
+                    (path, false, value)
+                } else if let Some((path, value)) = requirement.split_once("==") {
+                    (path, true, value)
+                } else if let Some((path, value)) = requirement.split_once('=') {
+                    (path, true, value)
+                } else {
+                    return Err(invalid(requirement));
+                };
+                filter
+                    .fields
+                    .push((path.trim().to_string(), equal, value.trim().to_string()));
+            }
+        }
+        Ok(filter)
+    }
+
+    fn matches(&self, value: &Value) -> bool {
+        if self.namespace.is_some() && value["metadata"]["namespace"].as_str() != self.namespace.as_deref() {
+            return false;
+        }
+        let labels = &value["metadata"]["labels"];
+        let label = |key: &str| labels.get(key).and_then(Value::as_str);
+        let labels_match = self.labels.iter().all(|requirement| match requirement {
+            LabelRequirement::In(key, values) => label(key).is_some_and(|v| values.contains(v)),
+            LabelRequirement::NotIn(key, values) => label(key).is_none_or(|v| !values.contains(v)),
+            LabelRequirement::Exists(key) => label(key).is_some(),
+            LabelRequirement::DoesNotExist(key) => label(key).is_none(),
+        });
+        labels_match
+            && self.fields.iter().all(|(path, equal, expected)| {
+                let actual = path
+                    .split('.')
+                    .try_fold(value, |value, segment| value.get(segment))
+                    .map(|value| match value {
+                        Value::String(s) => s.clone(),
+                        Value::Null => String::new(),
+                        other => other.to_string(),
+                    })
+                    .unwrap_or_default();
+                (actual == *expected) == *equal
+            })
+    }
+}
+
+/// Split a label selector on the commas that are not inside a set of values
+fn split_selector(selector: &str) -> Vec<&str> {
+    let mut requirements = Vec::new();
+    let (mut depth, mut start) = (0, 0);
+    for (i, c) in selector.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            ',' if depth == 0 => {
+                requirements.push(selector[start..i].trim());
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    requirements.push(selector[start..].trim());
+    requirements.retain(|requirement| !requirement.is_empty());
+    requirements
+}
+
+fn parse_label_requirement(requirement: &str) -> Option<LabelRequirement> {
+    let values = |set: &str| -> Option<BTreeSet<String>> {
+        let set = set.trim().strip_prefix('(')?.strip_suffix(')')?;
+        Some(set.split(',').map(|v| v.trim().to_string()).collect())
+    };
+    if let Some(key) = requirement.strip_prefix('!') {
+        return Some(LabelRequirement::DoesNotExist(key.trim().to_string()));
+    }
+    if let Some((key, value)) = requirement.split_once("!=") {
+        let value = [value.trim().to_string()].into();
+        return Some(LabelRequirement::NotIn(key.trim().to_string(), value));
+    }
+    if let Some((key, value)) = requirement.split_once("==").or_else(|| requirement.split_once('=')) {
+        let value = [value.trim().to_string()].into();
+        return Some(LabelRequirement::In(key.trim().to_string(), value));
+    }
+    if let Some((key, set)) = requirement.split_once(" notin ") {
+        return Some(LabelRequirement::NotIn(key.trim().to_string(), values(set)?));
+    }
+    if let Some((key, set)) = requirement.split_once(" in ") {
+        return Some(LabelRequirement::In(key.trim().to_string(), values(set)?));
+    }
+    (!requirement.contains(char::is_whitespace)).then(|| LabelRequirement::Exists(requirement.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::FakeApiServer;
+    use crate::{
+        Api,
+        api::{DeleteParams, ListParams, Patch, PatchParams, PostParams, WatchEvent, WatchParams},
+    };
+    use futures::{StreamExt, TryStreamExt};
+    use k8s_openapi::api::core::v1::{ConfigMap, Pod};
+    use kube_core::ObjectMeta;
+
+    fn configmap(name: &str, labels: &[(&str, &str)]) -> ConfigMap {
+        ConfigMap {
+            metadata: ObjectMeta {
+                name: Some(name.to_string()),
+                labels: Some(labels.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()),
+                ..ObjectMeta::default()
+            },
+            ..ConfigMap::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn crud_and_selectors() {
+        let server = FakeApiServer::new();
+        let api: Api<ConfigMap> = Api::namespaced(server.client(), "ns");
+        let pp = PostParams::default();
+
+        let a = api.create(&pp, &configmap("a", &[("app", "x"), ("tier", "web")])).await.unwrap();
+        api.create(&pp, &configmap("b", &[("app", "y")])).await.unwrap();
+        assert_eq!(a.metadata.namespace.as_deref(), Some("ns"));
+        assert_eq!(a.metadata.generation, Some(1));
+        let err = api.create(&pp, &configmap("a", &[])).await.unwrap_err();
+        assert!(matches!(err, crate::Error::Api(status) if status.code == 409 && status.reason == "AlreadyExists"));
+
+        let names = |list: kube_core::ObjectList<ConfigMap>| {
+            list.items
+                .into_iter()
+                .map(|cm| cm.metadata.name.unwrap())
+                .collect::<Vec<_>>()
+        };
+        let list = |selector: &'static str| {
+            let api = api.clone();
+            async move { names(api.list(&ListParams::default().labels(selector)).await.unwrap()) }
+        };
+        assert_eq!(list("app=x").await, ["a"]);
+        assert_eq!(list("app in (x, y),!tier").await, ["b"]);
+        assert_eq!(list("app!=x").await, ["b"]);
+        let by_name = api
+            .list(&ListParams::default().fields("metadata.name=b"))
+            .await
+            .unwrap();
+        assert_eq!(names(by_name), ["b"]);
+
+        // Spec changes bump the generation, stale replaces conflict
+        let patched = api
+            .patch(
+                "a",
+                &PatchParams::default(),
+                &Patch::Merge(serde_json::json!({ "data": { "k": "v" } })),
+            )
+            .await
+            .unwrap();
+        assert_eq!(patched.metadata.generation, Some(2));
+        let err = api.replace("a", &pp, &a).await.unwrap_err();
+        assert!(matches!(err, crate::Error::Api(status) if status.code == 409));
+
+        api.delete("b", &DeleteParams::default()).await.unwrap();
+        assert!(api.get_opt("b").await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn finalizers_block_deletion() {
+        let mut cm = configmap("a", &[]);
+        cm.metadata.namespace = Some("default".to_string());
+        cm.metadata.finalizers = Some(vec!["example.com/cleanup".to_string()]);
+        let server = FakeApiServer::new().with_object(&cm);
+        let api: Api<ConfigMap> = Api::default_namespaced(server.client());
+
+        api.delete("a", &DeleteParams::default()).await.unwrap();
+        let deleting = api.get("a").await.unwrap();
+        assert!(deleting.metadata.deletion_timestamp.is_some());
+
+        let patch = serde_json::json!({ "metadata": { "finalizers": null } });
+        api.patch("a", &PatchParams::default(), &Patch::Merge(patch))
+            .await
+            .unwrap();
+        assert!(api.get_opt("a").await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn dry_run_and_delete_preconditions() {
+        let server = FakeApiServer::new();
+        let api: Api<ConfigMap> = Api::namespaced(server.client(), "ns");
+
+        let dry_created = api
+            .create(&PostParams { dry_run: true, ..PostParams::default() }, &configmap("a", &[]))
+            .await
+            .unwrap();
+        assert_eq!(dry_created.metadata.name.as_deref(), Some("a"));
+        assert!(dry_created.metadata.resource_version.is_none());
+        assert!(api.get_opt("a").await.unwrap().is_none());
+
+        let a = api.create(&PostParams::default(), &configmap("a", &[])).await.unwrap();
+        let patch = serde_json::json!({ "data": { "k": "v" } });
+        let dry_patched = api
+            .patch("a", &PatchParams::default().dry_run(), &Patch::Merge(&patch))
+            .await
+            .unwrap();
+        assert_eq!(dry_patched.data.unwrap()["k"], "v");
+        assert_eq!(dry_patched.metadata.generation, Some(2));
+        assert_eq!(dry_patched.metadata.resource_version, a.metadata.resource_version);
+        let stored = api.get("a").await.unwrap();
+        assert!(stored.data.is_none());
+        assert_eq!(stored.metadata.generation, Some(1));
+        assert_eq!(stored.metadata.resource_version, a.metadata.resource_version);
+
+        api.delete("a", &DeleteParams::default().dry_run()).await.unwrap();
+        assert!(api.get_opt("a").await.unwrap().is_some());
+
+        let err = api
+            .delete("a", &DeleteParams::default().preconditions(kube_core::params::Preconditions {
+                uid: Some("other".to_string()),
+                resource_version: None,
+            }))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, crate::Error::Api(status) if status.code == 409));
+        let err = api
+            .delete("a", &DeleteParams::default().preconditions(kube_core::params::Preconditions {
+                uid: None,
+                resource_version: Some("0".to_string()),
+            }))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, crate::Error::Api(status) if status.code == 409));
+        api.delete("a", &DeleteParams::default().preconditions(kube_core::params::Preconditions {
+            uid: a.metadata.uid.clone(),
+            resource_version: a.metadata.resource_version.clone(),
+        }))
+        .await
+        .unwrap();
+        assert!(api.get_opt("a").await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn status_subresource_and_apply() {
+        let server = FakeApiServer::new();
+        let api: Api<Pod> = Api::default_namespaced(server.client());
+        let ssapply = PatchParams::apply("test");
+
+        let pod = serde_json::json!({
+            "apiVersion": "v1",
+            "kind": "Pod",
+            "metadata": { "name": "p" },
+            "spec": { "containers": [{ "name": "c", "image": "a" }] },
+            "status": { "phase": "Ignored" },
+        });
+        let created = api.patch("p", &ssapply, &Patch::Apply(&pod)).await.unwrap();
+        assert_eq!(created.metadata.generation, Some(1));
+
+        let status = serde_json::json!({ "status": { "phase": "Running" }, "spec": null });
+        let updated = api
+            .patch_status("p", &PatchParams::default(), &Patch::Merge(&status))
+            .await
+            .unwrap();
+        assert_eq!(updated.status.unwrap().phase.as_deref(), Some("Running"));
+        assert_eq!(updated.spec.unwrap().containers[0].image.as_deref(), Some("a"));
+        assert_eq!(updated.metadata.generation, Some(1));
+    }
+
+    #[tokio::test]
+    async fn watch_from_resource_version() {
+        let server = FakeApiServer::new();
+        let api: Api<ConfigMap> = Api::namespaced(server.client(), "ns");
+        let pp = PostParams::default();
+        let a = api.create(&pp, &configmap("a", &[])).await.unwrap();
+        let rv = a.metadata.resource_version.unwrap();
+
+        let mut events = api.watch(&WatchParams::default(), &rv).await.unwrap().boxed();
+        api.create(&pp, &configmap("b", &[])).await.unwrap();
+        api.delete("a", &DeleteParams::default()).await.unwrap();
+        assert!(matches!(events.try_next().await.unwrap(), Some(WatchEvent::Added(cm)) if cm.metadata.name.as_deref() == Some("b")));
+        assert!(matches!(events.try_next().await.unwrap(), Some(WatchEvent::Deleted(cm)) if cm.metadata.name.as_deref() == Some("a")));
+
+        server.compact();
+        let mut expired = api.watch(&WatchParams::default(), &rv).await.unwrap().boxed();
+        assert!(matches!(expired.try_next().await.unwrap(), Some(WatchEvent::Error(status)) if status.code == 410));
+    }
+}
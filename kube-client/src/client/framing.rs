@@ -0,0 +1,56 @@
+//! Framing of watch event streams in the negotiated wire format
+#[cfg(any(feature = "protobuf", feature = "cbor"))] use bytes::Bytes;
+use bytes::BytesMut;
+use tokio_util::codec::{Decoder, LinesCodec, LinesCodecError};
+
+/// Splits a watch response body into the frames of its wire format
+pub(crate) enum Framing {
+    /// Newline delimited JSON
+    Json(LinesCodec),
+    /// Length delimited protobuf
+    #[cfg(feature = "protobuf")]
+    Protobuf(super::protobuf::WatchFrames),
+    /// A CBOR sequence
+    #[cfg(feature = "cbor")]
+    Cbor(super::cbor::Sequence),
+}
+
+/// A single encoded watch event
+pub(crate) enum Frame {
+    Json(String),
+    #[cfg(feature = "protobuf")]
+    Protobuf(Bytes),
+    #[cfg(feature = "cbor")]
+    Cbor(Bytes),
+}
+
+impl Decoder for Framing {
+    type Error = LinesCodecError;
+    type Item = Frame;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        match self {
+            Self::Json(lines) => Ok(lines.decode(src)?.map(Frame::Json)),
+            #[cfg(feature = "protobuf")]
+            Self::Protobuf(frames) => Ok(frames.decode(src)?.map(Frame::Protobuf)),
+            #[cfg(feature = "cbor")]
+            Self::Cbor(items) => Ok(items.decode(src)?.map(Frame::Cbor)),
+        }
+    }
+
+    fn decode_eof(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        match self {
+            // Yield an incomplete last line, which is ignored if it can't be parsed
+            Self::Json(lines) => Ok(lines.decode_eof(src)?.map(Frame::Json)),
+            #[cfg(any(feature = "protobuf", feature = "cbor"))]
+            _ => {
+                let frame = self.decode(src)?;
+                if frame.is_none() && !src.is_empty() {
+                    tracing::warn!("watch stream ended with {} bytes of an incomplete event", src.len());
+                    src.clear();
+                }
+                Ok(frame)
+            }
+        }
+    }
+}
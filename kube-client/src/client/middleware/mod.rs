@@ -4,9 +4,13 @@ pub(crate) use tower_http::auth::AddAuthorizationLayer;
 
 mod base_uri;
 mod extra_headers;
+mod rate_limit;
+mod trace_context;
 
 pub use base_uri::{BaseUri, BaseUriLayer};
 pub use extra_headers::{ExtraHeaders, ExtraHeadersLayer};
+pub use rate_limit::{RateLimit, RateLimitLayer, Verb};
+pub use trace_context::{TraceContext, TraceContextLayer, TraceContextService};
 
 use super::auth::RefreshableToken;
 /// Layer to set up `Authorization` header depending on the config.
@@ -0,0 +1,269 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use futures::future::BoxFuture;
+use http::{Method, Request};
+use tokio::time::Instant;
+use tower::{Layer, Service};
+
+/// Kubernetes verb of a request, used to select a per-verb rate limit
+///
+/// Requests made through [`Api`](crate::Api) are classified by the method that made them,
+/// other requests by their HTTP method alone.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Verb {
+    /// Get a single object
+    Get,
+    /// List a collection
+    List,
+    /// Create an object
+    Create,
+    /// Replace an object
+    Update,
+    /// Patch an object
+    Patch,
+    /// Delete an object
+    Delete,
+    /// Delete a collection
+    DeleteCollection,
+}
+
+impl Verb {
+    fn of<B>(req: &Request<B>) -> Option<Self> {
+        let operation = req.extensions().get::<&'static str>().copied().unwrap_or_default();
+        match *req.method() {
+            Method::GET if operation.starts_with("list") => Some(Self::List),
+            Method::GET => Some(Self::Get),
+            Method::POST => Some(Self::Create),
+            Method::PUT => Some(Self::Update),
+            Method::PATCH => Some(Self::Patch),
+            Method::DELETE if operation == "delete_collection" => Some(Self::DeleteCollection),
+            Method::DELETE => Some(Self::Delete),
+            _ => None,
+        }
+    }
+}
+
+/// Whether a request stays open indefinitely, and is exempt from rate limiting
+fn is_long_running<B>(req: &Request<B>) -> bool {
+    req.uri()
+        .query()
+        .is_some_and(|query| query.split('&').any(|pair| pair == "watch=true" || pair == "follow=true"))
+}
+
+/// Lowest rate a bucket refills at, so that a zero, negative or NaN `qps` can't stall requests forever
+const MIN_QPS: f64 = 0.001;
+
+/// Token bucket refilling at `qps` up to `burst` tokens
+struct Bucket {
+    qps: f64,
+    burst: f64,
+    tokens: f64,
+    updated: Instant,
+}
+
+impl Bucket {
+    fn new(qps: f32, burst: u32) -> Self {
+        let burst = f64::from(burst.max(1));
+        Self {
+            // `max` also replaces NaN, and the upper bound keeps `elapsed * qps` finite
+            qps: f64::from(qps).max(MIN_QPS).min(f64::from(f32::MAX)),
+            burst,
+            tokens: burst,
+            updated: Instant::now(),
+        }
+    }
+
+    /// Take a token, returning how long to wait until it is available
+    fn reserve(&mut self, now: Instant) -> Duration {
+        let elapsed = now.saturating_duration_since(self.updated).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.qps).min(self.burst);
+        self.updated = now;
+        self.tokens -= 1.0;
+        if self.tokens >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::try_from_secs_f64(-self.tokens / self.qps).unwrap_or(Duration::MAX)
+        }
+    }
+}
+
+#[derive(Clone)]
+struct Limiter {
+    all: Arc<Mutex<Bucket>>,
+    verbs: HashMap<Verb, Arc<Mutex<Bucket>>>,
+}
+
+impl Limiter {
+    /// Reserve a token from every bucket that applies to `req`, returning the longest wait
+    fn reserve<B>(&self, req: &Request<B>) -> Duration {
+        if is_long_running(req) {
+            return Duration::ZERO;
+        }
+        let now = Instant::now();
+        let wait = self.all.lock().unwrap().reserve(now);
+        Verb::of(req)
+            .and_then(|verb| self.verbs.get(&verb))
+            .map_or(wait, |bucket| wait.max(bucket.lock().unwrap().reserve(now)))
+    }
+}
+
+/// Layer that throttles requests to a number of queries per second
+///
+/// Requests are let through in bursts of up to `burst` requests, after which they are delayed to keep
+/// to `qps` on average, like the `QPS` and `Burst` options of `client-go`. Watches and followed logs are
+/// exempt, as they stay open indefinitely.
+///
+/// The limit is shared between all clones of the layer and the services it creates.
+#[derive(Clone)]
+pub struct RateLimitLayer {
+    limiter: Limiter,
+}
+
+impl RateLimitLayer {
+    /// Limit all requests to `qps` queries per second, with bursts of up to `burst` requests
+    ///
+    /// `qps` is raised to at least one request every 1000 seconds, and `burst` to at least 1.
+    pub fn new(qps: f32, burst: u32) -> Self {
+        Self {
+            limiter: Limiter {
+                all: Arc::new(Mutex::new(Bucket::new(qps, burst))),
+                verbs: HashMap::new(),
+            },
+        }
+    }
+
+    /// Additionally limit requests with the given [`Verb`] to `qps` queries per second
+    ///
+    /// Requests with the verb must then fit within both limits. `qps` and `burst` are raised like for [`RateLimitLayer::new`].
+    #[must_use]
+    pub fn with_verb(mut self, verb: Verb, qps: f32, burst: u32) -> Self {
+        self.limiter
+            .verbs
+            .insert(verb, Arc::new(Mutex::new(Bucket::new(qps, burst))));
+        self
+    }
+}
+
+impl<S> Layer<S> for RateLimitLayer {
+    type Service = RateLimit<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RateLimit {
+            inner,
+            limiter: self.limiter.clone(),
+        }
+    }
+}
+
+/// Service that throttles requests to a number of queries per second
+#[derive(Clone)]
+pub struct RateLimit<S> {
+    inner: S,
+    limiter: Limiter,
+}
+
+impl<S, ReqBody> Service<Request<ReqBody>> for RateLimit<S>
+where
+    S: Service<Request<ReqBody>> + Clone + Send + 'static,
+    S::Future: Send,
+    ReqBody: Send + 'static,
+{
+    type Error = S::Error;
+    type Future = BoxFuture<'static, Result<S::Response, S::Error>>;
+    type Response = S::Response;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<ReqBody>) -> Self::Future {
+        let wait = self.limiter.reserve(&req);
+        // Take the service that was polled ready, leaving a clone for the next request
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+        Box::pin(async move {
+            if !wait.is_zero() {
+                tracing::debug!("waiting {wait:?} due to client-side throttling");
+                tokio::time::sleep(wait).await;
+            }
+            inner.call(req).await
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::convert::Infallible;
+
+    use tower::{ServiceExt, service_fn};
+
+    fn request(method: Method, uri: &str, operation: &'static str) -> Request<()> {
+        let mut req = Request::builder().method(method).uri(uri).body(()).unwrap();
+        req.extensions_mut().insert(operation);
+        req
+    }
+
+    // Sends the requests in order, returning how long after the start each one reached the inner service
+    async fn sent_after(layer: &RateLimitLayer, requests: Vec<Request<()>>) -> Vec<Duration> {
+        let start = Instant::now();
+        let mut svc = layer.layer(service_fn(|_: Request<()>| async { Ok::<_, Infallible>(Instant::now()) }));
+        let mut sent = Vec::new();
+        for req in requests {
+            let at = svc.ready().await.unwrap().call(req).await.unwrap();
+            sent.push(at - start);
+        }
+        sent
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn throttles_after_burst() {
+        let layer = RateLimitLayer::new(2.0, 2);
+        let requests = (0..4)
+            .map(|_| request(Method::GET, "/api/v1/namespaces/ns/pods/a", "get"))
+            .collect();
+        let sent = sent_after(&layer, requests).await;
+        assert_eq!(sent, [0, 0, 500, 1000].map(Duration::from_millis));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn non_positive_qps_is_clamped() {
+        for qps in [0.0, -1.0, f32::NAN] {
+            let layer = RateLimitLayer::new(qps, 1);
+            let requests = (0..2)
+                .map(|_| request(Method::GET, "/api/v1/namespaces/ns/pods/a", "get"))
+                .collect();
+            let sent = sent_after(&layer, requests).await;
+            assert_eq!(sent, [0, 1000].map(Duration::from_secs));
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn watches_are_exempt() {
+        let layer = RateLimitLayer::new(1.0, 1);
+        let requests = (0..3)
+            .map(|_| request(Method::GET, "/api/v1/pods?&watch=true&resourceVersion=0", "watch"))
+            .collect();
+        let sent = sent_after(&layer, requests).await;
+        assert_eq!(sent, [Duration::ZERO; 3]);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn verbs_have_their_own_bucket() {
+        let layer = RateLimitLayer::new(100.0, 100).with_verb(Verb::List, 1.0, 1);
+        let requests = vec![
+            request(Method::GET, "/api/v1/pods", "list"),
+            request(Method::GET, "/api/v1/namespaces/ns/pods/a", "get"),
+            request(Method::GET, "/api/v1/pods", "list"),
+        ];
+        let sent = sent_after(&layer, requests).await;
+        assert_eq!(sent, [0, 0, 1000].map(Duration::from_millis));
+    }
+}
@@ -0,0 +1,226 @@
+use std::{fmt, sync::Arc};
+
+use http::{HeaderName, HeaderValue, Request};
+use tower::{Layer, Service};
+use tracing::Span;
+
+const TRACEPARENT: HeaderName = HeaderName::from_static("traceparent");
+const TRACESTATE: HeaderName = HeaderName::from_static("tracestate");
+
+/// [W3C trace context] of a span, propagated to the apiserver by [`TraceContextLayer`]
+///
+/// [W3C trace context]: https://www.w3.org/TR/trace-context/
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TraceContext {
+    /// Id of the trace the span belongs to
+    pub trace_id: u128,
+    /// Id of the span, which becomes the parent of the apiserver's spans
+    pub span_id: u64,
+    /// Whether the span is sampled
+    pub sampled: bool,
+    /// Vendor specific trace state, sent as the `tracestate` header
+    pub trace_state: Option<String>,
+}
+
+impl TraceContext {
+    /// Whether the ids are set, as all zero ids are invalid
+    pub fn is_valid(&self) -> bool {
+        self.trace_id != 0 && self.span_id != 0
+    }
+
+    /// The value of the `traceparent` header
+    pub fn traceparent(&self) -> String {
+        format!(
+            "00-{:032x}-{:016x}-{:02x}",
+            self.trace_id,
+            self.span_id,
+            u8::from(self.sampled)
+        )
+    }
+}
+
+type Extract = dyn Fn(&Span) -> Option<TraceContext> + Send + Sync;
+
+/// Layer that propagates the trace context of the current [`tracing`] span in `traceparent` and
+/// `tracestate` headers
+///
+/// `tracing` spans have no W3C ids of their own, so they are read by the `extract` function given to
+/// [`TraceContextLayer::new`], typically from the OpenTelemetry context attached by `tracing-opentelemetry`:
+///
+/// ```no_run
+/// # // Stand-ins for the parts of `opentelemetry` and `tracing-opentelemetry` used by the example
+/// # mod opentelemetry {
+/// #     pub mod trace {
+/// #         pub struct Id<const N: usize>;
+/// #         impl<const N: usize> Id<N> { pub fn to_bytes(&self) -> [u8; N] { [1; N] } }
+/// #         pub struct TraceState;
+/// #         impl TraceState { pub fn header(&self) -> String { String::new() } }
+/// #         pub struct SpanContext;
+/// #         impl SpanContext {
+/// #             pub fn trace_id(&self) -> Id<16> { Id }
+/// #             pub fn span_id(&self) -> Id<8> { Id }
+/// #             pub fn is_sampled(&self) -> bool { true }
+/// #             pub fn trace_state(&self) -> &TraceState { &TraceState }
+/// #         }
+/// #         pub struct SpanRef;
+/// #         impl SpanRef { pub fn span_context(&self) -> &SpanContext { &SpanContext } }
+/// #         pub struct Context;
+/// #         pub trait TraceContextExt { fn span(&self) -> SpanRef; }
+/// #         impl TraceContextExt for Context { fn span(&self) -> SpanRef { SpanRef } }
+/// #     }
+/// # }
+/// # mod tracing_opentelemetry {
+/// #     pub trait OpenTelemetrySpanExt { fn context(&self) -> super::opentelemetry::trace::Context; }
+/// #     impl OpenTelemetrySpanExt for tracing::Span {
+/// #         fn context(&self) -> super::opentelemetry::trace::Context { super::opentelemetry::trace::Context }
+/// #     }
+/// # }
+/// use opentelemetry::trace::TraceContextExt;
+/// use tracing_opentelemetry::OpenTelemetrySpanExt;
+/// use kube::client::{ClientBuilder, middleware::{TraceContext, TraceContextLayer}};
+///
+/// # async fn wrapper(config: kube::Config) -> Result<(), Box<dyn std::error::Error>> {
+/// let trace_context = TraceContextLayer::new(|span| {
+///     let context = span.context();
+///     let span = context.span();
+///     let span_context = span.span_context();
+///     Some(TraceContext {
+///         trace_id: u128::from_be_bytes(span_context.trace_id().to_bytes()),
+///         span_id: u64::from_be_bytes(span_context.span_id().to_bytes()),
+///         sampled: span_context.is_sampled(),
+///         trace_state: Some(span_context.trace_state().header()).filter(|state| !state.is_empty()),
+///     })
+/// });
+/// let client = ClientBuilder::try_from(config)?.with_layer(&trace_context).build();
+/// # let _: kube::Client = client;
+/// # Ok(())
+/// # }
+/// # fn main() {}
+/// ```
+///
+/// Requests that already have a `traceparent` header are left untouched.
+#[derive(Clone)]
+pub struct TraceContextLayer {
+    extract: Arc<Extract>,
+}
+
+impl TraceContextLayer {
+    /// Propagate the trace context that `extract` reads from the current span
+    pub fn new(extract: impl Fn(&Span) -> Option<TraceContext> + Send + Sync + 'static) -> Self {
+        Self {
+            extract: Arc::new(extract),
+        }
+    }
+}
+
+impl fmt::Debug for TraceContextLayer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TraceContextLayer").finish_non_exhaustive()
+    }
+}
+
+impl<S> Layer<S> for TraceContextLayer {
+    type Service = TraceContextService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        TraceContextService {
+            inner,
+            extract: self.extract.clone(),
+        }
+    }
+}
+
+/// Service that propagates the trace context of the current [`tracing`] span
+#[derive(Clone)]
+pub struct TraceContextService<S> {
+    inner: S,
+    extract: Arc<Extract>,
+}
+
+impl<S, ReqBody> Service<Request<ReqBody>> for TraceContextService<S>
+where
+    S: Service<Request<ReqBody>>,
+{
+    type Error = S::Error;
+    type Future = S::Future;
+    type Response = S::Response;
+
+    fn poll_ready(&mut self, cx: &mut std::task::Context<'_>) -> std::task::Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut req: Request<ReqBody>) -> Self::Future {
+        if !req.headers().contains_key(TRACEPARENT)
+            && let Some(context) = (self.extract)(&Span::current()).filter(TraceContext::is_valid)
+        {
+            let headers = req.headers_mut();
+            if let Ok(traceparent) = HeaderValue::try_from(context.traceparent()) {
+                headers.insert(TRACEPARENT, traceparent);
+            }
+            if let Some(Ok(trace_state)) = context.trace_state.map(HeaderValue::try_from) {
+                headers.insert(TRACESTATE, trace_state);
+            }
+        }
+        self.inner.call(req)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::pin::pin;
+
+    use http::Response;
+    use tower::ServiceExt;
+    use tower_test::mock;
+
+    use crate::client::Body;
+
+    async fn headers_sent(trace_id: u128, req: Request<Body>) -> http::HeaderMap {
+        let layer = TraceContextLayer::new(move |_| {
+            Some(TraceContext {
+                trace_id,
+                span_id: 0x00f067aa0ba902b7,
+                sampled: true,
+                trace_state: Some("kube=1".into()),
+            })
+        });
+        let (svc, handle) = mock::pair::<Request<Body>, Response<Body>>();
+        let spawned = tokio::spawn(async move {
+            let mut handle = pin!(handle);
+            let (request, send) = handle.next_request().await.expect("service not called");
+            send.send_response(Response::new(Body::empty()));
+            request.headers().clone()
+        });
+        layer.layer(svc).oneshot(req).await.unwrap();
+        spawned.await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn injects_trace_context() {
+        let req = Request::get("/").body(Body::empty()).unwrap();
+        let headers = headers_sent(0x4bf92f3577b34da6a3ce929d0e0e4736, req).await;
+        assert_eq!(
+            headers[TRACEPARENT],
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01"
+        );
+        assert_eq!(headers[TRACESTATE], "kube=1");
+    }
+
+    #[tokio::test]
+    async fn skips_invalid_or_existing_trace_context() {
+        let req = Request::get("/").body(Body::empty()).unwrap();
+        let headers = headers_sent(0, req).await;
+        assert!(!headers.contains_key(TRACEPARENT));
+
+        let existing = "00-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-00";
+        let req = Request::get("/")
+            .header(TRACEPARENT, existing)
+            .body(Body::empty())
+            .unwrap();
+        let headers = headers_sent(1, req).await;
+        assert_eq!(headers[TRACEPARENT], existing);
+        assert!(!headers.contains_key(TRACESTATE));
+    }
+}
@@ -46,6 +46,8 @@ pub use client_ext::scope;
 mod config_ext;
 pub use auth::Error as AuthError;
 pub use config_ext::ConfigExt;
+mod list;
+pub use list::ObjectListStream;
 pub mod middleware;
 pub mod retry;
 
@@ -74,6 +76,21 @@ pub use upgrade::UpgradeConnectionError;
 #[cfg_attr(docsrs, doc(cfg(feature = "kubelet-debug")))]
 mod kubelet_debug;
 
+#[cfg(feature = "unstable-client-fake")]
+#[cfg_attr(docsrs, doc(cfg(feature = "unstable-client-fake")))]
+pub mod fake;
+
+#[cfg(feature = "protobuf")]
+#[cfg_attr(docsrs, doc(cfg(feature = "protobuf")))]
+pub mod protobuf;
+
+#[cfg(feature = "cbor")]
+#[cfg_attr(docsrs, doc(cfg(feature = "cbor")))]
+pub mod cbor;
+
+mod framing;
+pub mod warning;
+
 pub use builder::{ClientBuilder, DynBody};
 
 /// Client for connecting with a Kubernetes cluster.
@@ -90,6 +107,11 @@ pub struct Client {
     inner: Buffer<Request<Body>, BoxFuture<'static, Result<Response<Body>, BoxError>>>,
     default_ns: String,
     valid_until: Option<Timestamp>,
+    #[cfg(feature = "protobuf")]
+    protobuf: bool,
+    #[cfg(feature = "cbor")]
+    cbor: cbor::Negotiation,
+    warning_handler: std::sync::Arc<dyn warning::WarningHandler>,
 }
 
 /// Represents a WebSocket connection.
@@ -168,6 +190,11 @@ impl Client {
             inner: Buffer::new(service, 1024),
             default_ns: default_namespace.into(),
             valid_until: None,
+            #[cfg(feature = "protobuf")]
+            protobuf: false,
+            #[cfg(feature = "cbor")]
+            cbor: cbor::Negotiation::default(),
+            warning_handler: std::sync::Arc::new(warning::DedupWarnings::new(warning::LogWarnings)),
         }
     }
 
@@ -181,6 +208,43 @@ impl Client {
         &self.valid_until
     }
 
+    /// Sets whether to negotiate the protobuf wire format for the built-in types.
+    ///
+    /// See the [`protobuf`] module for details.
+    #[cfg(feature = "protobuf")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "protobuf")))]
+    #[must_use]
+    pub fn with_protobuf(self, enabled: bool) -> Self {
+        Client {
+            protobuf: enabled,
+            ..self
+        }
+    }
+
+    /// Sets whether to negotiate the CBOR wire format.
+    ///
+    /// See the [`cbor`] module for details.
+    #[cfg(feature = "cbor")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "cbor")))]
+    #[must_use]
+    pub fn with_cbor(self, enabled: bool) -> Self {
+        Client {
+            cbor: cbor::Negotiation::new(enabled),
+            ..self
+        }
+    }
+
+    /// Sets the handler for the warnings sent by the apiserver.
+    ///
+    /// Defaults to logging each distinct warning once. See the [`warning`] module for details.
+    #[must_use]
+    pub fn with_warning_handler(self, handler: impl warning::WarningHandler) -> Self {
+        Client {
+            warning_handler: std::sync::Arc::new(handler),
+            ..self
+        }
+    }
+
     /// Create and initialize a [`Client`] using the inferred configuration.
     ///
     /// Will use [`Config::infer`] which attempts to load the local kubeconfig first,
@@ -231,6 +295,10 @@ impl Client {
                     // Error from another middleware
                     .unwrap_or_else(Error::Service)
             })?;
+        for warning in warning::Warning::from_headers(res.headers()) {
+            self.warning_handler.handle(&warning);
+            warning::record(&warning);
+        }
         Ok(res)
     }
 
@@ -282,6 +350,15 @@ impl Client {
     where
         T: DeserializeOwned,
     {
+        #[cfg(feature = "protobuf")]
+        if self.protobuf && protobuf::negotiable(&request) {
+            return self.request_protobuf(request).await;
+        }
+        #[cfg(feature = "cbor")]
+        if self.cbor.enabled() {
+            return self.request_cbor(request).await;
+        }
+
         let text = self.request_text(request).await?;
 
         serde_json::from_str(&text).map_err(|e| {
@@ -290,6 +367,96 @@ impl Client {
         })
     }
 
+    /// Request `T` as protobuf, falling back to JSON if the apiserver responds with JSON
+    #[cfg(feature = "protobuf")]
+    async fn request_protobuf<T>(&self, mut request: Request<Vec<u8>>) -> Result<T>
+    where
+        T: DeserializeOwned,
+    {
+        request.headers_mut().insert(
+            http::header::ACCEPT,
+            http::HeaderValue::from_static(protobuf::ACCEPT),
+        );
+        let res = self.send(request.map(Body::from)).await?;
+        let res = handle_api_errors(res).await?;
+        let is_protobuf = protobuf::is_protobuf(res.headers().get(http::header::CONTENT_TYPE));
+        let body_bytes = res.into_body().collect().await?.to_bytes();
+        if is_protobuf {
+            let value = protobuf::decode(&body_bytes).map_err(Error::ProtobufDecode)?;
+            return serde_json::from_value(value).map_err(Error::SerdeError);
+        }
+        serde_json::from_slice(&body_bytes).map_err(Error::SerdeError)
+    }
+
+    /// Send a request negotiated as CBOR, resending `original` as JSON if the apiserver rejected CBOR
+    ///
+    /// CBOR is then disabled for the client and all its clones. Other responses are returned as they are.
+    #[cfg(feature = "cbor")]
+    async fn send_cbor(&self, request: Request<Vec<u8>>, original: Request<Vec<u8>>) -> Result<Response<Body>> {
+        let negotiated = cbor::Negotiated::of(&request);
+        let res = self.send(request.map(Body::from)).await?;
+        if !matches!(
+            res.status(),
+            http::StatusCode::NOT_ACCEPTABLE | http::StatusCode::UNSUPPORTED_MEDIA_TYPE
+        ) {
+            return Ok(res);
+        }
+
+        let (parts, body) = res.into_parts();
+        let body = body.collect().await?.to_bytes();
+        let status: Option<Status> = if cbor::is_cbor(parts.headers.get(http::header::CONTENT_TYPE)) {
+            cbor::decode(&body).ok()
+        } else {
+            serde_json::from_slice(&body).ok()
+        };
+        if negotiated.rejected_by(parts.status, status.as_ref()) {
+            tracing::debug!("apiserver rejected cbor with {}, falling back to json", parts.status);
+            self.cbor.disable();
+            return self.send(original.map(Body::from)).await;
+        }
+        Ok(Response::from_parts(parts, Body::from(body)))
+    }
+
+    /// Request `T` as CBOR, falling back to JSON if the apiserver responds with or requires JSON
+    #[cfg(feature = "cbor")]
+    async fn request_cbor<T>(&self, mut request: Request<Vec<u8>>) -> Result<T>
+    where
+        T: DeserializeOwned,
+    {
+        let original = cbor::negotiate(&mut request, cbor::CBOR_MIME);
+        let res = self.send_cbor(request, original).await?;
+        let res = handle_api_errors(res).await?;
+        let is_cbor = cbor::is_cbor(res.headers().get(http::header::CONTENT_TYPE));
+        let body_bytes = res.into_body().collect().await?.to_bytes();
+        if is_cbor {
+            return cbor::decode(&body_bytes).map_err(Error::CborDecode);
+        }
+        serde_json::from_slice(&body_bytes).map_err(Error::SerdeError)
+    }
+
+    /// Perform a raw HTTP request for a list against the API and stream its objects
+    ///
+    /// The objects are deserialized as JSON one at a time as the response body arrives,
+    /// rather than buffering the whole response first like [`Client::request`] does.
+    /// Lists negotiated as protobuf or CBOR are decoded in full before being streamed.
+    pub async fn request_list<T>(&self, request: Request<Vec<u8>>) -> Result<ObjectListStream<T>>
+    where
+        T: Clone + DeserializeOwned,
+    {
+        #[cfg(feature = "protobuf")]
+        if self.protobuf && protobuf::negotiable(&request) {
+            return self.request::<kube_core::ObjectList<T>>(request).await.map(Into::into);
+        }
+        #[cfg(feature = "cbor")]
+        if self.cbor.enabled() {
+            return self.request::<kube_core::ObjectList<T>>(request).await.map(Into::into);
+        }
+
+        let res = self.send(request.map(Body::from)).await?;
+        let res = handle_api_errors(res).await?;
+        Ok(ObjectListStream::json(res.into_body()))
+    }
+
     /// Perform a raw HTTP request against the API and get back the response
     /// as a string
     pub async fn request_text(&self, request: Request<Vec<u8>>) -> Result<String> {
@@ -344,10 +511,53 @@ impl Client {
     where
         T: Clone + DeserializeOwned,
     {
-        let res = self.send(request.map(Body::from)).await?;
+        #[cfg_attr(not(any(feature = "protobuf", feature = "cbor")), allow(unused_mut))]
+        let mut request = request;
+        #[cfg(feature = "protobuf")]
+        let negotiated_protobuf = self.protobuf && protobuf::negotiable(&request);
+        #[cfg(feature = "protobuf")]
+        if negotiated_protobuf {
+            request.headers_mut().insert(
+                http::header::ACCEPT,
+                http::HeaderValue::from_static(protobuf::ACCEPT_WATCH),
+            );
+        }
+        #[cfg(all(feature = "protobuf", feature = "cbor"))]
+        let original = (self.cbor.enabled() && !negotiated_protobuf)
+            .then(|| cbor::negotiate(&mut request, cbor::CBOR_SEQ_MIME));
+        #[cfg(all(not(feature = "protobuf"), feature = "cbor"))]
+        let original = self
+            .cbor
+            .enabled()
+            .then(|| cbor::negotiate(&mut request, cbor::CBOR_SEQ_MIME));
+
+        #[cfg(feature = "cbor")]
+        let mut res = match original {
+            Some(original) => self.send_cbor(request, original).await?,
+            None => self.send(request.map(Body::from)).await?,
+        };
+        #[cfg(not(feature = "cbor"))]
+        let mut res = self.send(request.map(Body::from)).await?;
         // trace!("Streaming from {} -> {}", res.url(), res.status().as_str());
         tracing::trace!("headers: {:?}", res.headers());
 
+        #[cfg_attr(not(any(feature = "protobuf", feature = "cbor")), allow(unused_mut))]
+        let mut framing = framing::Framing::Json(LinesCodec::new());
+        #[cfg(any(feature = "protobuf", feature = "cbor"))]
+        let content_type = res.headers().get(http::header::CONTENT_TYPE);
+        #[cfg(feature = "protobuf")]
+        if negotiated_protobuf && protobuf::is_protobuf(content_type) {
+            framing = framing::Framing::Protobuf(protobuf::WatchFrames);
+        }
+        #[cfg(feature = "cbor")]
+        if cbor::is_cbor(content_type) {
+            framing = framing::Framing::Cbor(cbor::Sequence);
+        }
+        // Errors are only reported inline in newline delimited JSON
+        if !matches!(framing, framing::Framing::Json(_)) {
+            res = handle_api_errors(res).await?;
+        }
+
         let frames = FramedRead::new(
             StreamReader::new(res.into_body().into_data_stream().map_err(|e| {
                 // Unexpected EOF from chunked decoder.
@@ -355,49 +565,72 @@ impl Client {
                 if e.to_string().contains("unexpected EOF during chunk") {
                     return std::io::Error::new(std::io::ErrorKind::UnexpectedEof, e);
                 }
+                // The HTTP/2 equivalent, the stream being reset when the watch times out.
+                #[cfg(feature = "http2")]
+                if is_stream_reset(&e) {
+                    return std::io::Error::new(std::io::ErrorKind::UnexpectedEof, e);
+                }
                 std::io::Error::other(e)
             })),
-            LinesCodec::new(),
+            framing,
         );
 
-        Ok(frames.filter_map(|res| async {
-            match res {
-                Ok(line) => match serde_json::from_str::<WatchEvent<T>>(&line) {
-                    Ok(event) => Some(Ok(event)),
-                    Err(e) => {
-                        // Ignore EOF error that can happen for incomplete line from `decode_eof`.
-                        if e.is_eof() {
-                            return None;
+        Ok(frames.filter_map(move |res| {
+            async move {
+                match res {
+                    Ok(framing::Frame::Json(line)) => match serde_json::from_str::<WatchEvent<T>>(&line) {
+                        Ok(event) => Some(Ok(event)),
+                        Err(e) => {
+                            // Ignore EOF error that can happen for incomplete line from `decode_eof`.
+                            if e.is_eof() {
+                                return None;
+                            }
+
+                            // Got general error response
+                            if let Ok(status) = serde_json::from_str::<Status>(&line) {
+                                return Some(Err(Error::Api(status.boxed())));
+                            }
+                            // Parsing error
+                            Some(Err(Error::SerdeError(e)))
                         }
+                    },
+
+                    #[cfg(feature = "protobuf")]
+                    Ok(framing::Frame::Protobuf(frame)) => Some(
+                        protobuf::decode_watch_event(&frame)
+                            .map_err(Error::ProtobufDecode)
+                            .and_then(|event| serde_json::from_value(event).map_err(Error::SerdeError)),
+                    ),
 
-                        // Got general error response
-                        if let Ok(status) = serde_json::from_str::<Status>(&line) {
-                            return Some(Err(Error::Api(status.boxed())));
+                    #[cfg(feature = "cbor")]
+                    Ok(framing::Frame::Cbor(item)) => match cbor::decode::<WatchEvent<T>>(&item) {
+                        Ok(event) => Some(Ok(event)),
+                        Err(e) => match cbor::decode_status(&item) {
+                            Some(status) => Some(Err(Error::Api(status.boxed()))),
+                            None => Some(Err(Error::CborDecode(e))),
+                        },
+                    },
+
+                    Err(LinesCodecError::Io(e)) => match e.kind() {
+                        // Client timeout
+                        std::io::ErrorKind::TimedOut => {
+                            tracing::warn!("timeout in poll: {}", e); // our client timeout
+                            None
                         }
-                        // Parsing error
-                        Some(Err(Error::SerdeError(e)))
-                    }
-                },
+                        // Unexpected EOF from chunked decoder.
+                        // Tends to happen after 300+s of watching.
+                        std::io::ErrorKind::UnexpectedEof => {
+                            tracing::warn!("eof in poll: {}", e);
+                            None
+                        }
+                        _ => Some(Err(Error::ReadEvents(e))),
+                    },
 
-                Err(LinesCodecError::Io(e)) => match e.kind() {
-                    // Client timeout
-                    std::io::ErrorKind::TimedOut => {
-                        tracing::warn!("timeout in poll: {}", e); // our client timeout
-                        None
+                    // Reached the maximum line length without finding a newline.
+                    // This should never happen because we're using the default `usize::MAX`.
+                    Err(LinesCodecError::MaxLineLengthExceeded) => {
+                        Some(Err(Error::LinesCodecMaxLineLengthExceeded))
                     }
-                    // Unexpected EOF from chunked decoder.
-                    // Tends to happen after 300+s of watching.
-                    std::io::ErrorKind::UnexpectedEof => {
-                        tracing::warn!("eof in poll: {}", e);
-                        None
-                    }
-                    _ => Some(Err(Error::ReadEvents(e))),
-                },
-
-                // Reached the maximum line length without finding a newline.
-                // This should never happen because we're using the default `usize::MAX`.
-                Err(LinesCodecError::MaxLineLengthExceeded) => {
-                    Some(Err(Error::LinesCodecMaxLineLengthExceeded))
                 }
             }
         }))
@@ -544,7 +777,21 @@ async fn handle_api_errors(res: Response<Body>) -> Result<Response<Body>> {
     let status = res.status();
     if status.is_client_error() || status.is_server_error() {
         // trace!("Status = {:?} for {}", status, res.url());
+        #[cfg(feature = "protobuf")]
+        let is_protobuf = protobuf::is_protobuf(res.headers().get(http::header::CONTENT_TYPE));
+        #[cfg(feature = "cbor")]
+        let is_cbor = cbor::is_cbor(res.headers().get(http::header::CONTENT_TYPE));
         let body_bytes = res.into_body().collect().await?.to_bytes();
+        #[cfg(feature = "protobuf")]
+        if is_protobuf && let Some(status) = protobuf::decode_status(&body_bytes) {
+            tracing::debug!("Unsuccessful: {status:?}");
+            return Err(Error::Api(status.boxed()));
+        }
+        #[cfg(feature = "cbor")]
+        if is_cbor && let Some(status) = cbor::decode_status(&body_bytes) {
+            tracing::debug!("Unsuccessful: {status:?}");
+            return Err(Error::Api(status.boxed()));
+        }
         let text = String::from_utf8(body_bytes.to_vec()).map_err(Error::FromUtf8)?;
         // Print better debug when things do fail
         // trace!("Parsing error: {}", text);
@@ -562,6 +809,21 @@ async fn handle_api_errors(res: Response<Body>) -> Result<Response<Body>> {
     }
 }
 
+/// Whether an HTTP/2 stream was closed gracefully by a reset or the connection going away
+///
+/// The apiserver ends watches this way at their timeout, and when rebalancing connections.
+#[cfg(feature = "http2")]
+fn is_stream_reset(err: &(dyn std::error::Error + 'static)) -> bool {
+    let mut source = Some(err);
+    while let Some(err) = source {
+        if let Some(err) = err.downcast_ref::<h2::Error>() {
+            return matches!(err.reason(), Some(h2::Reason::NO_ERROR | h2::Reason::CANCEL));
+        }
+        source = err.source();
+    }
+    false
+}
+
 impl TryFrom<Config> for Client {
     type Error = Error;
 
@@ -777,4 +1039,51 @@ mod tests {
         let _ = pods.watch(&Default::default(), "0").await;
         spawned.await.unwrap();
     }
+
+    #[cfg(feature = "http2")]
+    #[tokio::test]
+    async fn test_watch_ends_on_http2_stream_reset() {
+        use futures::TryStreamExt;
+        use http_body::Frame;
+        use http_body_util::StreamBody;
+
+        let (mock_service, handle) = mock::pair::<Request<Body>, Response<Body>>();
+        let spawned = tokio::spawn(async move {
+            let mut handle = pin!(handle);
+            let (_, send) = handle.next_request().await.expect("service not called");
+            let event = serde_json::json!({
+                "type": "ADDED",
+                "object": { "apiVersion": "v1", "kind": "Pod", "metadata": { "name": "a" } },
+            });
+            let frames: Vec<Result<_, h2::Error>> = vec![
+                Ok(Frame::data(format!("{event}\n").into())),
+                Err(h2::Reason::CANCEL.into()),
+            ];
+            send.send_response(Response::new(Body::wrap_body(StreamBody::new(
+                futures::stream::iter(frames),
+            ))));
+        });
+
+        let pods: Api<Pod> = Api::default_namespaced(Client::new(mock_service, "default"));
+        let events = pods
+            .watch(&Default::default(), "0")
+            .await
+            .unwrap()
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+        assert_eq!(events.len(), 1);
+        spawned.await.unwrap();
+    }
+
+    #[cfg(feature = "http2")]
+    #[test]
+    fn test_is_stream_reset() {
+        use super::is_stream_reset;
+
+        let graceful = crate::Error::Service(Box::new(h2::Error::from(h2::Reason::NO_ERROR)));
+        assert!(is_stream_reset(&graceful));
+        let broken = crate::Error::Service(Box::new(h2::Error::from(h2::Reason::PROTOCOL_ERROR)));
+        assert!(!is_stream_reset(&broken));
+    }
 }
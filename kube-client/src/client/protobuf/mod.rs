@@ -0,0 +1,803 @@
+//! Protobuf wire format support for the built-in types
+//!
+//! The apiserver can serve built-in resource types as `application/vnd.kubernetes.protobuf`,
+//! which is considerably cheaper to produce and to transfer than JSON for large lists and watches.
+//!
+//! When enabled with [`Client::with_protobuf`](crate::Client::with_protobuf), protobuf is negotiated by
+//! [`Client::request`](crate::Client::request), [`Client::request_list`](crate::Client::request_list) and
+//! [`Client::request_events`](crate::Client::request_events) (and so by the methods of [`Api`](crate::Api))
+//! for the resources of the `core/v1`, `apps/v1` and `batch/v1` APIs, as well as their `status` subresources.
+//! Requests for everything else, such as custom resources or other subresources, and requests that already set
+//! an `Accept` header continue to use JSON, as does any response the apiserver decides to send as JSON.
+//! Protobuf takes precedence over CBOR for the resources it is negotiated for.
+//!
+//! Protobuf messages are decoded through a schema of the built-in messages into the JSON representation of
+//! the object, which is then deserialized as usual, so any type deserializing from the JSON of a built-in
+//! resource (like [`DynamicObject`](crate::core::DynamicObject)) can be used.
+//! Zeroes and `false` in optional fields are kept, where the JSON of the apiserver would omit them.
+//!
+//! ```no_run
+//! use k8s_openapi::api::core::v1::Pod;
+//! use kube_client::{Api, Client};
+//!
+//! # async fn wrapper() -> Result<(), Box<dyn std::error::Error>> {
+//! let client = Client::try_default().await?.with_protobuf(true);
+//! let pods: Api<Pod> = Api::default_namespaced(client);
+//! let list = pods.list(&Default::default()).await?; // decoded from protobuf
+//! # Ok(())
+//! # }
+//! ```
+use base64::{Engine, engine::general_purpose::STANDARD};
+use bytes::{Buf, Bytes, BytesMut};
+use http::{HeaderValue, Request, header};
+use kube_core::Status;
+use serde_json::{Map as Object, Value};
+use tokio_util::codec::Decoder;
+
+mod schema;
+
+/// Media type of the protobuf wire format
+pub const PROTOBUF_MIME: &str = "application/vnd.kubernetes.protobuf";
+/// Media type of protobuf encoded watch streams
+pub const PROTOBUF_WATCH_MIME: &str = "application/vnd.kubernetes.protobuf;stream=watch";
+
+/// `Accept` header of requests negotiating protobuf, with JSON as the fallback
+pub(crate) const ACCEPT: &str = "application/vnd.kubernetes.protobuf, application/json";
+/// `Accept` header of watches negotiating protobuf, with JSON as the fallback
+pub(crate) const ACCEPT_WATCH: &str = "application/vnd.kubernetes.protobuf;stream=watch, application/json";
+
+/// Prefix identifying a protobuf encoded `runtime.Unknown` envelope
+const MAGIC: &[u8] = b"k8s\0";
+
+/// The API group versions with built-in types in the schema, with their package in the schema
+const GROUP_VERSIONS: &[(&str, &str, &str)] = &[
+    ("/api/v1/", "v1", "core.v1"),
+    ("/apis/apps/v1/", "apps/v1", "apps.v1"),
+    ("/apis/batch/v1/", "batch/v1", "batch.v1"),
+];
+
+/// The seconds of the zero `time.Time` of Go, which is `null` in JSON
+const GO_ZERO_TIME: i64 = -62_135_596_800;
+
+/// Error decoding a protobuf message
+#[derive(Debug, thiserror::Error)]
+#[error("{0}")]
+pub struct DecodeError(String);
+
+/// The type of a field in the schema
+#[derive(Clone, Copy, Debug)]
+enum Kind {
+    Bool,
+    Bytes,
+    Int32,
+    Int64,
+    /// A map with string keys
+    Map(&'static Kind),
+    /// An embedded message, by its name in the schema
+    Message(&'static str),
+    String,
+}
+
+/// A field of a message in the schema
+#[derive(Debug)]
+struct Field {
+    tag: u32,
+    name: &'static str,
+    kind: Kind,
+    repeated: bool,
+    /// Empty strings are omitted, as they would be from JSON
+    omit_empty: bool,
+    /// The fields of the message are fields of the parent in JSON, like embedded structs in Go
+    inline: bool,
+}
+
+impl Field {
+    const fn new(tag: u32, name: &'static str, kind: Kind) -> Self {
+        Self {
+            tag,
+            name,
+            kind,
+            repeated: false,
+            omit_empty: false,
+            inline: false,
+        }
+    }
+
+    const fn repeated(self) -> Self {
+        Self { repeated: true, ..self }
+    }
+
+    const fn omit_empty(self) -> Self {
+        Self {
+            omit_empty: true,
+            ..self
+        }
+    }
+
+    const fn inline(self) -> Self {
+        Self { inline: true, ..self }
+    }
+}
+
+/// A message in the schema, with its fields sorted by tag
+#[derive(Debug)]
+struct Schema {
+    name: &'static str,
+    fields: &'static [Field],
+}
+
+impl Schema {
+    const fn new(name: &'static str, fields: &'static [Field]) -> Self {
+        Self { name, fields }
+    }
+
+    fn get(name: &str) -> Option<&'static Self> {
+        let index = schema::MESSAGES.binary_search_by(|message| message.name.cmp(name)).ok()?;
+        Some(&schema::MESSAGES[index])
+    }
+
+    fn field(&self, tag: u32) -> Option<&'static Field> {
+        let index = self.fields.binary_search_by_key(&tag, |field| field.tag).ok()?;
+        Some(&self.fields[index])
+    }
+}
+
+/// Whether protobuf can be negotiated for a request
+///
+/// That is, whether it is for a resource of the built-in types in the schema, or its `status` subresource,
+/// and does not ask for a specific media type already.
+pub(crate) fn negotiable(request: &Request<Vec<u8>>) -> bool {
+    if request.headers().contains_key(header::ACCEPT) {
+        return false;
+    }
+    let path = request.uri().path();
+    let Some(rest) = GROUP_VERSIONS
+        .iter()
+        .find_map(|(prefix, ..)| path.strip_prefix(prefix))
+    else {
+        return false;
+    };
+    let mut segments = rest.split('/').collect::<Vec<_>>();
+    // Resources in a namespace, rather than the subresources of the namespace itself
+    if segments.len() > 2 && segments[0] == "namespaces" && !matches!(segments[2], "status" | "finalize") {
+        segments.drain(..2);
+    }
+    matches!(segments.as_slice(), [resource] | [resource, _] | [resource, _, "status"] if !resource.is_empty())
+}
+
+/// Whether a response `Content-Type` is a protobuf media type
+pub(crate) fn is_protobuf(content_type: Option<&HeaderValue>) -> bool {
+    content_type
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with(PROTOBUF_MIME))
+}
+
+/// Decode an enveloped protobuf object into its JSON representation
+pub(crate) fn decode(body: &[u8]) -> Result<Value, DecodeError> {
+    let body = body
+        .strip_prefix(MAGIC)
+        .ok_or_else(|| DecodeError("protobuf message without an envelope".into()))?;
+    let mut api_version = "";
+    let mut kind = "";
+    let mut raw: &[u8] = &[];
+    for field in Reader(body) {
+        match field? {
+            (1, Wire::Bytes(types)) => {
+                for field in Reader(types) {
+                    match field? {
+                        (1, Wire::Bytes(value)) => api_version = utf8(value)?,
+                        (2, Wire::Bytes(value)) => kind = utf8(value)?,
+                        _ => {}
+                    }
+                }
+            }
+            (2, Wire::Bytes(value)) => raw = value,
+            _ => {}
+        }
+    }
+
+    let package = GROUP_VERSIONS
+        .iter()
+        .find_map(|(_, group_version, package)| (*group_version == api_version).then_some(*package));
+    let schema = package
+        .and_then(|package| Schema::get(&format!("{package}.{kind}")))
+        .or_else(|| Schema::get(&format!("meta.v1.{kind}")))
+        .ok_or_else(|| DecodeError(format!("no protobuf schema for {api_version} {kind}")))?;
+    let mut object = Object::new();
+    object.insert("apiVersion".into(), api_version.into());
+    object.insert("kind".into(), kind.into());
+    decode_fields(schema, raw, &mut object)?;
+    Ok(Value::Object(object))
+}
+
+/// Decode an enveloped `metav1.Status`
+pub(crate) fn decode_status(body: &[u8]) -> Option<Status> {
+    serde_json::from_value(decode(body).ok()?).ok()
+}
+
+/// Decode a frame of a watch stream into the JSON representation of its `metav1.WatchEvent`
+pub(crate) fn decode_watch_event(frame: &[u8]) -> Result<Value, DecodeError> {
+    // Frames are usually sent without the envelope, as its type is always `WatchEvent`
+    let mut event = frame;
+    if let Some(body) = frame.strip_prefix(MAGIC) {
+        for field in Reader(body) {
+            if let (2, Wire::Bytes(raw)) = field? {
+                event = raw;
+            }
+        }
+    }
+    let mut kind = Value::Null;
+    let mut object = Value::Null;
+    for field in Reader(event) {
+        match field? {
+            (1, Wire::Bytes(value)) => kind = utf8(value)?.into(),
+            (2, Wire::Bytes(value)) => object = decode_message("runtime.RawExtension", value)?,
+            _ => {}
+        }
+    }
+    Ok(serde_json::json!({ "type": kind, "object": object }))
+}
+
+/// Decode the fields of a message of `schema` into `object`
+fn decode_fields(schema: &Schema, message: &[u8], object: &mut Object<String, Value>) -> Result<(), DecodeError> {
+    for field in Reader(message) {
+        let (tag, wire) = field?;
+        let Some(field) = schema.field(tag) else {
+            continue;
+        };
+        let invalid = || DecodeError(format!("invalid wire type for {}.{}", schema.name, field.name));
+        match (field.kind, wire) {
+            (Kind::Message(name), Wire::Bytes(message)) if field.inline => {
+                let inlined = Schema::get(name).ok_or_else(|| DecodeError(format!("no protobuf schema for {name}")))?;
+                decode_fields(inlined, message, object)?;
+            }
+            (Kind::Map(kind), Wire::Bytes(entry)) => {
+                let (key, value) = decode_entry(*kind, entry)?;
+                let map = object
+                    .entry(field.name)
+                    .or_insert_with(|| Value::Object(Object::new()));
+                if let Value::Object(map) = map {
+                    map.insert(key, value);
+                }
+            }
+            (kind, wire) if field.repeated => {
+                let values = object.entry(field.name).or_insert_with(|| Value::Array(Vec::new()));
+                let Value::Array(values) = values else {
+                    return Err(invalid());
+                };
+                match (kind, wire) {
+                    // Packed numbers and booleans
+                    (Kind::Bool | Kind::Int32 | Kind::Int64, Wire::Bytes(mut packed)) => {
+                        while !packed.is_empty() {
+                            values.push(decode_value(kind, Wire::Varint(varint(&mut packed)?)).ok_or_else(invalid)??);
+                        }
+                    }
+                    (kind, wire) => values.push(decode_value(kind, wire).ok_or_else(invalid)??),
+                }
+            }
+            (kind, wire) => {
+                let value = decode_value(kind, wire).ok_or_else(invalid)??;
+                if !(field.omit_empty && value == "") {
+                    object.insert(field.name.into(), value);
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Decode a single value of `kind`, or `None` if the wire type does not match
+fn decode_value(kind: Kind, wire: Wire<'_>) -> Option<Result<Value, DecodeError>> {
+    Some(match (kind, wire) {
+        (Kind::Bool, Wire::Varint(value)) => Ok(Value::Bool(value != 0)),
+        // Negative numbers are sign extended to 64 bits
+        (Kind::Int32, Wire::Varint(value)) => Ok((value as i32).into()),
+        (Kind::Int64, Wire::Varint(value)) => Ok((value as i64).into()),
+        (Kind::String, Wire::Bytes(value)) => utf8(value).map(Into::into),
+        (Kind::Bytes, Wire::Bytes(value)) => Ok(STANDARD.encode(value).into()),
+        (Kind::Message(name), Wire::Bytes(message)) => decode_message(name, message),
+        _ => return None,
+    })
+}
+
+/// Decode a map entry, with the key as field 1 and the value as field 2
+fn decode_entry(kind: Kind, entry: &[u8]) -> Result<(String, Value), DecodeError> {
+    let mut key = String::new();
+    let mut value = Value::Null;
+    for field in Reader(entry) {
+        match field? {
+            (1, Wire::Bytes(bytes)) => utf8(bytes)?.clone_into(&mut key),
+            (2, wire) => {
+                value = decode_value(kind, wire)
+                    .ok_or_else(|| DecodeError(format!("invalid wire type for map value of {kind:?}")))??;
+            }
+            _ => {}
+        }
+    }
+    Ok((key, value))
+}
+
+/// Decode a message into its JSON representation, which is not an object for some well-known messages
+fn decode_message(name: &str, message: &[u8]) -> Result<Value, DecodeError> {
+    if matches!(name, "meta.v1.FieldsV1" | "runtime.RawExtension") {
+        let mut raw: &[u8] = &[];
+        for field in Reader(message) {
+            if let (1, Wire::Bytes(value)) = field? {
+                raw = value;
+            }
+        }
+        return decode_raw(raw);
+    }
+
+    let schema = Schema::get(name).ok_or_else(|| DecodeError(format!("no protobuf schema for {name}")))?;
+    let mut object = Object::new();
+    decode_fields(schema, message, &mut object)?;
+    let number = |name| object.get(name).and_then(Value::as_i64).unwrap_or_default();
+    Ok(match name {
+        "resource.Quantity" => object.remove("string").unwrap_or_else(|| "".into()),
+        "intstr.IntOrString" if number("type") == 1 => object.remove("strVal").unwrap_or_else(|| "".into()),
+        "intstr.IntOrString" => number("intVal").into(),
+        "meta.v1.Time" | "meta.v1.MicroTime" if number("seconds") == GO_ZERO_TIME => Value::Null,
+        "meta.v1.Time" => jiff::Timestamp::from_second(number("seconds"))
+            .map_err(|e| DecodeError(e.to_string()))?
+            .to_string()
+            .into(),
+        "meta.v1.MicroTime" => jiff::Timestamp::new(number("seconds"), i32::try_from(number("nanos")).unwrap_or_default())
+            .map_err(|e| DecodeError(e.to_string()))?
+            .strftime("%Y-%m-%dT%H:%M:%S%.6fZ")
+            .to_string()
+            .into(),
+        _ => Value::Object(object),
+    })
+}
+
+/// Decode raw bytes embedded in a message, which are either JSON or an enveloped protobuf object
+fn decode_raw(raw: &[u8]) -> Result<Value, DecodeError> {
+    if raw.is_empty() {
+        Ok(Value::Null)
+    } else if raw.starts_with(MAGIC) {
+        decode(raw)
+    } else {
+        serde_json::from_slice(raw).map_err(|e| DecodeError(format!("invalid embedded json: {e}")))
+    }
+}
+
+fn utf8(bytes: &[u8]) -> Result<&str, DecodeError> {
+    std::str::from_utf8(bytes).map_err(|e| DecodeError(format!("invalid string: {e}")))
+}
+
+fn varint(buf: &mut &[u8]) -> Result<u64, DecodeError> {
+    let mut value = 0;
+    for shift in (0..64).step_by(7) {
+        let (&byte, rest) = buf
+            .split_first()
+            .ok_or_else(|| DecodeError("truncated varint".into()))?;
+        *buf = rest;
+        value |= u64::from(byte & 0x7f) << shift;
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+    Err(DecodeError("varint is longer than 64 bits".into()))
+}
+
+/// The value of a field on the wire
+enum Wire<'a> {
+    Varint(u64),
+    Bytes(&'a [u8]),
+    /// A fixed size value, which no field in the schema has
+    Fixed,
+}
+
+/// Iterates over the tags and values of the fields of a message
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: u64) -> Result<&'a [u8], DecodeError> {
+        let len = usize::try_from(len)
+            .ok()
+            .filter(|len| *len <= self.0.len())
+            .ok_or_else(|| DecodeError("truncated message".into()))?;
+        let (value, rest) = self.0.split_at(len);
+        self.0 = rest;
+        Ok(value)
+    }
+
+    fn field(&mut self) -> Result<(u32, Wire<'a>), DecodeError> {
+        let key = varint(&mut self.0)?;
+        let tag = u32::try_from(key >> 3).map_err(|_| DecodeError("invalid field tag".into()))?;
+        let wire = match key & 0b111 {
+            0 => Wire::Varint(varint(&mut self.0)?),
+            1 => self.take(8).map(|_| Wire::Fixed)?,
+            2 => {
+                let len = varint(&mut self.0)?;
+                Wire::Bytes(self.take(len)?)
+            }
+            5 => self.take(4).map(|_| Wire::Fixed)?,
+            wire_type => return Err(DecodeError(format!("unsupported wire type {wire_type}"))),
+        };
+        Ok((tag, wire))
+    }
+}
+
+impl<'a> Iterator for Reader<'a> {
+    type Item = Result<(u32, Wire<'a>), DecodeError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.0.is_empty() {
+            return None;
+        }
+        let field = self.field();
+        if field.is_err() {
+            // Stop after the first error, as the rest of the message can't be read
+            self.0 = &[];
+        }
+        Some(field)
+    }
+}
+
+/// Maximum length of a single frame of a protobuf watch stream
+///
+/// This is far above the size limit of objects in etcd, and only guards against allocating for a corrupt length.
+const MAX_WATCH_FRAME_LENGTH: usize = 64 * 1024 * 1024;
+
+/// Decoder for the length-delimited frames of a protobuf watch stream
+#[derive(Default)]
+pub(crate) struct WatchFrames;
+
+impl Decoder for WatchFrames {
+    type Error = std::io::Error;
+    type Item = Bytes;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        if src.len() < 4 {
+            return Ok(None);
+        }
+        let len = u32::from_be_bytes([src[0], src[1], src[2], src[3]]) as usize;
+        if len > MAX_WATCH_FRAME_LENGTH {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("watch frame of {len} bytes exceeds the maximum of {MAX_WATCH_FRAME_LENGTH} bytes"),
+            ));
+        }
+        if src.len() < 4 + len {
+            src.reserve(4 + len - src.len());
+            return Ok(None);
+        }
+        src.advance(4);
+        Ok(Some(src.split_to(len).freeze()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::{StreamExt, TryStreamExt};
+    use http::Response;
+    use k8s_openapi::api::core::v1::{Event, Pod, Volume};
+    use kube_core::{DynamicObject, WatchEvent, params::WatchParams};
+    use tower_test::mock;
+
+    use crate::{Api, Client, client::Body, core::ApiResource};
+
+    fn varint_to(mut value: u64, out: &mut Vec<u8>) {
+        while value >= 0x80 {
+            out.push((value as u8) | 0x80);
+            value >>= 7;
+        }
+        out.push(value as u8);
+    }
+
+    /// A length-delimited field
+    fn bytes(tag: u64, value: impl AsRef<[u8]>) -> Vec<u8> {
+        let mut out = Vec::new();
+        varint_to(tag << 3 | 2, &mut out);
+        varint_to(value.as_ref().len() as u64, &mut out);
+        out.extend_from_slice(value.as_ref());
+        out
+    }
+
+    /// A varint field
+    fn number(tag: u64, value: i64) -> Vec<u8> {
+        let mut out = Vec::new();
+        varint_to(tag << 3, &mut out);
+        varint_to(value as u64, &mut out);
+        out
+    }
+
+    fn envelope(api_version: &str, kind: &str, raw: Vec<u8>) -> Vec<u8> {
+        let types = [bytes(1, api_version), bytes(2, kind)].concat();
+        [MAGIC.to_vec(), bytes(1, types), bytes(2, raw)].concat()
+    }
+
+    fn object_meta(name: &str) -> Vec<u8> {
+        [
+            bytes(1, name),
+            bytes(2, ""),
+            bytes(3, "default"),
+            number(7, 0),
+            bytes(8, number(1, 1_700_000_000)),
+            bytes(11, [bytes(1, "app"), bytes(2, "web")].concat()),
+        ]
+        .concat()
+    }
+
+    fn pod(name: &str) -> Vec<u8> {
+        let http_get = [bytes(1, "/healthz"), bytes(2, number(2, 8080))].concat();
+        let probe = [bytes(1, bytes(2, http_get)), number(2, 5)].concat();
+        let limits = bytes(1, [bytes(1, "cpu"), bytes(2, bytes(1, "100m"))].concat());
+        let container = [
+            bytes(1, "app"),
+            bytes(2, "nginx"),
+            bytes(8, limits),
+            bytes(10, probe),
+        ]
+        .concat();
+        let status = [bytes(1, "Running"), bytes(6, ""), bytes(12, bytes(1, "10.0.0.1"))].concat();
+        [
+            bytes(1, object_meta(name)),
+            bytes(2, bytes(2, container)),
+            bytes(3, status),
+        ]
+        .concat()
+    }
+
+    fn status(code: i64, reason: &str) -> Vec<u8> {
+        envelope(
+            "v1",
+            "Status",
+            [bytes(2, "Failure"), bytes(4, reason), number(6, code)].concat(),
+        )
+    }
+
+    fn protobuf_response(content_type: &'static str, body: Vec<u8>) -> Response<Body> {
+        Response::builder()
+            .header(header::CONTENT_TYPE, content_type)
+            .body(Body::from(body))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn negotiates_built_in_types_through_api() {
+        let (mock_service, handle) = mock::pair::<http::Request<Body>, Response<Body>>();
+        let client = Client::new(mock_service, "default").with_protobuf(true);
+        let pods: Api<Pod> = Api::default_namespaced(client);
+
+        let spawned = tokio::spawn(async move {
+            let mut handle = std::pin::pin!(handle);
+
+            let (request, send) = handle.next_request().await.expect("service not called");
+            assert_eq!(request.headers()[header::ACCEPT], ACCEPT);
+            let list = [
+                bytes(1, bytes(2, "10")),
+                bytes(2, pod("a")),
+                bytes(2, pod("b")),
+            ]
+            .concat();
+            send.send_response(protobuf_response(PROTOBUF_MIME, envelope("v1", "PodList", list)));
+
+            let (request, send) = handle.next_request().await.expect("service not called");
+            assert_eq!(request.headers()[header::ACCEPT], ACCEPT);
+            let mut response = protobuf_response(PROTOBUF_MIME, status(404, "NotFound"));
+            *response.status_mut() = http::StatusCode::NOT_FOUND;
+            send.send_response(response);
+
+            let (request, send) = handle.next_request().await.expect("service not called");
+            assert_eq!(request.headers()[header::ACCEPT], ACCEPT_WATCH);
+            let mut stream = Vec::new();
+            for event in [
+                [bytes(1, "ADDED"), bytes(2, bytes(1, envelope("v1", "Pod", pod("c"))))].concat(),
+                [bytes(1, "ERROR"), bytes(2, bytes(1, status(410, "Expired")))].concat(),
+            ] {
+                stream.extend_from_slice(&u32::try_from(event.len()).unwrap().to_be_bytes());
+                stream.extend(event);
+            }
+            send.send_response(protobuf_response(PROTOBUF_WATCH_MIME, stream));
+        });
+
+        let list = pods.list(&Default::default()).await.unwrap();
+        assert_eq!(list.metadata.resource_version.as_deref(), Some("10"));
+        let names = list.items.iter().map(|pod| pod.metadata.name.as_deref().unwrap());
+        assert_eq!(names.collect::<Vec<_>>(), ["a", "b"]);
+        let pod = &list.items[0];
+        assert_eq!(pod.metadata.namespace.as_deref(), Some("default"));
+        assert_eq!(pod.metadata.generate_name, None);
+        assert_eq!(pod.metadata.labels.as_ref().unwrap()["app"], "web");
+        assert_eq!(
+            pod.metadata.creation_timestamp.as_ref().unwrap().0.to_string(),
+            "2023-11-14T22:13:20Z"
+        );
+        let container = &pod.spec.as_ref().unwrap().containers[0];
+        assert_eq!(container.image.as_deref(), Some("nginx"));
+        assert_eq!(container.resources.as_ref().unwrap().limits.as_ref().unwrap()["cpu"].0, "100m");
+        let probe = container.liveness_probe.as_ref().unwrap();
+        assert_eq!(probe.initial_delay_seconds, Some(5));
+        let http_get = probe.http_get.as_ref().unwrap();
+        assert_eq!(http_get.path.as_deref(), Some("/healthz"));
+        assert_eq!(
+            http_get.port,
+            k8s_openapi::apimachinery::pkg::util::intstr::IntOrString::Int(8080)
+        );
+        let status = pod.status.as_ref().unwrap();
+        assert_eq!(status.phase.as_deref(), Some("Running"));
+        assert_eq!(status.pod_ip, None);
+        assert_eq!(status.pod_ips.as_ref().unwrap()[0].ip, "10.0.0.1");
+
+        let err = pods.get("missing").await.unwrap_err();
+        assert!(matches!(err, crate::Error::Api(status) if status.code == 404 && status.reason == "NotFound"));
+
+        let events = pods
+            .watch(&WatchParams::default(), "10")
+            .await
+            .unwrap()
+            .collect::<Vec<_>>()
+            .await;
+        assert!(
+            matches!(&events[0], Ok(WatchEvent::Added(pod)) if pod.metadata.name.as_deref() == Some("c"))
+        );
+        assert!(matches!(&events[1], Ok(WatchEvent::Error(status)) if status.code == 410));
+        spawned.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn decodes_json_responses_and_custom_resources() {
+        let (mock_service, handle) = mock::pair::<http::Request<Body>, Response<Body>>();
+        let client = Client::new(mock_service, "default").with_protobuf(true);
+        let pods: Api<Pod> = Api::default_namespaced(client.clone());
+        let resource = ApiResource::from_gvk(&kube_core::GroupVersionKind::gvk("example.com", "v1", "Widget"));
+        let widgets: Api<DynamicObject> = Api::default_namespaced_with(client, &resource);
+
+        let spawned = tokio::spawn(async move {
+            let mut handle = std::pin::pin!(handle);
+
+            let (request, send) = handle.next_request().await.expect("service not called");
+            assert_eq!(request.headers()[header::ACCEPT], ACCEPT);
+            let pod = serde_json::json!({ "apiVersion": "v1", "kind": "Pod", "metadata": { "name": "a" } });
+            send.send_response(Response::new(Body::from(serde_json::to_vec(&pod).unwrap())));
+
+            let (request, send) = handle.next_request().await.expect("service not called");
+            assert!(request.headers().get(header::ACCEPT).is_none());
+            let widget =
+                serde_json::json!({ "apiVersion": "example.com/v1", "kind": "Widget", "metadata": { "name": "b" } });
+            send.send_response(Response::new(Body::from(serde_json::to_vec(&widget).unwrap())));
+        });
+
+        assert_eq!(pods.get("a").await.unwrap().metadata.name.as_deref(), Some("a"));
+        assert_eq!(widgets.get("b").await.unwrap().metadata.name.as_deref(), Some("b"));
+        spawned.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn decodes_built_in_types_as_dynamic_objects() {
+        let (mock_service, handle) = mock::pair::<http::Request<Body>, Response<Body>>();
+        let client = Client::new(mock_service, "default").with_protobuf(true);
+        let resource = ApiResource::erase::<Pod>(&());
+        let pods: Api<DynamicObject> = Api::default_namespaced_with(client, &resource);
+
+        let spawned = tokio::spawn(async move {
+            let mut handle = std::pin::pin!(handle);
+            let (request, send) = handle.next_request().await.expect("service not called");
+            assert_eq!(request.headers()[header::ACCEPT], ACCEPT);
+            send.send_response(protobuf_response(PROTOBUF_MIME, envelope("v1", "Pod", pod("a"))));
+        });
+
+        let pod = pods.get("a").await.unwrap();
+        assert_eq!(pod.types.unwrap().kind, "Pod");
+        assert_eq!(pod.data["spec"]["containers"][0]["image"], "nginx");
+        spawned.await.unwrap();
+    }
+
+    #[test]
+    fn negotiates_resources_and_status_of_built_in_types() {
+        let negotiable = |path: &str| negotiable(&http::Request::get(path).body(vec![]).unwrap());
+        assert!(negotiable("/api/v1/namespaces/default/pods"));
+        assert!(negotiable("/api/v1/namespaces/default/pods/a"));
+        assert!(negotiable("/api/v1/namespaces/default/pods/a/status"));
+        assert!(negotiable("/api/v1/pods?watch=true"));
+        assert!(negotiable("/api/v1/namespaces"));
+        assert!(negotiable("/api/v1/namespaces/default"));
+        assert!(negotiable("/api/v1/namespaces/default/status"));
+        assert!(negotiable("/apis/apps/v1/namespaces/default/deployments/a"));
+        assert!(negotiable("/apis/batch/v1/jobs"));
+
+        assert!(!negotiable("/api/v1/namespaces/default/finalize"));
+        assert!(!negotiable("/api/v1/namespaces/default/pods/a/log"));
+        assert!(!negotiable("/apis/apps/v1/namespaces/default/deployments/a/scale"));
+        assert!(!negotiable("/apis/example.com/v1/namespaces/default/widgets"));
+        assert!(!negotiable("/apis/apps/v1"));
+        assert!(!negotiable("/version"));
+
+        let metadata = http::Request::get("/api/v1/pods")
+            .header(header::ACCEPT, "application/json;as=PartialObjectMetadataList;g=meta.k8s.io;v=v1")
+            .body(vec![])
+            .unwrap();
+        assert!(!super::negotiable(&metadata));
+    }
+
+    #[test]
+    fn decodes_inlined_fields() {
+        let config_map = [bytes(1, bytes(1, "settings")), number(4, 1)].concat();
+        let volume = [bytes(1, "config"), bytes(2, bytes(19, config_map))].concat();
+        let volume: Volume = serde_json::from_value(decode_message("core.v1.Volume", &volume).unwrap()).unwrap();
+        assert_eq!(volume.name, "config");
+        let config_map = volume.config_map.unwrap();
+        assert_eq!(config_map.name, "settings");
+        assert_eq!(config_map.optional, Some(true));
+    }
+
+    #[test]
+    fn decodes_well_known_messages() {
+        let event = [
+            bytes(1, object_meta("a")),
+            bytes(10, [number(1, 1_700_000_000), number(2, 123_456_789)].concat()),
+            bytes(6, number(1, GO_ZERO_TIME)),
+        ]
+        .concat();
+        let value = decode(&envelope("v1", "Event", event)).unwrap();
+        assert_eq!(value["eventTime"], "2023-11-14T22:13:20.123456Z");
+        assert_eq!(value["firstTimestamp"], Value::Null);
+        let event: Event = serde_json::from_value(value).unwrap();
+        assert_eq!(event.first_timestamp, None);
+
+        let port = [number(1, 1), bytes(3, "http")].concat();
+        assert_eq!(decode_message("intstr.IntOrString", &port).unwrap(), "http");
+        let negative = number(2, -1);
+        assert_eq!(decode_message("intstr.IntOrString", &negative).unwrap(), -1);
+
+        let fields = bytes(1, r#"{"f:metadata":{}}"#);
+        assert_eq!(
+            decode_message("meta.v1.FieldsV1", &fields).unwrap(),
+            serde_json::json!({ "f:metadata": {} })
+        );
+    }
+
+    #[test]
+    fn rejects_truncated_messages() {
+        let mut truncated = envelope("v1", "Pod", pod("a"));
+        truncated.truncate(truncated.len() - 1);
+        assert!(decode(&truncated).is_err());
+        assert!(decode(&pod("a")).is_err());
+    }
+
+    #[test]
+    fn schema_is_sorted_and_complete() {
+        assert!(schema::MESSAGES.is_sorted_by(|a, b| a.name < b.name));
+        for message in schema::MESSAGES {
+            assert!(message.fields.is_sorted_by(|a, b| a.tag < b.tag), "{}", message.name);
+            for field in message.fields {
+                if let Kind::Message(name) | Kind::Map(&Kind::Message(name)) = field.kind {
+                    assert!(Schema::get(name).is_some(), "{}.{} is missing {name}", message.name, field.name);
+                }
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn watch_events_stop_at_protobuf_errors() {
+        let (mock_service, handle) = mock::pair::<http::Request<Body>, Response<Body>>();
+        let client = Client::new(mock_service, "default").with_protobuf(true);
+        let pods: Api<Pod> = Api::default_namespaced(client);
+
+        let spawned = tokio::spawn(async move {
+            let mut handle = std::pin::pin!(handle);
+            let (_, send) = handle.next_request().await.expect("service not called");
+            let event = [bytes(1, "ADDED"), bytes(2, bytes(1, b"k8s\0\x0a"))].concat();
+            let mut stream = u32::try_from(event.len()).unwrap().to_be_bytes().to_vec();
+            stream.extend(event);
+            send.send_response(protobuf_response(PROTOBUF_WATCH_MIME, stream));
+        });
+
+        let events = pods.watch(&WatchParams::default(), "0").await.unwrap();
+        let err = events.try_collect::<Vec<_>>().await.unwrap_err();
+        assert!(matches!(err, crate::Error::ProtobufDecode(_)));
+        spawned.await.unwrap();
+    }
+
+    #[test]
+    fn watch_frames_reject_oversized_lengths() {
+        let mut src = BytesMut::from(&u32::MAX.to_be_bytes()[..]);
+        let err = WatchFrames.decode(&mut src).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+        assert!(src.capacity() < 1024);
+    }
+}
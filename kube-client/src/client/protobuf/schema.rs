@@ -0,0 +1,2030 @@
+// Generated by scripts/protobuf-schema.py from k8s-pb-0.9.0 and k8s-openapi-0.28.0 (v1_36), do not edit
+use super::{
+    Field,
+    Kind::{Bool, Bytes, Int32, Int64, Map, Message, String},
+    Schema,
+};
+
+/// Messages of the built-in types, sorted by name
+#[rustfmt::skip]
+pub(super) static MESSAGES: &[Schema] = &[
+    Schema::new("apps.v1.ControllerRevision", &[
+        Field::new(1, "metadata", Message("meta.v1.ObjectMeta")),
+        Field::new(2, "data", Message("runtime.RawExtension")),
+        Field::new(3, "revision", Int64),
+    ]),
+    Schema::new("apps.v1.ControllerRevisionList", &[
+        Field::new(1, "metadata", Message("meta.v1.ListMeta")),
+        Field::new(2, "items", Message("apps.v1.ControllerRevision")).repeated(),
+    ]),
+    Schema::new("apps.v1.DaemonSet", &[
+        Field::new(1, "metadata", Message("meta.v1.ObjectMeta")),
+        Field::new(2, "spec", Message("apps.v1.DaemonSetSpec")),
+        Field::new(3, "status", Message("apps.v1.DaemonSetStatus")),
+    ]),
+    Schema::new("apps.v1.DaemonSetCondition", &[
+        Field::new(1, "type", String),
+        Field::new(2, "status", String),
+        Field::new(3, "lastTransitionTime", Message("meta.v1.Time")),
+        Field::new(4, "reason", String).omit_empty(),
+        Field::new(5, "message", String).omit_empty(),
+    ]),
+    Schema::new("apps.v1.DaemonSetList", &[
+        Field::new(1, "metadata", Message("meta.v1.ListMeta")),
+        Field::new(2, "items", Message("apps.v1.DaemonSet")).repeated(),
+    ]),
+    Schema::new("apps.v1.DaemonSetSpec", &[
+        Field::new(1, "selector", Message("meta.v1.LabelSelector")),
+        Field::new(2, "template", Message("core.v1.PodTemplateSpec")),
+        Field::new(3, "updateStrategy", Message("apps.v1.DaemonSetUpdateStrategy")),
+        Field::new(4, "minReadySeconds", Int32),
+        Field::new(6, "revisionHistoryLimit", Int32),
+    ]),
+    Schema::new("apps.v1.DaemonSetStatus", &[
+        Field::new(1, "currentNumberScheduled", Int32),
+        Field::new(2, "numberMisscheduled", Int32),
+        Field::new(3, "desiredNumberScheduled", Int32),
+        Field::new(4, "numberReady", Int32),
+        Field::new(5, "observedGeneration", Int64),
+        Field::new(6, "updatedNumberScheduled", Int32),
+        Field::new(7, "numberAvailable", Int32),
+        Field::new(8, "numberUnavailable", Int32),
+        Field::new(9, "collisionCount", Int32),
+        Field::new(10, "conditions", Message("apps.v1.DaemonSetCondition")).repeated(),
+    ]),
+    Schema::new("apps.v1.DaemonSetUpdateStrategy", &[
+        Field::new(1, "type", String).omit_empty(),
+        Field::new(2, "rollingUpdate", Message("apps.v1.RollingUpdateDaemonSet")),
+    ]),
+    Schema::new("apps.v1.Deployment", &[
+        Field::new(1, "metadata", Message("meta.v1.ObjectMeta")),
+        Field::new(2, "spec", Message("apps.v1.DeploymentSpec")),
+        Field::new(3, "status", Message("apps.v1.DeploymentStatus")),
+    ]),
+    Schema::new("apps.v1.DeploymentCondition", &[
+        Field::new(1, "type", String),
+        Field::new(2, "status", String),
+        Field::new(4, "reason", String).omit_empty(),
+        Field::new(5, "message", String).omit_empty(),
+        Field::new(6, "lastUpdateTime", Message("meta.v1.Time")),
+        Field::new(7, "lastTransitionTime", Message("meta.v1.Time")),
+    ]),
+    Schema::new("apps.v1.DeploymentList", &[
+        Field::new(1, "metadata", Message("meta.v1.ListMeta")),
+        Field::new(2, "items", Message("apps.v1.Deployment")).repeated(),
+    ]),
+    Schema::new("apps.v1.DeploymentSpec", &[
+        Field::new(1, "replicas", Int32),
+        Field::new(2, "selector", Message("meta.v1.LabelSelector")),
+        Field::new(3, "template", Message("core.v1.PodTemplateSpec")),
+        Field::new(4, "strategy", Message("apps.v1.DeploymentStrategy")),
+        Field::new(5, "minReadySeconds", Int32),
+        Field::new(6, "revisionHistoryLimit", Int32),
+        Field::new(7, "paused", Bool),
+        Field::new(9, "progressDeadlineSeconds", Int32),
+    ]),
+    Schema::new("apps.v1.DeploymentStatus", &[
+        Field::new(1, "observedGeneration", Int64),
+        Field::new(2, "replicas", Int32),
+        Field::new(3, "updatedReplicas", Int32),
+        Field::new(4, "availableReplicas", Int32),
+        Field::new(5, "unavailableReplicas", Int32),
+        Field::new(6, "conditions", Message("apps.v1.DeploymentCondition")).repeated(),
+        Field::new(7, "readyReplicas", Int32),
+        Field::new(8, "collisionCount", Int32),
+        Field::new(9, "terminatingReplicas", Int32),
+    ]),
+    Schema::new("apps.v1.DeploymentStrategy", &[
+        Field::new(1, "type", String).omit_empty(),
+        Field::new(2, "rollingUpdate", Message("apps.v1.RollingUpdateDeployment")),
+    ]),
+    Schema::new("apps.v1.ReplicaSet", &[
+        Field::new(1, "metadata", Message("meta.v1.ObjectMeta")),
+        Field::new(2, "spec", Message("apps.v1.ReplicaSetSpec")),
+        Field::new(3, "status", Message("apps.v1.ReplicaSetStatus")),
+    ]),
+    Schema::new("apps.v1.ReplicaSetCondition", &[
+        Field::new(1, "type", String),
+        Field::new(2, "status", String),
+        Field::new(3, "lastTransitionTime", Message("meta.v1.Time")),
+        Field::new(4, "reason", String).omit_empty(),
+        Field::new(5, "message", String).omit_empty(),
+    ]),
+    Schema::new("apps.v1.ReplicaSetList", &[
+        Field::new(1, "metadata", Message("meta.v1.ListMeta")),
+        Field::new(2, "items", Message("apps.v1.ReplicaSet")).repeated(),
+    ]),
+    Schema::new("apps.v1.ReplicaSetSpec", &[
+        Field::new(1, "replicas", Int32),
+        Field::new(2, "selector", Message("meta.v1.LabelSelector")),
+        Field::new(3, "template", Message("core.v1.PodTemplateSpec")),
+        Field::new(4, "minReadySeconds", Int32),
+    ]),
+    Schema::new("apps.v1.ReplicaSetStatus", &[
+        Field::new(1, "replicas", Int32),
+        Field::new(2, "fullyLabeledReplicas", Int32),
+        Field::new(3, "observedGeneration", Int64),
+        Field::new(4, "readyReplicas", Int32),
+        Field::new(5, "availableReplicas", Int32),
+        Field::new(6, "conditions", Message("apps.v1.ReplicaSetCondition")).repeated(),
+        Field::new(7, "terminatingReplicas", Int32),
+    ]),
+    Schema::new("apps.v1.RollingUpdateDaemonSet", &[
+        Field::new(1, "maxUnavailable", Message("intstr.IntOrString")),
+        Field::new(2, "maxSurge", Message("intstr.IntOrString")),
+    ]),
+    Schema::new("apps.v1.RollingUpdateDeployment", &[
+        Field::new(1, "maxUnavailable", Message("intstr.IntOrString")),
+        Field::new(2, "maxSurge", Message("intstr.IntOrString")),
+    ]),
+    Schema::new("apps.v1.RollingUpdateStatefulSetStrategy", &[
+        Field::new(1, "partition", Int32),
+        Field::new(2, "maxUnavailable", Message("intstr.IntOrString")),
+    ]),
+    Schema::new("apps.v1.StatefulSet", &[
+        Field::new(1, "metadata", Message("meta.v1.ObjectMeta")),
+        Field::new(2, "spec", Message("apps.v1.StatefulSetSpec")),
+        Field::new(3, "status", Message("apps.v1.StatefulSetStatus")),
+    ]),
+    Schema::new("apps.v1.StatefulSetCondition", &[
+        Field::new(1, "type", String),
+        Field::new(2, "status", String),
+        Field::new(3, "lastTransitionTime", Message("meta.v1.Time")),
+        Field::new(4, "reason", String).omit_empty(),
+        Field::new(5, "message", String).omit_empty(),
+    ]),
+    Schema::new("apps.v1.StatefulSetList", &[
+        Field::new(1, "metadata", Message("meta.v1.ListMeta")),
+        Field::new(2, "items", Message("apps.v1.StatefulSet")).repeated(),
+    ]),
+    Schema::new("apps.v1.StatefulSetOrdinals", &[
+        Field::new(1, "start", Int32),
+    ]),
+    Schema::new("apps.v1.StatefulSetPersistentVolumeClaimRetentionPolicy", &[
+        Field::new(1, "whenDeleted", String).omit_empty(),
+        Field::new(2, "whenScaled", String).omit_empty(),
+    ]),
+    Schema::new("apps.v1.StatefulSetSpec", &[
+        Field::new(1, "replicas", Int32),
+        Field::new(2, "selector", Message("meta.v1.LabelSelector")),
+        Field::new(3, "template", Message("core.v1.PodTemplateSpec")),
+        Field::new(4, "volumeClaimTemplates", Message("core.v1.PersistentVolumeClaim")).repeated(),
+        Field::new(5, "serviceName", String).omit_empty(),
+        Field::new(6, "podManagementPolicy", String).omit_empty(),
+        Field::new(7, "updateStrategy", Message("apps.v1.StatefulSetUpdateStrategy")),
+        Field::new(8, "revisionHistoryLimit", Int32),
+        Field::new(9, "minReadySeconds", Int32),
+        Field::new(10, "persistentVolumeClaimRetentionPolicy", Message("apps.v1.StatefulSetPersistentVolumeClaimRetentionPolicy")),
+        Field::new(11, "ordinals", Message("apps.v1.StatefulSetOrdinals")),
+    ]),
+    Schema::new("apps.v1.StatefulSetStatus", &[
+        Field::new(1, "observedGeneration", Int64),
+        Field::new(2, "replicas", Int32),
+        Field::new(3, "readyReplicas", Int32),
+        Field::new(4, "currentReplicas", Int32),
+        Field::new(5, "updatedReplicas", Int32),
+        Field::new(6, "currentRevision", String).omit_empty(),
+        Field::new(7, "updateRevision", String).omit_empty(),
+        Field::new(9, "collisionCount", Int32),
+        Field::new(10, "conditions", Message("apps.v1.StatefulSetCondition")).repeated(),
+        Field::new(11, "availableReplicas", Int32),
+    ]),
+    Schema::new("apps.v1.StatefulSetUpdateStrategy", &[
+        Field::new(1, "type", String).omit_empty(),
+        Field::new(2, "rollingUpdate", Message("apps.v1.RollingUpdateStatefulSetStrategy")),
+    ]),
+    Schema::new("batch.v1.CronJob", &[
+        Field::new(1, "metadata", Message("meta.v1.ObjectMeta")),
+        Field::new(2, "spec", Message("batch.v1.CronJobSpec")),
+        Field::new(3, "status", Message("batch.v1.CronJobStatus")),
+    ]),
+    Schema::new("batch.v1.CronJobList", &[
+        Field::new(1, "metadata", Message("meta.v1.ListMeta")),
+        Field::new(2, "items", Message("batch.v1.CronJob")).repeated(),
+    ]),
+    Schema::new("batch.v1.CronJobSpec", &[
+        Field::new(1, "schedule", String),
+        Field::new(2, "startingDeadlineSeconds", Int64),
+        Field::new(3, "concurrencyPolicy", String).omit_empty(),
+        Field::new(4, "suspend", Bool),
+        Field::new(5, "jobTemplate", Message("batch.v1.JobTemplateSpec")),
+        Field::new(6, "successfulJobsHistoryLimit", Int32),
+        Field::new(7, "failedJobsHistoryLimit", Int32),
+        Field::new(8, "timeZone", String).omit_empty(),
+    ]),
+    Schema::new("batch.v1.CronJobStatus", &[
+        Field::new(1, "active", Message("core.v1.ObjectReference")).repeated(),
+        Field::new(4, "lastScheduleTime", Message("meta.v1.Time")),
+        Field::new(5, "lastSuccessfulTime", Message("meta.v1.Time")),
+    ]),
+    Schema::new("batch.v1.Job", &[
+        Field::new(1, "metadata", Message("meta.v1.ObjectMeta")),
+        Field::new(2, "spec", Message("batch.v1.JobSpec")),
+        Field::new(3, "status", Message("batch.v1.JobStatus")),
+    ]),
+    Schema::new("batch.v1.JobCondition", &[
+        Field::new(1, "type", String),
+        Field::new(2, "status", String),
+        Field::new(3, "lastProbeTime", Message("meta.v1.Time")),
+        Field::new(4, "lastTransitionTime", Message("meta.v1.Time")),
+        Field::new(5, "reason", String).omit_empty(),
+        Field::new(6, "message", String).omit_empty(),
+    ]),
+    Schema::new("batch.v1.JobList", &[
+        Field::new(1, "metadata", Message("meta.v1.ListMeta")),
+        Field::new(2, "items", Message("batch.v1.Job")).repeated(),
+    ]),
+    Schema::new("batch.v1.JobSpec", &[
+        Field::new(1, "parallelism", Int32),
+        Field::new(2, "completions", Int32),
+        Field::new(3, "activeDeadlineSeconds", Int64),
+        Field::new(4, "selector", Message("meta.v1.LabelSelector")),
+        Field::new(5, "manualSelector", Bool),
+        Field::new(6, "template", Message("core.v1.PodTemplateSpec")),
+        Field::new(7, "backoffLimit", Int32),
+        Field::new(8, "ttlSecondsAfterFinished", Int32),
+        Field::new(9, "completionMode", String).omit_empty(),
+        Field::new(10, "suspend", Bool),
+        Field::new(11, "podFailurePolicy", Message("batch.v1.PodFailurePolicy")),
+        Field::new(12, "backoffLimitPerIndex", Int32),
+        Field::new(13, "maxFailedIndexes", Int32),
+        Field::new(14, "podReplacementPolicy", String).omit_empty(),
+        Field::new(15, "managedBy", String).omit_empty(),
+        Field::new(16, "successPolicy", Message("batch.v1.SuccessPolicy")),
+    ]),
+    Schema::new("batch.v1.JobStatus", &[
+        Field::new(1, "conditions", Message("batch.v1.JobCondition")).repeated(),
+        Field::new(2, "startTime", Message("meta.v1.Time")),
+        Field::new(3, "completionTime", Message("meta.v1.Time")),
+        Field::new(4, "active", Int32),
+        Field::new(5, "succeeded", Int32),
+        Field::new(6, "failed", Int32),
+        Field::new(7, "completedIndexes", String).omit_empty(),
+        Field::new(8, "uncountedTerminatedPods", Message("batch.v1.UncountedTerminatedPods")),
+        Field::new(9, "ready", Int32),
+        Field::new(10, "failedIndexes", String).omit_empty(),
+        Field::new(11, "terminating", Int32),
+    ]),
+    Schema::new("batch.v1.JobTemplateSpec", &[
+        Field::new(1, "metadata", Message("meta.v1.ObjectMeta")),
+        Field::new(2, "spec", Message("batch.v1.JobSpec")),
+    ]),
+    Schema::new("batch.v1.PodFailurePolicy", &[
+        Field::new(1, "rules", Message("batch.v1.PodFailurePolicyRule")).repeated(),
+    ]),
+    Schema::new("batch.v1.PodFailurePolicyOnExitCodesRequirement", &[
+        Field::new(1, "containerName", String).omit_empty(),
+        Field::new(2, "operator", String),
+        Field::new(3, "values", Int32).repeated(),
+    ]),
+    Schema::new("batch.v1.PodFailurePolicyOnPodConditionsPattern", &[
+        Field::new(1, "type", String),
+        Field::new(2, "status", String).omit_empty(),
+    ]),
+    Schema::new("batch.v1.PodFailurePolicyRule", &[
+        Field::new(1, "action", String),
+        Field::new(2, "onExitCodes", Message("batch.v1.PodFailurePolicyOnExitCodesRequirement")),
+        Field::new(3, "onPodConditions", Message("batch.v1.PodFailurePolicyOnPodConditionsPattern")).repeated(),
+    ]),
+    Schema::new("batch.v1.SuccessPolicy", &[
+        Field::new(1, "rules", Message("batch.v1.SuccessPolicyRule")).repeated(),
+    ]),
+    Schema::new("batch.v1.SuccessPolicyRule", &[
+        Field::new(1, "succeededIndexes", String).omit_empty(),
+        Field::new(2, "succeededCount", Int32),
+    ]),
+    Schema::new("batch.v1.UncountedTerminatedPods", &[
+        Field::new(1, "succeeded", String).repeated(),
+        Field::new(2, "failed", String).repeated(),
+    ]),
+    Schema::new("core.v1.AWSElasticBlockStoreVolumeSource", &[
+        Field::new(1, "volumeID", String),
+        Field::new(2, "fsType", String).omit_empty(),
+        Field::new(3, "partition", Int32),
+        Field::new(4, "readOnly", Bool),
+    ]),
+    Schema::new("core.v1.Affinity", &[
+        Field::new(1, "nodeAffinity", Message("core.v1.NodeAffinity")),
+        Field::new(2, "podAffinity", Message("core.v1.PodAffinity")),
+        Field::new(3, "podAntiAffinity", Message("core.v1.PodAntiAffinity")),
+    ]),
+    Schema::new("core.v1.AppArmorProfile", &[
+        Field::new(1, "type", String),
+        Field::new(2, "localhostProfile", String).omit_empty(),
+    ]),
+    Schema::new("core.v1.AttachedVolume", &[
+        Field::new(1, "name", String),
+        Field::new(2, "devicePath", String),
+    ]),
+    Schema::new("core.v1.AvoidPods", &[
+        Field::new(1, "preferAvoidPods", Message("core.v1.PreferAvoidPodsEntry")).repeated(),
+    ]),
+    Schema::new("core.v1.AzureDiskVolumeSource", &[
+        Field::new(1, "diskName", String),
+        Field::new(2, "diskURI", String),
+        Field::new(3, "cachingMode", String).omit_empty(),
+        Field::new(4, "fsType", String).omit_empty(),
+        Field::new(5, "readOnly", Bool),
+        Field::new(6, "kind", String).omit_empty(),
+    ]),
+    Schema::new("core.v1.AzureFilePersistentVolumeSource", &[
+        Field::new(1, "secretName", String),
+        Field::new(2, "shareName", String),
+        Field::new(3, "readOnly", Bool),
+        Field::new(4, "secretNamespace", String).omit_empty(),
+    ]),
+    Schema::new("core.v1.AzureFileVolumeSource", &[
+        Field::new(1, "secretName", String),
+        Field::new(2, "shareName", String),
+        Field::new(3, "readOnly", Bool),
+    ]),
+    Schema::new("core.v1.Binding", &[
+        Field::new(1, "metadata", Message("meta.v1.ObjectMeta")),
+        Field::new(2, "target", Message("core.v1.ObjectReference")),
+    ]),
+    Schema::new("core.v1.CSIPersistentVolumeSource", &[
+        Field::new(1, "driver", String),
+        Field::new(2, "volumeHandle", String),
+        Field::new(3, "readOnly", Bool),
+        Field::new(4, "fsType", String).omit_empty(),
+        Field::new(5, "volumeAttributes", Map(&String)),
+        Field::new(6, "controllerPublishSecretRef", Message("core.v1.SecretReference")),
+        Field::new(7, "nodeStageSecretRef", Message("core.v1.SecretReference")),
+        Field::new(8, "nodePublishSecretRef", Message("core.v1.SecretReference")),
+        Field::new(9, "controllerExpandSecretRef", Message("core.v1.SecretReference")),
+        Field::new(10, "nodeExpandSecretRef", Message("core.v1.SecretReference")),
+    ]),
+    Schema::new("core.v1.CSIVolumeSource", &[
+        Field::new(1, "driver", String),
+        Field::new(2, "readOnly", Bool),
+        Field::new(3, "fsType", String).omit_empty(),
+        Field::new(4, "volumeAttributes", Map(&String)),
+        Field::new(5, "nodePublishSecretRef", Message("core.v1.LocalObjectReference")),
+    ]),
+    Schema::new("core.v1.Capabilities", &[
+        Field::new(1, "add", String).repeated(),
+        Field::new(2, "drop", String).repeated(),
+    ]),
+    Schema::new("core.v1.CephFSPersistentVolumeSource", &[
+        Field::new(1, "monitors", String).repeated(),
+        Field::new(2, "path", String).omit_empty(),
+        Field::new(3, "user", String).omit_empty(),
+        Field::new(4, "secretFile", String).omit_empty(),
+        Field::new(5, "secretRef", Message("core.v1.SecretReference")),
+        Field::new(6, "readOnly", Bool),
+    ]),
+    Schema::new("core.v1.CephFSVolumeSource", &[
+        Field::new(1, "monitors", String).repeated(),
+        Field::new(2, "path", String).omit_empty(),
+        Field::new(3, "user", String).omit_empty(),
+        Field::new(4, "secretFile", String).omit_empty(),
+        Field::new(5, "secretRef", Message("core.v1.LocalObjectReference")),
+        Field::new(6, "readOnly", Bool),
+    ]),
+    Schema::new("core.v1.CinderPersistentVolumeSource", &[
+        Field::new(1, "volumeID", String),
+        Field::new(2, "fsType", String).omit_empty(),
+        Field::new(3, "readOnly", Bool),
+        Field::new(4, "secretRef", Message("core.v1.SecretReference")),
+    ]),
+    Schema::new("core.v1.CinderVolumeSource", &[
+        Field::new(1, "volumeID", String),
+        Field::new(2, "fsType", String).omit_empty(),
+        Field::new(3, "readOnly", Bool),
+        Field::new(4, "secretRef", Message("core.v1.LocalObjectReference")),
+    ]),
+    Schema::new("core.v1.ClientIPConfig", &[
+        Field::new(1, "timeoutSeconds", Int32),
+    ]),
+    Schema::new("core.v1.ClusterTrustBundleProjection", &[
+        Field::new(1, "name", String).omit_empty(),
+        Field::new(2, "signerName", String).omit_empty(),
+        Field::new(3, "labelSelector", Message("meta.v1.LabelSelector")),
+        Field::new(4, "path", String),
+        Field::new(5, "optional", Bool),
+    ]),
+    Schema::new("core.v1.ComponentCondition", &[
+        Field::new(1, "type", String),
+        Field::new(2, "status", String),
+        Field::new(3, "message", String).omit_empty(),
+        Field::new(4, "error", String).omit_empty(),
+    ]),
+    Schema::new("core.v1.ComponentStatus", &[
+        Field::new(1, "metadata", Message("meta.v1.ObjectMeta")),
+        Field::new(2, "conditions", Message("core.v1.ComponentCondition")).repeated(),
+    ]),
+    Schema::new("core.v1.ComponentStatusList", &[
+        Field::new(1, "metadata", Message("meta.v1.ListMeta")),
+        Field::new(2, "items", Message("core.v1.ComponentStatus")).repeated(),
+    ]),
+    Schema::new("core.v1.ConfigMap", &[
+        Field::new(1, "metadata", Message("meta.v1.ObjectMeta")),
+        Field::new(2, "data", Map(&String)),
+        Field::new(3, "binaryData", Map(&Bytes)),
+        Field::new(4, "immutable", Bool),
+    ]),
+    Schema::new("core.v1.ConfigMapEnvSource", &[
+        Field::new(1, "localObjectReference", Message("core.v1.LocalObjectReference")).inline(),
+        Field::new(2, "optional", Bool),
+    ]),
+    Schema::new("core.v1.ConfigMapKeySelector", &[
+        Field::new(1, "localObjectReference", Message("core.v1.LocalObjectReference")).inline(),
+        Field::new(2, "key", String),
+        Field::new(3, "optional", Bool),
+    ]),
+    Schema::new("core.v1.ConfigMapList", &[
+        Field::new(1, "metadata", Message("meta.v1.ListMeta")),
+        Field::new(2, "items", Message("core.v1.ConfigMap")).repeated(),
+    ]),
+    Schema::new("core.v1.ConfigMapNodeConfigSource", &[
+        Field::new(1, "namespace", String),
+        Field::new(2, "name", String),
+        Field::new(3, "uid", String).omit_empty(),
+        Field::new(4, "resourceVersion", String).omit_empty(),
+        Field::new(5, "kubeletConfigKey", String),
+    ]),
+    Schema::new("core.v1.ConfigMapProjection", &[
+        Field::new(1, "localObjectReference", Message("core.v1.LocalObjectReference")).inline(),
+        Field::new(2, "items", Message("core.v1.KeyToPath")).repeated(),
+        Field::new(4, "optional", Bool),
+    ]),
+    Schema::new("core.v1.ConfigMapVolumeSource", &[
+        Field::new(1, "localObjectReference", Message("core.v1.LocalObjectReference")).inline(),
+        Field::new(2, "items", Message("core.v1.KeyToPath")).repeated(),
+        Field::new(3, "defaultMode", Int32),
+        Field::new(4, "optional", Bool),
+    ]),
+    Schema::new("core.v1.Container", &[
+        Field::new(1, "name", String),
+        Field::new(2, "image", String).omit_empty(),
+        Field::new(3, "command", String).repeated(),
+        Field::new(4, "args", String).repeated(),
+        Field::new(5, "workingDir", String).omit_empty(),
+        Field::new(6, "ports", Message("core.v1.ContainerPort")).repeated(),
+        Field::new(7, "env", Message("core.v1.EnvVar")).repeated(),
+        Field::new(8, "resources", Message("core.v1.ResourceRequirements")),
+        Field::new(9, "volumeMounts", Message("core.v1.VolumeMount")).repeated(),
+        Field::new(10, "livenessProbe", Message("core.v1.Probe")),
+        Field::new(11, "readinessProbe", Message("core.v1.Probe")),
+        Field::new(12, "lifecycle", Message("core.v1.Lifecycle")),
+        Field::new(13, "terminationMessagePath", String).omit_empty(),
+        Field::new(14, "imagePullPolicy", String).omit_empty(),
+        Field::new(15, "securityContext", Message("core.v1.SecurityContext")),
+        Field::new(16, "stdin", Bool),
+        Field::new(17, "stdinOnce", Bool),
+        Field::new(18, "tty", Bool),
+        Field::new(19, "envFrom", Message("core.v1.EnvFromSource")).repeated(),
+        Field::new(20, "terminationMessagePolicy", String).omit_empty(),
+        Field::new(21, "volumeDevices", Message("core.v1.VolumeDevice")).repeated(),
+        Field::new(22, "startupProbe", Message("core.v1.Probe")),
+        Field::new(23, "resizePolicy", Message("core.v1.ContainerResizePolicy")).repeated(),
+        Field::new(24, "restartPolicy", String).omit_empty(),
+        Field::new(25, "restartPolicyRules", Message("core.v1.ContainerRestartRule")).repeated(),
+    ]),
+    Schema::new("core.v1.ContainerExtendedResourceRequest", &[
+        Field::new(1, "containerName", String),
+        Field::new(2, "resourceName", String),
+        Field::new(3, "requestName", String),
+    ]),
+    Schema::new("core.v1.ContainerImage", &[
+        Field::new(1, "names", String).repeated(),
+        Field::new(2, "sizeBytes", Int64),
+    ]),
+    Schema::new("core.v1.ContainerPort", &[
+        Field::new(1, "name", String).omit_empty(),
+        Field::new(2, "hostPort", Int32),
+        Field::new(3, "containerPort", Int32),
+        Field::new(4, "protocol", String).omit_empty(),
+        Field::new(5, "hostIP", String).omit_empty(),
+    ]),
+    Schema::new("core.v1.ContainerResizePolicy", &[
+        Field::new(1, "resourceName", String),
+        Field::new(2, "restartPolicy", String),
+    ]),
+    Schema::new("core.v1.ContainerRestartRule", &[
+        Field::new(1, "action", String),
+        Field::new(2, "exitCodes", Message("core.v1.ContainerRestartRuleOnExitCodes")),
+    ]),
+    Schema::new("core.v1.ContainerRestartRuleOnExitCodes", &[
+        Field::new(1, "operator", String),
+        Field::new(2, "values", Int32).repeated(),
+    ]),
+    Schema::new("core.v1.ContainerState", &[
+        Field::new(1, "waiting", Message("core.v1.ContainerStateWaiting")),
+        Field::new(2, "running", Message("core.v1.ContainerStateRunning")),
+        Field::new(3, "terminated", Message("core.v1.ContainerStateTerminated")),
+    ]),
+    Schema::new("core.v1.ContainerStateRunning", &[
+        Field::new(1, "startedAt", Message("meta.v1.Time")),
+    ]),
+    Schema::new("core.v1.ContainerStateTerminated", &[
+        Field::new(1, "exitCode", Int32),
+        Field::new(2, "signal", Int32),
+        Field::new(3, "reason", String).omit_empty(),
+        Field::new(4, "message", String).omit_empty(),
+        Field::new(5, "startedAt", Message("meta.v1.Time")),
+        Field::new(6, "finishedAt", Message("meta.v1.Time")),
+        Field::new(7, "containerID", String).omit_empty(),
+    ]),
+    Schema::new("core.v1.ContainerStateWaiting", &[
+        Field::new(1, "reason", String).omit_empty(),
+        Field::new(2, "message", String).omit_empty(),
+    ]),
+    Schema::new("core.v1.ContainerStatus", &[
+        Field::new(1, "name", String),
+        Field::new(2, "state", Message("core.v1.ContainerState")),
+        Field::new(3, "lastState", Message("core.v1.ContainerState")),
+        Field::new(4, "ready", Bool),
+        Field::new(5, "restartCount", Int32),
+        Field::new(6, "image", String),
+        Field::new(7, "imageID", String),
+        Field::new(8, "containerID", String).omit_empty(),
+        Field::new(9, "started", Bool),
+        Field::new(10, "allocatedResources", Map(&Message("resource.Quantity"))),
+        Field::new(11, "resources", Message("core.v1.ResourceRequirements")),
+        Field::new(12, "volumeMounts", Message("core.v1.VolumeMountStatus")).repeated(),
+        Field::new(13, "user", Message("core.v1.ContainerUser")),
+        Field::new(14, "allocatedResourcesStatus", Message("core.v1.ResourceStatus")).repeated(),
+        Field::new(15, "stopSignal", String).omit_empty(),
+    ]),
+    Schema::new("core.v1.ContainerUser", &[
+        Field::new(1, "linux", Message("core.v1.LinuxContainerUser")),
+    ]),
+    Schema::new("core.v1.DaemonEndpoint", &[
+        Field::new(1, "Port", Int32),
+    ]),
+    Schema::new("core.v1.DownwardAPIProjection", &[
+        Field::new(1, "items", Message("core.v1.DownwardAPIVolumeFile")).repeated(),
+    ]),
+    Schema::new("core.v1.DownwardAPIVolumeFile", &[
+        Field::new(1, "path", String),
+        Field::new(2, "fieldRef", Message("core.v1.ObjectFieldSelector")),
+        Field::new(3, "resourceFieldRef", Message("core.v1.ResourceFieldSelector")),
+        Field::new(4, "mode", Int32),
+    ]),
+    Schema::new("core.v1.DownwardAPIVolumeSource", &[
+        Field::new(1, "items", Message("core.v1.DownwardAPIVolumeFile")).repeated(),
+        Field::new(2, "defaultMode", Int32),
+    ]),
+    Schema::new("core.v1.EmptyDirVolumeSource", &[
+        Field::new(1, "medium", String).omit_empty(),
+        Field::new(2, "sizeLimit", Message("resource.Quantity")),
+    ]),
+    Schema::new("core.v1.EndpointAddress", &[
+        Field::new(1, "ip", String),
+        Field::new(2, "targetRef", Message("core.v1.ObjectReference")),
+        Field::new(3, "hostname", String).omit_empty(),
+        Field::new(4, "nodeName", String).omit_empty(),
+    ]),
+    Schema::new("core.v1.EndpointPort", &[
+        Field::new(1, "name", String).omit_empty(),
+        Field::new(2, "port", Int32),
+        Field::new(3, "protocol", String).omit_empty(),
+        Field::new(4, "appProtocol", String).omit_empty(),
+    ]),
+    Schema::new("core.v1.EndpointSubset", &[
+        Field::new(1, "addresses", Message("core.v1.EndpointAddress")).repeated(),
+        Field::new(2, "notReadyAddresses", Message("core.v1.EndpointAddress")).repeated(),
+        Field::new(3, "ports", Message("core.v1.EndpointPort")).repeated(),
+    ]),
+    Schema::new("core.v1.Endpoints", &[
+        Field::new(1, "metadata", Message("meta.v1.ObjectMeta")),
+        Field::new(2, "subsets", Message("core.v1.EndpointSubset")).repeated(),
+    ]),
+    Schema::new("core.v1.EndpointsList", &[
+        Field::new(1, "metadata", Message("meta.v1.ListMeta")),
+        Field::new(2, "items", Message("core.v1.Endpoints")).repeated(),
+    ]),
+    Schema::new("core.v1.EnvFromSource", &[
+        Field::new(1, "prefix", String).omit_empty(),
+        Field::new(2, "configMapRef", Message("core.v1.ConfigMapEnvSource")),
+        Field::new(3, "secretRef", Message("core.v1.SecretEnvSource")),
+    ]),
+    Schema::new("core.v1.EnvVar", &[
+        Field::new(1, "name", String),
+        Field::new(2, "value", String).omit_empty(),
+        Field::new(3, "valueFrom", Message("core.v1.EnvVarSource")),
+    ]),
+    Schema::new("core.v1.EnvVarSource", &[
+        Field::new(1, "fieldRef", Message("core.v1.ObjectFieldSelector")),
+        Field::new(2, "resourceFieldRef", Message("core.v1.ResourceFieldSelector")),
+        Field::new(3, "configMapKeyRef", Message("core.v1.ConfigMapKeySelector")),
+        Field::new(4, "secretKeyRef", Message("core.v1.SecretKeySelector")),
+        Field::new(5, "fileKeyRef", Message("core.v1.FileKeySelector")),
+    ]),
+    Schema::new("core.v1.EphemeralContainer", &[
+        Field::new(1, "ephemeralContainerCommon", Message("core.v1.EphemeralContainerCommon")).inline(),
+        Field::new(2, "targetContainerName", String).omit_empty(),
+    ]),
+    Schema::new("core.v1.EphemeralContainerCommon", &[
+        Field::new(1, "name", String).omit_empty(),
+        Field::new(2, "image", String).omit_empty(),
+        Field::new(3, "command", String).repeated(),
+        Field::new(4, "args", String).repeated(),
+        Field::new(5, "workingDir", String).omit_empty(),
+        Field::new(6, "ports", Message("core.v1.ContainerPort")).repeated(),
+        Field::new(7, "env", Message("core.v1.EnvVar")).repeated(),
+        Field::new(8, "resources", Message("core.v1.ResourceRequirements")),
+        Field::new(9, "volumeMounts", Message("core.v1.VolumeMount")).repeated(),
+        Field::new(10, "livenessProbe", Message("core.v1.Probe")),
+        Field::new(11, "readinessProbe", Message("core.v1.Probe")),
+        Field::new(12, "lifecycle", Message("core.v1.Lifecycle")),
+        Field::new(13, "terminationMessagePath", String).omit_empty(),
+        Field::new(14, "imagePullPolicy", String).omit_empty(),
+        Field::new(15, "securityContext", Message("core.v1.SecurityContext")),
+        Field::new(16, "stdin", Bool),
+        Field::new(17, "stdinOnce", Bool),
+        Field::new(18, "tty", Bool),
+        Field::new(19, "envFrom", Message("core.v1.EnvFromSource")).repeated(),
+        Field::new(20, "terminationMessagePolicy", String).omit_empty(),
+        Field::new(21, "volumeDevices", Message("core.v1.VolumeDevice")).repeated(),
+        Field::new(22, "startupProbe", Message("core.v1.Probe")),
+        Field::new(23, "resizePolicy", Message("core.v1.ContainerResizePolicy")).repeated(),
+        Field::new(24, "restartPolicy", String).omit_empty(),
+        Field::new(25, "restartPolicyRules", Message("core.v1.ContainerRestartRule")).repeated(),
+    ]),
+    Schema::new("core.v1.EphemeralVolumeSource", &[
+        Field::new(1, "volumeClaimTemplate", Message("core.v1.PersistentVolumeClaimTemplate")),
+    ]),
+    Schema::new("core.v1.Event", &[
+        Field::new(1, "metadata", Message("meta.v1.ObjectMeta")),
+        Field::new(2, "involvedObject", Message("core.v1.ObjectReference")),
+        Field::new(3, "reason", String).omit_empty(),
+        Field::new(4, "message", String).omit_empty(),
+        Field::new(5, "source", Message("core.v1.EventSource")),
+        Field::new(6, "firstTimestamp", Message("meta.v1.Time")),
+        Field::new(7, "lastTimestamp", Message("meta.v1.Time")),
+        Field::new(8, "count", Int32),
+        Field::new(9, "type", String).omit_empty(),
+        Field::new(10, "eventTime", Message("meta.v1.MicroTime")),
+        Field::new(11, "series", Message("core.v1.EventSeries")),
+        Field::new(12, "action", String).omit_empty(),
+        Field::new(13, "related", Message("core.v1.ObjectReference")),
+        Field::new(14, "reportingComponent", String).omit_empty(),
+        Field::new(15, "reportingInstance", String).omit_empty(),
+    ]),
+    Schema::new("core.v1.EventList", &[
+        Field::new(1, "metadata", Message("meta.v1.ListMeta")),
+        Field::new(2, "items", Message("core.v1.Event")).repeated(),
+    ]),
+    Schema::new("core.v1.EventSeries", &[
+        Field::new(1, "count", Int32),
+        Field::new(2, "lastObservedTime", Message("meta.v1.MicroTime")),
+    ]),
+    Schema::new("core.v1.EventSource", &[
+        Field::new(1, "component", String).omit_empty(),
+        Field::new(2, "host", String).omit_empty(),
+    ]),
+    Schema::new("core.v1.ExecAction", &[
+        Field::new(1, "command", String).repeated(),
+    ]),
+    Schema::new("core.v1.FCVolumeSource", &[
+        Field::new(1, "targetWWNs", String).repeated(),
+        Field::new(2, "lun", Int32),
+        Field::new(3, "fsType", String).omit_empty(),
+        Field::new(4, "readOnly", Bool),
+        Field::new(5, "wwids", String).repeated(),
+    ]),
+    Schema::new("core.v1.FileKeySelector", &[
+        Field::new(1, "volumeName", String),
+        Field::new(2, "path", String),
+        Field::new(3, "key", String),
+        Field::new(4, "optional", Bool),
+    ]),
+    Schema::new("core.v1.FlexPersistentVolumeSource", &[
+        Field::new(1, "driver", String),
+        Field::new(2, "fsType", String).omit_empty(),
+        Field::new(3, "secretRef", Message("core.v1.SecretReference")),
+        Field::new(4, "readOnly", Bool),
+        Field::new(5, "options", Map(&String)),
+    ]),
+    Schema::new("core.v1.FlexVolumeSource", &[
+        Field::new(1, "driver", String),
+        Field::new(2, "fsType", String).omit_empty(),
+        Field::new(3, "secretRef", Message("core.v1.LocalObjectReference")),
+        Field::new(4, "readOnly", Bool),
+        Field::new(5, "options", Map(&String)),
+    ]),
+    Schema::new("core.v1.FlockerVolumeSource", &[
+        Field::new(1, "datasetName", String).omit_empty(),
+        Field::new(2, "datasetUUID", String).omit_empty(),
+    ]),
+    Schema::new("core.v1.GCEPersistentDiskVolumeSource", &[
+        Field::new(1, "pdName", String),
+        Field::new(2, "fsType", String).omit_empty(),
+        Field::new(3, "partition", Int32),
+        Field::new(4, "readOnly", Bool),
+    ]),
+    Schema::new("core.v1.GRPCAction", &[
+        Field::new(1, "port", Int32),
+        Field::new(2, "service", String).omit_empty(),
+    ]),
+    Schema::new("core.v1.GitRepoVolumeSource", &[
+        Field::new(1, "repository", String),
+        Field::new(2, "revision", String).omit_empty(),
+        Field::new(3, "directory", String).omit_empty(),
+    ]),
+    Schema::new("core.v1.GlusterfsPersistentVolumeSource", &[
+        Field::new(1, "endpoints", String),
+        Field::new(2, "path", String),
+        Field::new(3, "readOnly", Bool),
+        Field::new(4, "endpointsNamespace", String).omit_empty(),
+    ]),
+    Schema::new("core.v1.GlusterfsVolumeSource", &[
+        Field::new(1, "endpoints", String),
+        Field::new(2, "path", String),
+        Field::new(3, "readOnly", Bool),
+    ]),
+    Schema::new("core.v1.HTTPGetAction", &[
+        Field::new(1, "path", String).omit_empty(),
+        Field::new(2, "port", Message("intstr.IntOrString")),
+        Field::new(3, "host", String).omit_empty(),
+        Field::new(4, "scheme", String).omit_empty(),
+        Field::new(5, "httpHeaders", Message("core.v1.HTTPHeader")).repeated(),
+    ]),
+    Schema::new("core.v1.HTTPHeader", &[
+        Field::new(1, "name", String),
+        Field::new(2, "value", String),
+    ]),
+    Schema::new("core.v1.HostAlias", &[
+        Field::new(1, "ip", String),
+        Field::new(2, "hostnames", String).repeated(),
+    ]),
+    Schema::new("core.v1.HostIP", &[
+        Field::new(1, "ip", String),
+    ]),
+    Schema::new("core.v1.HostPathVolumeSource", &[
+        Field::new(1, "path", String),
+        Field::new(2, "type", String).omit_empty(),
+    ]),
+    Schema::new("core.v1.ISCSIPersistentVolumeSource", &[
+        Field::new(1, "targetPortal", String),
+        Field::new(2, "iqn", String),
+        Field::new(3, "lun", Int32),
+        Field::new(4, "iscsiInterface", String).omit_empty(),
+        Field::new(5, "fsType", String).omit_empty(),
+        Field::new(6, "readOnly", Bool),
+        Field::new(7, "portals", String).repeated(),
+        Field::new(8, "chapAuthDiscovery", Bool),
+        Field::new(10, "secretRef", Message("core.v1.SecretReference")),
+        Field::new(11, "chapAuthSession", Bool),
+        Field::new(12, "initiatorName", String).omit_empty(),
+    ]),
+    Schema::new("core.v1.ISCSIVolumeSource", &[
+        Field::new(1, "targetPortal", String),
+        Field::new(2, "iqn", String),
+        Field::new(3, "lun", Int32),
+        Field::new(4, "iscsiInterface", String).omit_empty(),
+        Field::new(5, "fsType", String).omit_empty(),
+        Field::new(6, "readOnly", Bool),
+        Field::new(7, "portals", String).repeated(),
+        Field::new(8, "chapAuthDiscovery", Bool),
+        Field::new(10, "secretRef", Message("core.v1.LocalObjectReference")),
+        Field::new(11, "chapAuthSession", Bool),
+        Field::new(12, "initiatorName", String).omit_empty(),
+    ]),
+    Schema::new("core.v1.ImageVolumeSource", &[
+        Field::new(1, "reference", String).omit_empty(),
+        Field::new(2, "pullPolicy", String).omit_empty(),
+    ]),
+    Schema::new("core.v1.KeyToPath", &[
+        Field::new(1, "key", String),
+        Field::new(2, "path", String),
+        Field::new(3, "mode", Int32),
+    ]),
+    Schema::new("core.v1.Lifecycle", &[
+        Field::new(1, "postStart", Message("core.v1.LifecycleHandler")),
+        Field::new(2, "preStop", Message("core.v1.LifecycleHandler")),
+        Field::new(3, "stopSignal", String).omit_empty(),
+    ]),
+    Schema::new("core.v1.LifecycleHandler", &[
+        Field::new(1, "exec", Message("core.v1.ExecAction")),
+        Field::new(2, "httpGet", Message("core.v1.HTTPGetAction")),
+        Field::new(3, "tcpSocket", Message("core.v1.TCPSocketAction")),
+        Field::new(4, "sleep", Message("core.v1.SleepAction")),
+    ]),
+    Schema::new("core.v1.LimitRange", &[
+        Field::new(1, "metadata", Message("meta.v1.ObjectMeta")),
+        Field::new(2, "spec", Message("core.v1.LimitRangeSpec")),
+    ]),
+    Schema::new("core.v1.LimitRangeItem", &[
+        Field::new(1, "type", String),
+        Field::new(2, "max", Map(&Message("resource.Quantity"))),
+        Field::new(3, "min", Map(&Message("resource.Quantity"))),
+        Field::new(4, "default", Map(&Message("resource.Quantity"))),
+        Field::new(5, "defaultRequest", Map(&Message("resource.Quantity"))),
+        Field::new(6, "maxLimitRequestRatio", Map(&Message("resource.Quantity"))),
+    ]),
+    Schema::new("core.v1.LimitRangeList", &[
+        Field::new(1, "metadata", Message("meta.v1.ListMeta")),
+        Field::new(2, "items", Message("core.v1.LimitRange")).repeated(),
+    ]),
+    Schema::new("core.v1.LimitRangeSpec", &[
+        Field::new(1, "limits", Message("core.v1.LimitRangeItem")).repeated(),
+    ]),
+    Schema::new("core.v1.LinuxContainerUser", &[
+        Field::new(1, "uid", Int64),
+        Field::new(2, "gid", Int64),
+        Field::new(3, "supplementalGroups", Int64).repeated(),
+    ]),
+    Schema::new("core.v1.List", &[
+        Field::new(1, "metadata", Message("meta.v1.ListMeta")),
+        Field::new(2, "items", Message("runtime.RawExtension")).repeated(),
+    ]),
+    Schema::new("core.v1.LoadBalancerIngress", &[
+        Field::new(1, "ip", String).omit_empty(),
+        Field::new(2, "hostname", String).omit_empty(),
+        Field::new(3, "ipMode", String).omit_empty(),
+        Field::new(4, "ports", Message("core.v1.PortStatus")).repeated(),
+    ]),
+    Schema::new("core.v1.LoadBalancerStatus", &[
+        Field::new(1, "ingress", Message("core.v1.LoadBalancerIngress")).repeated(),
+    ]),
+    Schema::new("core.v1.LocalObjectReference", &[
+        Field::new(1, "name", String),
+    ]),
+    Schema::new("core.v1.LocalVolumeSource", &[
+        Field::new(1, "path", String),
+        Field::new(2, "fsType", String).omit_empty(),
+    ]),
+    Schema::new("core.v1.ModifyVolumeStatus", &[
+        Field::new(1, "targetVolumeAttributesClassName", String).omit_empty(),
+        Field::new(2, "status", String),
+    ]),
+    Schema::new("core.v1.NFSVolumeSource", &[
+        Field::new(1, "server", String),
+        Field::new(2, "path", String),
+        Field::new(3, "readOnly", Bool),
+    ]),
+    Schema::new("core.v1.Namespace", &[
+        Field::new(1, "metadata", Message("meta.v1.ObjectMeta")),
+        Field::new(2, "spec", Message("core.v1.NamespaceSpec")),
+        Field::new(3, "status", Message("core.v1.NamespaceStatus")),
+    ]),
+    Schema::new("core.v1.NamespaceCondition", &[
+        Field::new(1, "type", String),
+        Field::new(2, "status", String),
+        Field::new(4, "lastTransitionTime", Message("meta.v1.Time")),
+        Field::new(5, "reason", String).omit_empty(),
+        Field::new(6, "message", String).omit_empty(),
+    ]),
+    Schema::new("core.v1.NamespaceList", &[
+        Field::new(1, "metadata", Message("meta.v1.ListMeta")),
+        Field::new(2, "items", Message("core.v1.Namespace")).repeated(),
+    ]),
+    Schema::new("core.v1.NamespaceSpec", &[
+        Field::new(1, "finalizers", String).repeated(),
+    ]),
+    Schema::new("core.v1.NamespaceStatus", &[
+        Field::new(1, "phase", String).omit_empty(),
+        Field::new(2, "conditions", Message("core.v1.NamespaceCondition")).repeated(),
+    ]),
+    Schema::new("core.v1.Node", &[
+        Field::new(1, "metadata", Message("meta.v1.ObjectMeta")),
+        Field::new(2, "spec", Message("core.v1.NodeSpec")),
+        Field::new(3, "status", Message("core.v1.NodeStatus")),
+    ]),
+    Schema::new("core.v1.NodeAddress", &[
+        Field::new(1, "type", String),
+        Field::new(2, "address", String),
+    ]),
+    Schema::new("core.v1.NodeAffinity", &[
+        Field::new(1, "requiredDuringSchedulingIgnoredDuringExecution", Message("core.v1.NodeSelector")),
+        Field::new(2, "preferredDuringSchedulingIgnoredDuringExecution", Message("core.v1.PreferredSchedulingTerm")).repeated(),
+    ]),
+    Schema::new("core.v1.NodeCondition", &[
+        Field::new(1, "type", String),
+        Field::new(2, "status", String),
+        Field::new(3, "lastHeartbeatTime", Message("meta.v1.Time")),
+        Field::new(4, "lastTransitionTime", Message("meta.v1.Time")),
+        Field::new(5, "reason", String).omit_empty(),
+        Field::new(6, "message", String).omit_empty(),
+    ]),
+    Schema::new("core.v1.NodeConfigSource", &[
+        Field::new(2, "configMap", Message("core.v1.ConfigMapNodeConfigSource")),
+    ]),
+    Schema::new("core.v1.NodeConfigStatus", &[
+        Field::new(1, "assigned", Message("core.v1.NodeConfigSource")),
+        Field::new(2, "active", Message("core.v1.NodeConfigSource")),
+        Field::new(3, "lastKnownGood", Message("core.v1.NodeConfigSource")),
+        Field::new(4, "error", String).omit_empty(),
+    ]),
+    Schema::new("core.v1.NodeDaemonEndpoints", &[
+        Field::new(1, "kubeletEndpoint", Message("core.v1.DaemonEndpoint")),
+    ]),
+    Schema::new("core.v1.NodeFeatures", &[
+        Field::new(1, "supplementalGroupsPolicy", Bool),
+    ]),
+    Schema::new("core.v1.NodeList", &[
+        Field::new(1, "metadata", Message("meta.v1.ListMeta")),
+        Field::new(2, "items", Message("core.v1.Node")).repeated(),
+    ]),
+    Schema::new("core.v1.NodeProxyOptions", &[
+        Field::new(1, "path", String).omit_empty(),
+    ]),
+    Schema::new("core.v1.NodeRuntimeHandler", &[
+        Field::new(1, "name", String).omit_empty(),
+        Field::new(2, "features", Message("core.v1.NodeRuntimeHandlerFeatures")),
+    ]),
+    Schema::new("core.v1.NodeRuntimeHandlerFeatures", &[
+        Field::new(1, "recursiveReadOnlyMounts", Bool),
+        Field::new(2, "userNamespaces", Bool),
+    ]),
+    Schema::new("core.v1.NodeSelector", &[
+        Field::new(1, "nodeSelectorTerms", Message("core.v1.NodeSelectorTerm")).repeated(),
+    ]),
+    Schema::new("core.v1.NodeSelectorRequirement", &[
+        Field::new(1, "key", String),
+        Field::new(2, "operator", String),
+        Field::new(3, "values", String).repeated(),
+    ]),
+    Schema::new("core.v1.NodeSelectorTerm", &[
+        Field::new(1, "matchExpressions", Message("core.v1.NodeSelectorRequirement")).repeated(),
+        Field::new(2, "matchFields", Message("core.v1.NodeSelectorRequirement")).repeated(),
+    ]),
+    Schema::new("core.v1.NodeSpec", &[
+        Field::new(1, "podCIDR", String).omit_empty(),
+        Field::new(2, "externalID", String).omit_empty(),
+        Field::new(3, "providerID", String).omit_empty(),
+        Field::new(4, "unschedulable", Bool),
+        Field::new(5, "taints", Message("core.v1.Taint")).repeated(),
+        Field::new(6, "configSource", Message("core.v1.NodeConfigSource")),
+        Field::new(7, "podCIDRs", String).repeated(),
+    ]),
+    Schema::new("core.v1.NodeStatus", &[
+        Field::new(1, "capacity", Map(&Message("resource.Quantity"))),
+        Field::new(2, "allocatable", Map(&Message("resource.Quantity"))),
+        Field::new(3, "phase", String).omit_empty(),
+        Field::new(4, "conditions", Message("core.v1.NodeCondition")).repeated(),
+        Field::new(5, "addresses", Message("core.v1.NodeAddress")).repeated(),
+        Field::new(6, "daemonEndpoints", Message("core.v1.NodeDaemonEndpoints")),
+        Field::new(7, "nodeInfo", Message("core.v1.NodeSystemInfo")),
+        Field::new(8, "images", Message("core.v1.ContainerImage")).repeated(),
+        Field::new(9, "volumesInUse", String).repeated(),
+        Field::new(10, "volumesAttached", Message("core.v1.AttachedVolume")).repeated(),
+        Field::new(11, "config", Message("core.v1.NodeConfigStatus")),
+        Field::new(12, "runtimeHandlers", Message("core.v1.NodeRuntimeHandler")).repeated(),
+        Field::new(13, "features", Message("core.v1.NodeFeatures")),
+        Field::new(14, "declaredFeatures", String).repeated(),
+    ]),
+    Schema::new("core.v1.NodeSwapStatus", &[
+        Field::new(1, "capacity", Int64),
+    ]),
+    Schema::new("core.v1.NodeSystemInfo", &[
+        Field::new(1, "machineID", String),
+        Field::new(2, "systemUUID", String),
+        Field::new(3, "bootID", String),
+        Field::new(4, "kernelVersion", String),
+        Field::new(5, "osImage", String),
+        Field::new(6, "containerRuntimeVersion", String),
+        Field::new(7, "kubeletVersion", String),
+        Field::new(8, "kubeProxyVersion", String),
+        Field::new(9, "operatingSystem", String),
+        Field::new(10, "architecture", String),
+        Field::new(11, "swap", Message("core.v1.NodeSwapStatus")),
+    ]),
+    Schema::new("core.v1.ObjectFieldSelector", &[
+        Field::new(1, "apiVersion", String).omit_empty(),
+        Field::new(2, "fieldPath", String),
+    ]),
+    Schema::new("core.v1.ObjectReference", &[
+        Field::new(1, "kind", String).omit_empty(),
+        Field::new(2, "namespace", String).omit_empty(),
+        Field::new(3, "name", String).omit_empty(),
+        Field::new(4, "uid", String).omit_empty(),
+        Field::new(5, "apiVersion", String).omit_empty(),
+        Field::new(6, "resourceVersion", String).omit_empty(),
+        Field::new(7, "fieldPath", String).omit_empty(),
+    ]),
+    Schema::new("core.v1.PersistentVolume", &[
+        Field::new(1, "metadata", Message("meta.v1.ObjectMeta")),
+        Field::new(2, "spec", Message("core.v1.PersistentVolumeSpec")),
+        Field::new(3, "status", Message("core.v1.PersistentVolumeStatus")),
+    ]),
+    Schema::new("core.v1.PersistentVolumeClaim", &[
+        Field::new(1, "metadata", Message("meta.v1.ObjectMeta")),
+        Field::new(2, "spec", Message("core.v1.PersistentVolumeClaimSpec")),
+        Field::new(3, "status", Message("core.v1.PersistentVolumeClaimStatus")),
+    ]),
+    Schema::new("core.v1.PersistentVolumeClaimCondition", &[
+        Field::new(1, "type", String),
+        Field::new(2, "status", String),
+        Field::new(3, "lastProbeTime", Message("meta.v1.Time")),
+        Field::new(4, "lastTransitionTime", Message("meta.v1.Time")),
+        Field::new(5, "reason", String).omit_empty(),
+        Field::new(6, "message", String).omit_empty(),
+    ]),
+    Schema::new("core.v1.PersistentVolumeClaimList", &[
+        Field::new(1, "metadata", Message("meta.v1.ListMeta")),
+        Field::new(2, "items", Message("core.v1.PersistentVolumeClaim")).repeated(),
+    ]),
+    Schema::new("core.v1.PersistentVolumeClaimSpec", &[
+        Field::new(1, "accessModes", String).repeated(),
+        Field::new(2, "resources", Message("core.v1.VolumeResourceRequirements")),
+        Field::new(3, "volumeName", String).omit_empty(),
+        Field::new(4, "selector", Message("meta.v1.LabelSelector")),
+        Field::new(5, "storageClassName", String),
+        Field::new(6, "volumeMode", String).omit_empty(),
+        Field::new(7, "dataSource", Message("core.v1.TypedLocalObjectReference")),
+        Field::new(8, "dataSourceRef", Message("core.v1.TypedObjectReference")),
+        Field::new(9, "volumeAttributesClassName", String),
+    ]),
+    Schema::new("core.v1.PersistentVolumeClaimStatus", &[
+        Field::new(1, "phase", String).omit_empty(),
+        Field::new(2, "accessModes", String).repeated(),
+        Field::new(3, "capacity", Map(&Message("resource.Quantity"))),
+        Field::new(4, "conditions", Message("core.v1.PersistentVolumeClaimCondition")).repeated(),
+        Field::new(5, "allocatedResources", Map(&Message("resource.Quantity"))),
+        Field::new(7, "allocatedResourceStatuses", Map(&String)),
+        Field::new(8, "currentVolumeAttributesClassName", String),
+        Field::new(9, "modifyVolumeStatus", Message("core.v1.ModifyVolumeStatus")),
+    ]),
+    Schema::new("core.v1.PersistentVolumeClaimTemplate", &[
+        Field::new(1, "metadata", Message("meta.v1.ObjectMeta")),
+        Field::new(2, "spec", Message("core.v1.PersistentVolumeClaimSpec")),
+    ]),
+    Schema::new("core.v1.PersistentVolumeClaimVolumeSource", &[
+        Field::new(1, "claimName", String),
+        Field::new(2, "readOnly", Bool),
+    ]),
+    Schema::new("core.v1.PersistentVolumeList", &[
+        Field::new(1, "metadata", Message("meta.v1.ListMeta")),
+        Field::new(2, "items", Message("core.v1.PersistentVolume")).repeated(),
+    ]),
+    Schema::new("core.v1.PersistentVolumeSource", &[
+        Field::new(1, "gcePersistentDisk", Message("core.v1.GCEPersistentDiskVolumeSource")),
+        Field::new(2, "awsElasticBlockStore", Message("core.v1.AWSElasticBlockStoreVolumeSource")),
+        Field::new(3, "hostPath", Message("core.v1.HostPathVolumeSource")),
+        Field::new(4, "glusterfs", Message("core.v1.GlusterfsPersistentVolumeSource")),
+        Field::new(5, "nfs", Message("core.v1.NFSVolumeSource")),
+        Field::new(6, "rbd", Message("core.v1.RBDPersistentVolumeSource")),
+        Field::new(7, "iscsi", Message("core.v1.ISCSIPersistentVolumeSource")),
+        Field::new(8, "cinder", Message("core.v1.CinderPersistentVolumeSource")),
+        Field::new(9, "cephfs", Message("core.v1.CephFSPersistentVolumeSource")),
+        Field::new(10, "fc", Message("core.v1.FCVolumeSource")),
+        Field::new(11, "flocker", Message("core.v1.FlockerVolumeSource")),
+        Field::new(12, "flexVolume", Message("core.v1.FlexPersistentVolumeSource")),
+        Field::new(13, "azureFile", Message("core.v1.AzureFilePersistentVolumeSource")),
+        Field::new(14, "vsphereVolume", Message("core.v1.VsphereVirtualDiskVolumeSource")),
+        Field::new(15, "quobyte", Message("core.v1.QuobyteVolumeSource")),
+        Field::new(16, "azureDisk", Message("core.v1.AzureDiskVolumeSource")),
+        Field::new(17, "photonPersistentDisk", Message("core.v1.PhotonPersistentDiskVolumeSource")),
+        Field::new(18, "portworxVolume", Message("core.v1.PortworxVolumeSource")),
+        Field::new(19, "scaleIo", Message("core.v1.ScaleIOPersistentVolumeSource")),
+        Field::new(20, "local", Message("core.v1.LocalVolumeSource")),
+        Field::new(21, "storageos", Message("core.v1.StorageOSPersistentVolumeSource")),
+        Field::new(22, "csi", Message("core.v1.CSIPersistentVolumeSource")),
+    ]),
+    Schema::new("core.v1.PersistentVolumeSpec", &[
+        Field::new(1, "capacity", Map(&Message("resource.Quantity"))),
+        Field::new(2, "persistentVolumeSource", Message("core.v1.PersistentVolumeSource")).inline(),
+        Field::new(3, "accessModes", String).repeated(),
+        Field::new(4, "claimRef", Message("core.v1.ObjectReference")),
+        Field::new(5, "persistentVolumeReclaimPolicy", String).omit_empty(),
+        Field::new(6, "storageClassName", String).omit_empty(),
+        Field::new(7, "mountOptions", String).repeated(),
+        Field::new(8, "volumeMode", String).omit_empty(),
+        Field::new(9, "nodeAffinity", Message("core.v1.VolumeNodeAffinity")),
+        Field::new(10, "volumeAttributesClassName", String),
+    ]),
+    Schema::new("core.v1.PersistentVolumeStatus", &[
+        Field::new(1, "phase", String).omit_empty(),
+        Field::new(2, "message", String).omit_empty(),
+        Field::new(3, "reason", String).omit_empty(),
+        Field::new(4, "lastPhaseTransitionTime", Message("meta.v1.Time")),
+    ]),
+    Schema::new("core.v1.PhotonPersistentDiskVolumeSource", &[
+        Field::new(1, "pdID", String),
+        Field::new(2, "fsType", String).omit_empty(),
+    ]),
+    Schema::new("core.v1.Pod", &[
+        Field::new(1, "metadata", Message("meta.v1.ObjectMeta")),
+        Field::new(2, "spec", Message("core.v1.PodSpec")),
+        Field::new(3, "status", Message("core.v1.PodStatus")),
+    ]),
+    Schema::new("core.v1.PodAffinity", &[
+        Field::new(1, "requiredDuringSchedulingIgnoredDuringExecution", Message("core.v1.PodAffinityTerm")).repeated(),
+        Field::new(2, "preferredDuringSchedulingIgnoredDuringExecution", Message("core.v1.WeightedPodAffinityTerm")).repeated(),
+    ]),
+    Schema::new("core.v1.PodAffinityTerm", &[
+        Field::new(1, "labelSelector", Message("meta.v1.LabelSelector")),
+        Field::new(2, "namespaces", String).repeated(),
+        Field::new(3, "topologyKey", String),
+        Field::new(4, "namespaceSelector", Message("meta.v1.LabelSelector")),
+        Field::new(5, "matchLabelKeys", String).repeated(),
+        Field::new(6, "mismatchLabelKeys", String).repeated(),
+    ]),
+    Schema::new("core.v1.PodAntiAffinity", &[
+        Field::new(1, "requiredDuringSchedulingIgnoredDuringExecution", Message("core.v1.PodAffinityTerm")).repeated(),
+        Field::new(2, "preferredDuringSchedulingIgnoredDuringExecution", Message("core.v1.WeightedPodAffinityTerm")).repeated(),
+    ]),
+    Schema::new("core.v1.PodAttachOptions", &[
+        Field::new(1, "stdin", Bool),
+        Field::new(2, "stdout", Bool),
+        Field::new(3, "stderr", Bool),
+        Field::new(4, "tty", Bool),
+        Field::new(5, "container", String).omit_empty(),
+    ]),
+    Schema::new("core.v1.PodCertificateProjection", &[
+        Field::new(1, "signerName", String),
+        Field::new(2, "keyType", String),
+        Field::new(3, "maxExpirationSeconds", Int32),
+        Field::new(4, "credentialBundlePath", String).omit_empty(),
+        Field::new(5, "keyPath", String).omit_empty(),
+        Field::new(6, "certificateChainPath", String).omit_empty(),
+        Field::new(7, "userAnnotations", Map(&String)),
+    ]),
+    Schema::new("core.v1.PodCondition", &[
+        Field::new(1, "type", String),
+        Field::new(2, "status", String),
+        Field::new(3, "lastProbeTime", Message("meta.v1.Time")),
+        Field::new(4, "lastTransitionTime", Message("meta.v1.Time")),
+        Field::new(5, "reason", String).omit_empty(),
+        Field::new(6, "message", String).omit_empty(),
+        Field::new(7, "observedGeneration", Int64),
+    ]),
+    Schema::new("core.v1.PodDNSConfig", &[
+        Field::new(1, "nameservers", String).repeated(),
+        Field::new(2, "searches", String).repeated(),
+        Field::new(3, "options", Message("core.v1.PodDNSConfigOption")).repeated(),
+    ]),
+    Schema::new("core.v1.PodDNSConfigOption", &[
+        Field::new(1, "name", String).omit_empty(),
+        Field::new(2, "value", String).omit_empty(),
+    ]),
+    Schema::new("core.v1.PodExecOptions", &[
+        Field::new(1, "stdin", Bool),
+        Field::new(2, "stdout", Bool),
+        Field::new(3, "stderr", Bool),
+        Field::new(4, "tty", Bool),
+        Field::new(5, "container", String).omit_empty(),
+        Field::new(6, "command", String).repeated(),
+    ]),
+    Schema::new("core.v1.PodExtendedResourceClaimStatus", &[
+        Field::new(1, "requestMappings", Message("core.v1.ContainerExtendedResourceRequest")).repeated(),
+        Field::new(2, "resourceClaimName", String),
+    ]),
+    Schema::new("core.v1.PodIP", &[
+        Field::new(1, "ip", String),
+    ]),
+    Schema::new("core.v1.PodList", &[
+        Field::new(1, "metadata", Message("meta.v1.ListMeta")),
+        Field::new(2, "items", Message("core.v1.Pod")).repeated(),
+    ]),
+    Schema::new("core.v1.PodLogOptions", &[
+        Field::new(1, "container", String).omit_empty(),
+        Field::new(2, "follow", Bool),
+        Field::new(3, "previous", Bool),
+        Field::new(4, "sinceSeconds", Int64),
+        Field::new(5, "sinceTime", Message("meta.v1.Time")),
+        Field::new(6, "timestamps", Bool),
+        Field::new(7, "tailLines", Int64),
+        Field::new(8, "limitBytes", Int64),
+        Field::new(9, "insecureSkipTlsVerifyBackend", Bool),
+        Field::new(10, "stream", String).omit_empty(),
+    ]),
+    Schema::new("core.v1.PodOS", &[
+        Field::new(1, "name", String),
+    ]),
+    Schema::new("core.v1.PodPortForwardOptions", &[
+        Field::new(1, "ports", Int32).repeated(),
+    ]),
+    Schema::new("core.v1.PodProxyOptions", &[
+        Field::new(1, "path", String).omit_empty(),
+    ]),
+    Schema::new("core.v1.PodReadinessGate", &[
+        Field::new(1, "conditionType", String),
+    ]),
+    Schema::new("core.v1.PodResourceClaim", &[
+        Field::new(1, "name", String),
+        Field::new(3, "resourceClaimName", String).omit_empty(),
+        Field::new(4, "resourceClaimTemplateName", String).omit_empty(),
+    ]),
+    Schema::new("core.v1.PodResourceClaimStatus", &[
+        Field::new(1, "name", String),
+        Field::new(2, "resourceClaimName", String).omit_empty(),
+    ]),
+    Schema::new("core.v1.PodSchedulingGate", &[
+        Field::new(1, "name", String),
+    ]),
+    Schema::new("core.v1.PodSecurityContext", &[
+        Field::new(1, "seLinuxOptions", Message("core.v1.SELinuxOptions")),
+        Field::new(2, "runAsUser", Int64),
+        Field::new(3, "runAsNonRoot", Bool),
+        Field::new(4, "supplementalGroups", Int64).repeated(),
+        Field::new(5, "fsGroup", Int64),
+        Field::new(6, "runAsGroup", Int64),
+        Field::new(7, "sysctls", Message("core.v1.Sysctl")).repeated(),
+        Field::new(8, "windowsOptions", Message("core.v1.WindowsSecurityContextOptions")),
+        Field::new(9, "fsGroupChangePolicy", String).omit_empty(),
+        Field::new(10, "seccompProfile", Message("core.v1.SeccompProfile")),
+        Field::new(11, "appArmorProfile", Message("core.v1.AppArmorProfile")),
+        Field::new(12, "supplementalGroupsPolicy", String).omit_empty(),
+        Field::new(13, "seLinuxChangePolicy", String).omit_empty(),
+    ]),
+    Schema::new("core.v1.PodSignature", &[
+        Field::new(1, "podController", Message("meta.v1.OwnerReference")),
+    ]),
+    Schema::new("core.v1.PodSpec", &[
+        Field::new(1, "volumes", Message("core.v1.Volume")).repeated(),
+        Field::new(2, "containers", Message("core.v1.Container")).repeated(),
+        Field::new(3, "restartPolicy", String).omit_empty(),
+        Field::new(4, "terminationGracePeriodSeconds", Int64),
+        Field::new(5, "activeDeadlineSeconds", Int64),
+        Field::new(6, "dnsPolicy", String).omit_empty(),
+        Field::new(7, "nodeSelector", Map(&String)),
+        Field::new(8, "serviceAccountName", String).omit_empty(),
+        Field::new(9, "serviceAccount", String).omit_empty(),
+        Field::new(10, "nodeName", String).omit_empty(),
+        Field::new(11, "hostNetwork", Bool),
+        Field::new(12, "hostPID", Bool),
+        Field::new(13, "hostIPC", Bool),
+        Field::new(14, "securityContext", Message("core.v1.PodSecurityContext")),
+        Field::new(15, "imagePullSecrets", Message("core.v1.LocalObjectReference")).repeated(),
+        Field::new(16, "hostname", String).omit_empty(),
+        Field::new(17, "subdomain", String).omit_empty(),
+        Field::new(18, "affinity", Message("core.v1.Affinity")),
+        Field::new(19, "schedulerName", String).omit_empty(),
+        Field::new(20, "initContainers", Message("core.v1.Container")).repeated(),
+        Field::new(21, "automountServiceAccountToken", Bool),
+        Field::new(22, "tolerations", Message("core.v1.Toleration")).repeated(),
+        Field::new(23, "hostAliases", Message("core.v1.HostAlias")).repeated(),
+        Field::new(24, "priorityClassName", String).omit_empty(),
+        Field::new(25, "priority", Int32),
+        Field::new(26, "dnsConfig", Message("core.v1.PodDNSConfig")),
+        Field::new(27, "shareProcessNamespace", Bool),
+        Field::new(28, "readinessGates", Message("core.v1.PodReadinessGate")).repeated(),
+        Field::new(29, "runtimeClassName", String).omit_empty(),
+        Field::new(30, "enableServiceLinks", Bool),
+        Field::new(31, "preemptionPolicy", String).omit_empty(),
+        Field::new(32, "overhead", Map(&Message("resource.Quantity"))),
+        Field::new(33, "topologySpreadConstraints", Message("core.v1.TopologySpreadConstraint")).repeated(),
+        Field::new(34, "ephemeralContainers", Message("core.v1.EphemeralContainer")).repeated(),
+        Field::new(35, "setHostnameAsFQDN", Bool),
+        Field::new(36, "os", Message("core.v1.PodOS")),
+        Field::new(37, "hostUsers", Bool),
+        Field::new(38, "schedulingGates", Message("core.v1.PodSchedulingGate")).repeated(),
+        Field::new(39, "resourceClaims", Message("core.v1.PodResourceClaim")).repeated(),
+        Field::new(40, "resources", Message("core.v1.ResourceRequirements")),
+        Field::new(41, "hostnameOverride", String).omit_empty(),
+        Field::new(42, "workloadRef", Message("core.v1.WorkloadReference")),
+    ]),
+    Schema::new("core.v1.PodStatus", &[
+        Field::new(1, "phase", String).omit_empty(),
+        Field::new(2, "conditions", Message("core.v1.PodCondition")).repeated(),
+        Field::new(3, "message", String).omit_empty(),
+        Field::new(4, "reason", String).omit_empty(),
+        Field::new(5, "hostIP", String).omit_empty(),
+        Field::new(6, "podIP", String).omit_empty(),
+        Field::new(7, "startTime", Message("meta.v1.Time")),
+        Field::new(8, "containerStatuses", Message("core.v1.ContainerStatus")).repeated(),
+        Field::new(9, "qosClass", String).omit_empty(),
+        Field::new(10, "initContainerStatuses", Message("core.v1.ContainerStatus")).repeated(),
+        Field::new(11, "nominatedNodeName", String).omit_empty(),
+        Field::new(12, "podIPs", Message("core.v1.PodIP")).repeated(),
+        Field::new(13, "ephemeralContainerStatuses", Message("core.v1.ContainerStatus")).repeated(),
+        Field::new(14, "resize", String).omit_empty(),
+        Field::new(15, "resourceClaimStatuses", Message("core.v1.PodResourceClaimStatus")).repeated(),
+        Field::new(16, "hostIPs", Message("core.v1.HostIP")).repeated(),
+        Field::new(17, "observedGeneration", Int64),
+        Field::new(18, "extendedResourceClaimStatus", Message("core.v1.PodExtendedResourceClaimStatus")),
+        Field::new(19, "allocatedResources", Map(&Message("resource.Quantity"))),
+        Field::new(20, "resources", Message("core.v1.ResourceRequirements")),
+    ]),
+    Schema::new("core.v1.PodStatusResult", &[
+        Field::new(1, "metadata", Message("meta.v1.ObjectMeta")),
+        Field::new(2, "status", Message("core.v1.PodStatus")),
+    ]),
+    Schema::new("core.v1.PodTemplate", &[
+        Field::new(1, "metadata", Message("meta.v1.ObjectMeta")),
+        Field::new(2, "template", Message("core.v1.PodTemplateSpec")),
+    ]),
+    Schema::new("core.v1.PodTemplateList", &[
+        Field::new(1, "metadata", Message("meta.v1.ListMeta")),
+        Field::new(2, "items", Message("core.v1.PodTemplate")).repeated(),
+    ]),
+    Schema::new("core.v1.PodTemplateSpec", &[
+        Field::new(1, "metadata", Message("meta.v1.ObjectMeta")),
+        Field::new(2, "spec", Message("core.v1.PodSpec")),
+    ]),
+    Schema::new("core.v1.PortStatus", &[
+        Field::new(1, "port", Int32),
+        Field::new(2, "protocol", String),
+        Field::new(3, "error", String).omit_empty(),
+    ]),
+    Schema::new("core.v1.PortworxVolumeSource", &[
+        Field::new(1, "volumeID", String),
+        Field::new(2, "fsType", String).omit_empty(),
+        Field::new(3, "readOnly", Bool),
+    ]),
+    Schema::new("core.v1.Preconditions", &[
+        Field::new(1, "uid", String).omit_empty(),
+    ]),
+    Schema::new("core.v1.PreferAvoidPodsEntry", &[
+        Field::new(1, "podSignature", Message("core.v1.PodSignature")),
+        Field::new(2, "evictionTime", Message("meta.v1.Time")),
+        Field::new(3, "reason", String).omit_empty(),
+        Field::new(4, "message", String).omit_empty(),
+    ]),
+    Schema::new("core.v1.PreferredSchedulingTerm", &[
+        Field::new(1, "weight", Int32),
+        Field::new(2, "preference", Message("core.v1.NodeSelectorTerm")),
+    ]),
+    Schema::new("core.v1.Probe", &[
+        Field::new(1, "handler", Message("core.v1.ProbeHandler")).inline(),
+        Field::new(2, "initialDelaySeconds", Int32),
+        Field::new(3, "timeoutSeconds", Int32),
+        Field::new(4, "periodSeconds", Int32),
+        Field::new(5, "successThreshold", Int32),
+        Field::new(6, "failureThreshold", Int32),
+        Field::new(7, "terminationGracePeriodSeconds", Int64),
+    ]),
+    Schema::new("core.v1.ProbeHandler", &[
+        Field::new(1, "exec", Message("core.v1.ExecAction")),
+        Field::new(2, "httpGet", Message("core.v1.HTTPGetAction")),
+        Field::new(3, "tcpSocket", Message("core.v1.TCPSocketAction")),
+        Field::new(4, "grpc", Message("core.v1.GRPCAction")),
+    ]),
+    Schema::new("core.v1.ProjectedVolumeSource", &[
+        Field::new(1, "sources", Message("core.v1.VolumeProjection")).repeated(),
+        Field::new(2, "defaultMode", Int32),
+    ]),
+    Schema::new("core.v1.QuobyteVolumeSource", &[
+        Field::new(1, "registry", String),
+        Field::new(2, "volume", String),
+        Field::new(3, "readOnly", Bool),
+        Field::new(4, "user", String).omit_empty(),
+        Field::new(5, "group", String).omit_empty(),
+        Field::new(6, "tenant", String).omit_empty(),
+    ]),
+    Schema::new("core.v1.RBDPersistentVolumeSource", &[
+        Field::new(1, "monitors", String).repeated(),
+        Field::new(2, "image", String),
+        Field::new(3, "fsType", String).omit_empty(),
+        Field::new(4, "pool", String).omit_empty(),
+        Field::new(5, "user", String).omit_empty(),
+        Field::new(6, "keyring", String).omit_empty(),
+        Field::new(7, "secretRef", Message("core.v1.SecretReference")),
+        Field::new(8, "readOnly", Bool),
+    ]),
+    Schema::new("core.v1.RBDVolumeSource", &[
+        Field::new(1, "monitors", String).repeated(),
+        Field::new(2, "image", String),
+        Field::new(3, "fsType", String).omit_empty(),
+        Field::new(4, "pool", String).omit_empty(),
+        Field::new(5, "user", String).omit_empty(),
+        Field::new(6, "keyring", String).omit_empty(),
+        Field::new(7, "secretRef", Message("core.v1.LocalObjectReference")),
+        Field::new(8, "readOnly", Bool),
+    ]),
+    Schema::new("core.v1.RangeAllocation", &[
+        Field::new(1, "metadata", Message("meta.v1.ObjectMeta")),
+        Field::new(2, "range", String).omit_empty(),
+        Field::new(3, "data", Bytes),
+    ]),
+    Schema::new("core.v1.ReplicationController", &[
+        Field::new(1, "metadata", Message("meta.v1.ObjectMeta")),
+        Field::new(2, "spec", Message("core.v1.ReplicationControllerSpec")),
+        Field::new(3, "status", Message("core.v1.ReplicationControllerStatus")),
+    ]),
+    Schema::new("core.v1.ReplicationControllerCondition", &[
+        Field::new(1, "type", String),
+        Field::new(2, "status", String),
+        Field::new(3, "lastTransitionTime", Message("meta.v1.Time")),
+        Field::new(4, "reason", String).omit_empty(),
+        Field::new(5, "message", String).omit_empty(),
+    ]),
+    Schema::new("core.v1.ReplicationControllerList", &[
+        Field::new(1, "metadata", Message("meta.v1.ListMeta")),
+        Field::new(2, "items", Message("core.v1.ReplicationController")).repeated(),
+    ]),
+    Schema::new("core.v1.ReplicationControllerSpec", &[
+        Field::new(1, "replicas", Int32),
+        Field::new(2, "selector", Map(&String)),
+        Field::new(3, "template", Message("core.v1.PodTemplateSpec")),
+        Field::new(4, "minReadySeconds", Int32),
+    ]),
+    Schema::new("core.v1.ReplicationControllerStatus", &[
+        Field::new(1, "replicas", Int32),
+        Field::new(2, "fullyLabeledReplicas", Int32),
+        Field::new(3, "observedGeneration", Int64),
+        Field::new(4, "readyReplicas", Int32),
+        Field::new(5, "availableReplicas", Int32),
+        Field::new(6, "conditions", Message("core.v1.ReplicationControllerCondition")).repeated(),
+    ]),
+    Schema::new("core.v1.ResourceClaim", &[
+        Field::new(1, "name", String),
+        Field::new(2, "request", String).omit_empty(),
+    ]),
+    Schema::new("core.v1.ResourceFieldSelector", &[
+        Field::new(1, "containerName", String).omit_empty(),
+        Field::new(2, "resource", String),
+        Field::new(3, "divisor", Message("resource.Quantity")),
+    ]),
+    Schema::new("core.v1.ResourceHealth", &[
+        Field::new(1, "resourceID", String),
+        Field::new(2, "health", String).omit_empty(),
+    ]),
+    Schema::new("core.v1.ResourceQuota", &[
+        Field::new(1, "metadata", Message("meta.v1.ObjectMeta")),
+        Field::new(2, "spec", Message("core.v1.ResourceQuotaSpec")),
+        Field::new(3, "status", Message("core.v1.ResourceQuotaStatus")),
+    ]),
+    Schema::new("core.v1.ResourceQuotaList", &[
+        Field::new(1, "metadata", Message("meta.v1.ListMeta")),
+        Field::new(2, "items", Message("core.v1.ResourceQuota")).repeated(),
+    ]),
+    Schema::new("core.v1.ResourceQuotaSpec", &[
+        Field::new(1, "hard", Map(&Message("resource.Quantity"))),
+        Field::new(2, "scopes", String).repeated(),
+        Field::new(3, "scopeSelector", Message("core.v1.ScopeSelector")),
+    ]),
+    Schema::new("core.v1.ResourceQuotaStatus", &[
+        Field::new(1, "hard", Map(&Message("resource.Quantity"))),
+        Field::new(2, "used", Map(&Message("resource.Quantity"))),
+    ]),
+    Schema::new("core.v1.ResourceRequirements", &[
+        Field::new(1, "limits", Map(&Message("resource.Quantity"))),
+        Field::new(2, "requests", Map(&Message("resource.Quantity"))),
+        Field::new(3, "claims", Message("core.v1.ResourceClaim")).repeated(),
+    ]),
+    Schema::new("core.v1.ResourceStatus", &[
+        Field::new(1, "name", String),
+        Field::new(2, "resources", Message("core.v1.ResourceHealth")).repeated(),
+    ]),
+    Schema::new("core.v1.SELinuxOptions", &[
+        Field::new(1, "user", String).omit_empty(),
+        Field::new(2, "role", String).omit_empty(),
+        Field::new(3, "type", String).omit_empty(),
+        Field::new(4, "level", String).omit_empty(),
+    ]),
+    Schema::new("core.v1.ScaleIOPersistentVolumeSource", &[
+        Field::new(1, "gateway", String),
+        Field::new(2, "system", String),
+        Field::new(3, "secretRef", Message("core.v1.SecretReference")),
+        Field::new(4, "sslEnabled", Bool),
+        Field::new(5, "protectionDomain", String).omit_empty(),
+        Field::new(6, "storagePool", String).omit_empty(),
+        Field::new(7, "storageMode", String).omit_empty(),
+        Field::new(8, "volumeName", String).omit_empty(),
+        Field::new(9, "fsType", String).omit_empty(),
+        Field::new(10, "readOnly", Bool),
+    ]),
+    Schema::new("core.v1.ScaleIOVolumeSource", &[
+        Field::new(1, "gateway", String),
+        Field::new(2, "system", String),
+        Field::new(3, "secretRef", Message("core.v1.LocalObjectReference")),
+        Field::new(4, "sslEnabled", Bool),
+        Field::new(5, "protectionDomain", String).omit_empty(),
+        Field::new(6, "storagePool", String).omit_empty(),
+        Field::new(7, "storageMode", String).omit_empty(),
+        Field::new(8, "volumeName", String).omit_empty(),
+        Field::new(9, "fsType", String).omit_empty(),
+        Field::new(10, "readOnly", Bool),
+    ]),
+    Schema::new("core.v1.ScopeSelector", &[
+        Field::new(1, "matchExpressions", Message("core.v1.ScopedResourceSelectorRequirement")).repeated(),
+    ]),
+    Schema::new("core.v1.ScopedResourceSelectorRequirement", &[
+        Field::new(1, "scopeName", String),
+        Field::new(2, "operator", String),
+        Field::new(3, "values", String).repeated(),
+    ]),
+    Schema::new("core.v1.SeccompProfile", &[
+        Field::new(1, "type", String),
+        Field::new(2, "localhostProfile", String).omit_empty(),
+    ]),
+    Schema::new("core.v1.Secret", &[
+        Field::new(1, "metadata", Message("meta.v1.ObjectMeta")),
+        Field::new(2, "data", Map(&Bytes)),
+        Field::new(3, "type", String).omit_empty(),
+        Field::new(4, "stringData", Map(&String)),
+        Field::new(5, "immutable", Bool),
+    ]),
+    Schema::new("core.v1.SecretEnvSource", &[
+        Field::new(1, "localObjectReference", Message("core.v1.LocalObjectReference")).inline(),
+        Field::new(2, "optional", Bool),
+    ]),
+    Schema::new("core.v1.SecretKeySelector", &[
+        Field::new(1, "localObjectReference", Message("core.v1.LocalObjectReference")).inline(),
+        Field::new(2, "key", String),
+        Field::new(3, "optional", Bool),
+    ]),
+    Schema::new("core.v1.SecretList", &[
+        Field::new(1, "metadata", Message("meta.v1.ListMeta")),
+        Field::new(2, "items", Message("core.v1.Secret")).repeated(),
+    ]),
+    Schema::new("core.v1.SecretProjection", &[
+        Field::new(1, "localObjectReference", Message("core.v1.LocalObjectReference")).inline(),
+        Field::new(2, "items", Message("core.v1.KeyToPath")).repeated(),
+        Field::new(4, "optional", Bool),
+    ]),
+    Schema::new("core.v1.SecretReference", &[
+        Field::new(1, "name", String).omit_empty(),
+        Field::new(2, "namespace", String).omit_empty(),
+    ]),
+    Schema::new("core.v1.SecretVolumeSource", &[
+        Field::new(1, "secretName", String).omit_empty(),
+        Field::new(2, "items", Message("core.v1.KeyToPath")).repeated(),
+        Field::new(3, "defaultMode", Int32),
+        Field::new(4, "optional", Bool),
+    ]),
+    Schema::new("core.v1.SecurityContext", &[
+        Field::new(1, "capabilities", Message("core.v1.Capabilities")),
+        Field::new(2, "privileged", Bool),
+        Field::new(3, "seLinuxOptions", Message("core.v1.SELinuxOptions")),
+        Field::new(4, "runAsUser", Int64),
+        Field::new(5, "runAsNonRoot", Bool),
+        Field::new(6, "readOnlyRootFilesystem", Bool),
+        Field::new(7, "allowPrivilegeEscalation", Bool),
+        Field::new(8, "runAsGroup", Int64),
+        Field::new(9, "procMount", String).omit_empty(),
+        Field::new(10, "windowsOptions", Message("core.v1.WindowsSecurityContextOptions")),
+        Field::new(11, "seccompProfile", Message("core.v1.SeccompProfile")),
+        Field::new(12, "appArmorProfile", Message("core.v1.AppArmorProfile")),
+    ]),
+    Schema::new("core.v1.SerializedReference", &[
+        Field::new(1, "reference", Message("core.v1.ObjectReference")),
+    ]),
+    Schema::new("core.v1.Service", &[
+        Field::new(1, "metadata", Message("meta.v1.ObjectMeta")),
+        Field::new(2, "spec", Message("core.v1.ServiceSpec")),
+        Field::new(3, "status", Message("core.v1.ServiceStatus")),
+    ]),
+    Schema::new("core.v1.ServiceAccount", &[
+        Field::new(1, "metadata", Message("meta.v1.ObjectMeta")),
+        Field::new(2, "secrets", Message("core.v1.ObjectReference")).repeated(),
+        Field::new(3, "imagePullSecrets", Message("core.v1.LocalObjectReference")).repeated(),
+        Field::new(4, "automountServiceAccountToken", Bool),
+    ]),
+    Schema::new("core.v1.ServiceAccountList", &[
+        Field::new(1, "metadata", Message("meta.v1.ListMeta")),
+        Field::new(2, "items", Message("core.v1.ServiceAccount")).repeated(),
+    ]),
+    Schema::new("core.v1.ServiceAccountTokenProjection", &[
+        Field::new(1, "audience", String).omit_empty(),
+        Field::new(2, "expirationSeconds", Int64),
+        Field::new(3, "path", String),
+    ]),
+    Schema::new("core.v1.ServiceList", &[
+        Field::new(1, "metadata", Message("meta.v1.ListMeta")),
+        Field::new(2, "items", Message("core.v1.Service")).repeated(),
+    ]),
+    Schema::new("core.v1.ServicePort", &[
+        Field::new(1, "name", String).omit_empty(),
+        Field::new(2, "protocol", String).omit_empty(),
+        Field::new(3, "port", Int32),
+        Field::new(4, "targetPort", Message("intstr.IntOrString")),
+        Field::new(5, "nodePort", Int32),
+        Field::new(6, "appProtocol", String).omit_empty(),
+    ]),
+    Schema::new("core.v1.ServiceProxyOptions", &[
+        Field::new(1, "path", String).omit_empty(),
+    ]),
+    Schema::new("core.v1.ServiceSpec", &[
+        Field::new(1, "ports", Message("core.v1.ServicePort")).repeated(),
+        Field::new(2, "selector", Map(&String)),
+        Field::new(3, "clusterIP", String).omit_empty(),
+        Field::new(4, "type", String).omit_empty(),
+        Field::new(5, "externalIPs", String).repeated(),
+        Field::new(7, "sessionAffinity", String).omit_empty(),
+        Field::new(8, "loadBalancerIP", String).omit_empty(),
+        Field::new(9, "loadBalancerSourceRanges", String).repeated(),
+        Field::new(10, "externalName", String).omit_empty(),
+        Field::new(11, "externalTrafficPolicy", String).omit_empty(),
+        Field::new(12, "healthCheckNodePort", Int32),
+        Field::new(13, "publishNotReadyAddresses", Bool),
+        Field::new(14, "sessionAffinityConfig", Message("core.v1.SessionAffinityConfig")),
+        Field::new(17, "ipFamilyPolicy", String).omit_empty(),
+        Field::new(18, "clusterIPs", String).repeated(),
+        Field::new(19, "ipFamilies", String).repeated(),
+        Field::new(20, "allocateLoadBalancerNodePorts", Bool),
+        Field::new(21, "loadBalancerClass", String).omit_empty(),
+        Field::new(22, "internalTrafficPolicy", String).omit_empty(),
+        Field::new(23, "trafficDistribution", String).omit_empty(),
+    ]),
+    Schema::new("core.v1.ServiceStatus", &[
+        Field::new(1, "loadBalancer", Message("core.v1.LoadBalancerStatus")),
+        Field::new(2, "conditions", Message("meta.v1.Condition")).repeated(),
+    ]),
+    Schema::new("core.v1.SessionAffinityConfig", &[
+        Field::new(1, "clientIP", Message("core.v1.ClientIPConfig")),
+    ]),
+    Schema::new("core.v1.SleepAction", &[
+        Field::new(1, "seconds", Int64),
+    ]),
+    Schema::new("core.v1.StorageOSPersistentVolumeSource", &[
+        Field::new(1, "volumeName", String).omit_empty(),
+        Field::new(2, "volumeNamespace", String).omit_empty(),
+        Field::new(3, "fsType", String).omit_empty(),
+        Field::new(4, "readOnly", Bool),
+        Field::new(5, "secretRef", Message("core.v1.ObjectReference")),
+    ]),
+    Schema::new("core.v1.StorageOSVolumeSource", &[
+        Field::new(1, "volumeName", String).omit_empty(),
+        Field::new(2, "volumeNamespace", String).omit_empty(),
+        Field::new(3, "fsType", String).omit_empty(),
+        Field::new(4, "readOnly", Bool),
+        Field::new(5, "secretRef", Message("core.v1.LocalObjectReference")),
+    ]),
+    Schema::new("core.v1.Sysctl", &[
+        Field::new(1, "name", String),
+        Field::new(2, "value", String),
+    ]),
+    Schema::new("core.v1.TCPSocketAction", &[
+        Field::new(1, "port", Message("intstr.IntOrString")),
+        Field::new(2, "host", String).omit_empty(),
+    ]),
+    Schema::new("core.v1.Taint", &[
+        Field::new(1, "key", String),
+        Field::new(2, "value", String).omit_empty(),
+        Field::new(3, "effect", String),
+        Field::new(4, "timeAdded", Message("meta.v1.Time")),
+    ]),
+    Schema::new("core.v1.Toleration", &[
+        Field::new(1, "key", String).omit_empty(),
+        Field::new(2, "operator", String).omit_empty(),
+        Field::new(3, "value", String).omit_empty(),
+        Field::new(4, "effect", String).omit_empty(),
+        Field::new(5, "tolerationSeconds", Int64),
+    ]),
+    Schema::new("core.v1.TopologySelectorLabelRequirement", &[
+        Field::new(1, "key", String),
+        Field::new(2, "values", String).repeated(),
+    ]),
+    Schema::new("core.v1.TopologySelectorTerm", &[
+        Field::new(1, "matchLabelExpressions", Message("core.v1.TopologySelectorLabelRequirement")).repeated(),
+    ]),
+    Schema::new("core.v1.TopologySpreadConstraint", &[
+        Field::new(1, "maxSkew", Int32),
+        Field::new(2, "topologyKey", String),
+        Field::new(3, "whenUnsatisfiable", String),
+        Field::new(4, "labelSelector", Message("meta.v1.LabelSelector")),
+        Field::new(5, "minDomains", Int32),
+        Field::new(6, "nodeAffinityPolicy", String).omit_empty(),
+        Field::new(7, "nodeTaintsPolicy", String).omit_empty(),
+        Field::new(8, "matchLabelKeys", String).repeated(),
+    ]),
+    Schema::new("core.v1.TypedLocalObjectReference", &[
+        Field::new(1, "apiGroup", String).omit_empty(),
+        Field::new(2, "kind", String),
+        Field::new(3, "name", String),
+    ]),
+    Schema::new("core.v1.TypedObjectReference", &[
+        Field::new(1, "apiGroup", String).omit_empty(),
+        Field::new(2, "kind", String),
+        Field::new(3, "name", String),
+        Field::new(4, "namespace", String).omit_empty(),
+    ]),
+    Schema::new("core.v1.Volume", &[
+        Field::new(1, "name", String),
+        Field::new(2, "volumeSource", Message("core.v1.VolumeSource")).inline(),
+    ]),
+    Schema::new("core.v1.VolumeDevice", &[
+        Field::new(1, "name", String),
+        Field::new(2, "devicePath", String),
+    ]),
+    Schema::new("core.v1.VolumeMount", &[
+        Field::new(1, "name", String),
+        Field::new(2, "readOnly", Bool),
+        Field::new(3, "mountPath", String),
+        Field::new(4, "subPath", String).omit_empty(),
+        Field::new(5, "mountPropagation", String).omit_empty(),
+        Field::new(6, "subPathExpr", String).omit_empty(),
+        Field::new(7, "recursiveReadOnly", String).omit_empty(),
+    ]),
+    Schema::new("core.v1.VolumeMountStatus", &[
+        Field::new(1, "name", String),
+        Field::new(2, "mountPath", String),
+        Field::new(3, "readOnly", Bool),
+        Field::new(4, "recursiveReadOnly", String).omit_empty(),
+    ]),
+    Schema::new("core.v1.VolumeNodeAffinity", &[
+        Field::new(1, "required", Message("core.v1.NodeSelector")),
+    ]),
+    Schema::new("core.v1.VolumeProjection", &[
+        Field::new(1, "secret", Message("core.v1.SecretProjection")),
+        Field::new(2, "downwardAPI", Message("core.v1.DownwardAPIProjection")),
+        Field::new(3, "configMap", Message("core.v1.ConfigMapProjection")),
+        Field::new(4, "serviceAccountToken", Message("core.v1.ServiceAccountTokenProjection")),
+        Field::new(5, "clusterTrustBundle", Message("core.v1.ClusterTrustBundleProjection")),
+        Field::new(6, "podCertificate", Message("core.v1.PodCertificateProjection")),
+    ]),
+    Schema::new("core.v1.VolumeResourceRequirements", &[
+        Field::new(1, "limits", Map(&Message("resource.Quantity"))),
+        Field::new(2, "requests", Map(&Message("resource.Quantity"))),
+    ]),
+    Schema::new("core.v1.VolumeSource", &[
+        Field::new(1, "hostPath", Message("core.v1.HostPathVolumeSource")),
+        Field::new(2, "emptyDir", Message("core.v1.EmptyDirVolumeSource")),
+        Field::new(3, "gcePersistentDisk", Message("core.v1.GCEPersistentDiskVolumeSource")),
+        Field::new(4, "awsElasticBlockStore", Message("core.v1.AWSElasticBlockStoreVolumeSource")),
+        Field::new(5, "gitRepo", Message("core.v1.GitRepoVolumeSource")),
+        Field::new(6, "secret", Message("core.v1.SecretVolumeSource")),
+        Field::new(7, "nfs", Message("core.v1.NFSVolumeSource")),
+        Field::new(8, "iscsi", Message("core.v1.ISCSIVolumeSource")),
+        Field::new(9, "glusterfs", Message("core.v1.GlusterfsVolumeSource")),
+        Field::new(10, "persistentVolumeClaim", Message("core.v1.PersistentVolumeClaimVolumeSource")),
+        Field::new(11, "rbd", Message("core.v1.RBDVolumeSource")),
+        Field::new(12, "flexVolume", Message("core.v1.FlexVolumeSource")),
+        Field::new(13, "cinder", Message("core.v1.CinderVolumeSource")),
+        Field::new(14, "cephfs", Message("core.v1.CephFSVolumeSource")),
+        Field::new(15, "flocker", Message("core.v1.FlockerVolumeSource")),
+        Field::new(16, "downwardApi", Message("core.v1.DownwardAPIVolumeSource")),
+        Field::new(17, "fc", Message("core.v1.FCVolumeSource")),
+        Field::new(18, "azureFile", Message("core.v1.AzureFileVolumeSource")),
+        Field::new(19, "configMap", Message("core.v1.ConfigMapVolumeSource")),
+        Field::new(20, "vsphereVolume", Message("core.v1.VsphereVirtualDiskVolumeSource")),
+        Field::new(21, "quobyte", Message("core.v1.QuobyteVolumeSource")),
+        Field::new(22, "azureDisk", Message("core.v1.AzureDiskVolumeSource")),
+        Field::new(23, "photonPersistentDisk", Message("core.v1.PhotonPersistentDiskVolumeSource")),
+        Field::new(24, "portworxVolume", Message("core.v1.PortworxVolumeSource")),
+        Field::new(25, "scaleIo", Message("core.v1.ScaleIOVolumeSource")),
+        Field::new(26, "projected", Message("core.v1.ProjectedVolumeSource")),
+        Field::new(27, "storageos", Message("core.v1.StorageOSVolumeSource")),
+        Field::new(28, "csi", Message("core.v1.CSIVolumeSource")),
+        Field::new(29, "ephemeral", Message("core.v1.EphemeralVolumeSource")),
+        Field::new(30, "image", Message("core.v1.ImageVolumeSource")),
+    ]),
+    Schema::new("core.v1.VsphereVirtualDiskVolumeSource", &[
+        Field::new(1, "volumePath", String),
+        Field::new(2, "fsType", String).omit_empty(),
+        Field::new(3, "storagePolicyName", String).omit_empty(),
+        Field::new(4, "storagePolicyID", String).omit_empty(),
+    ]),
+    Schema::new("core.v1.WeightedPodAffinityTerm", &[
+        Field::new(1, "weight", Int32),
+        Field::new(2, "podAffinityTerm", Message("core.v1.PodAffinityTerm")),
+    ]),
+    Schema::new("core.v1.WindowsSecurityContextOptions", &[
+        Field::new(1, "gmsaCredentialSpecName", String).omit_empty(),
+        Field::new(2, "gmsaCredentialSpec", String).omit_empty(),
+        Field::new(3, "runAsUserName", String).omit_empty(),
+        Field::new(4, "hostProcess", Bool),
+    ]),
+    Schema::new("core.v1.WorkloadReference", &[
+        Field::new(1, "name", String).omit_empty(),
+        Field::new(2, "podGroup", String).omit_empty(),
+        Field::new(3, "podGroupReplicaKey", String).omit_empty(),
+    ]),
+    Schema::new("intstr.IntOrString", &[
+        Field::new(1, "type", Int64),
+        Field::new(2, "intVal", Int32),
+        Field::new(3, "strVal", String).omit_empty(),
+    ]),
+    Schema::new("meta.v1.APIGroup", &[
+        Field::new(1, "name", String),
+        Field::new(2, "versions", Message("meta.v1.GroupVersionForDiscovery")).repeated(),
+        Field::new(3, "preferredVersion", Message("meta.v1.GroupVersionForDiscovery")),
+        Field::new(4, "serverAddressByClientCIDRs", Message("meta.v1.ServerAddressByClientCIDR")).repeated(),
+    ]),
+    Schema::new("meta.v1.APIGroupList", &[
+        Field::new(1, "groups", Message("meta.v1.APIGroup")).repeated(),
+    ]),
+    Schema::new("meta.v1.APIResource", &[
+        Field::new(1, "name", String),
+        Field::new(2, "namespaced", Bool),
+        Field::new(3, "kind", String),
+        Field::new(4, "verbs", Message("meta.v1.Verbs")),
+        Field::new(5, "shortNames", String).repeated(),
+        Field::new(6, "singularName", String),
+        Field::new(7, "categories", String).repeated(),
+        Field::new(8, "group", String).omit_empty(),
+        Field::new(9, "version", String).omit_empty(),
+        Field::new(10, "storageVersionHash", String).omit_empty(),
+    ]),
+    Schema::new("meta.v1.APIResourceList", &[
+        Field::new(1, "groupVersion", String),
+        Field::new(2, "resources", Message("meta.v1.APIResource")).repeated(),
+    ]),
+    Schema::new("meta.v1.APIVersions", &[
+        Field::new(1, "versions", String).repeated(),
+        Field::new(2, "serverAddressByClientCIDRs", Message("meta.v1.ServerAddressByClientCIDR")).repeated(),
+    ]),
+    Schema::new("meta.v1.ApplyOptions", &[
+        Field::new(1, "dryRun", String).repeated(),
+        Field::new(2, "force", Bool),
+        Field::new(3, "fieldManager", String).omit_empty(),
+    ]),
+    Schema::new("meta.v1.Condition", &[
+        Field::new(1, "type", String),
+        Field::new(2, "status", String),
+        Field::new(3, "observedGeneration", Int64),
+        Field::new(4, "lastTransitionTime", Message("meta.v1.Time")),
+        Field::new(5, "reason", String),
+        Field::new(6, "message", String),
+    ]),
+    Schema::new("meta.v1.CreateOptions", &[
+        Field::new(1, "dryRun", String).repeated(),
+        Field::new(3, "fieldManager", String).omit_empty(),
+        Field::new(4, "fieldValidation", String).omit_empty(),
+    ]),
+    Schema::new("meta.v1.DeleteOptions", &[
+        Field::new(1, "gracePeriodSeconds", Int64),
+        Field::new(2, "preconditions", Message("meta.v1.Preconditions")),
+        Field::new(3, "orphanDependents", Bool),
+        Field::new(4, "propagationPolicy", String).omit_empty(),
+        Field::new(5, "dryRun", String).repeated(),
+        Field::new(6, "ignoreStoreReadErrorWithClusterBreakingPotential", Bool),
+    ]),
+    Schema::new("meta.v1.Duration", &[
+        Field::new(1, "duration", Int64),
+    ]),
+    Schema::new("meta.v1.FieldSelectorRequirement", &[
+        Field::new(1, "key", String),
+        Field::new(2, "operator", String),
+        Field::new(3, "values", String).repeated(),
+    ]),
+    Schema::new("meta.v1.FieldsV1", &[
+        Field::new(1, "raw", Bytes),
+    ]),
+    Schema::new("meta.v1.GetOptions", &[
+        Field::new(1, "resourceVersion", String).omit_empty(),
+    ]),
+    Schema::new("meta.v1.GroupKind", &[
+        Field::new(1, "group", String).omit_empty(),
+        Field::new(2, "kind", String).omit_empty(),
+    ]),
+    Schema::new("meta.v1.GroupResource", &[
+        Field::new(1, "group", String),
+        Field::new(2, "resource", String),
+    ]),
+    Schema::new("meta.v1.GroupVersion", &[
+        Field::new(1, "group", String).omit_empty(),
+        Field::new(2, "version", String).omit_empty(),
+    ]),
+    Schema::new("meta.v1.GroupVersionForDiscovery", &[
+        Field::new(1, "groupVersion", String),
+        Field::new(2, "version", String),
+    ]),
+    Schema::new("meta.v1.GroupVersionKind", &[
+        Field::new(1, "group", String).omit_empty(),
+        Field::new(2, "version", String).omit_empty(),
+        Field::new(3, "kind", String).omit_empty(),
+    ]),
+    Schema::new("meta.v1.GroupVersionResource", &[
+        Field::new(1, "group", String).omit_empty(),
+        Field::new(2, "version", String).omit_empty(),
+        Field::new(3, "resource", String).omit_empty(),
+    ]),
+    Schema::new("meta.v1.LabelSelector", &[
+        Field::new(1, "matchLabels", Map(&String)),
+        Field::new(2, "matchExpressions", Message("meta.v1.LabelSelectorRequirement")).repeated(),
+    ]),
+    Schema::new("meta.v1.LabelSelectorRequirement", &[
+        Field::new(1, "key", String),
+        Field::new(2, "operator", String),
+        Field::new(3, "values", String).repeated(),
+    ]),
+    Schema::new("meta.v1.List", &[
+        Field::new(1, "metadata", Message("meta.v1.ListMeta")),
+        Field::new(2, "items", Message("runtime.RawExtension")).repeated(),
+    ]),
+    Schema::new("meta.v1.ListMeta", &[
+        Field::new(1, "selfLink", String).omit_empty(),
+        Field::new(2, "resourceVersion", String).omit_empty(),
+        Field::new(3, "continue", String).omit_empty(),
+        Field::new(4, "remainingItemCount", Int64),
+    ]),
+    Schema::new("meta.v1.ListOptions", &[
+        Field::new(1, "labelSelector", String).omit_empty(),
+        Field::new(2, "fieldSelector", String).omit_empty(),
+        Field::new(3, "watch", Bool),
+        Field::new(4, "resourceVersion", String).omit_empty(),
+        Field::new(5, "timeoutSeconds", Int64),
+        Field::new(7, "limit", Int64),
+        Field::new(8, "continue", String).omit_empty(),
+        Field::new(9, "allowWatchBookmarks", Bool),
+        Field::new(10, "resourceVersionMatch", String).omit_empty(),
+        Field::new(11, "sendInitialEvents", Bool),
+    ]),
+    Schema::new("meta.v1.ManagedFieldsEntry", &[
+        Field::new(1, "manager", String).omit_empty(),
+        Field::new(2, "operation", String).omit_empty(),
+        Field::new(3, "apiVersion", String).omit_empty(),
+        Field::new(4, "time", Message("meta.v1.Time")),
+        Field::new(6, "fieldsType", String).omit_empty(),
+        Field::new(7, "fieldsV1", Message("meta.v1.FieldsV1")),
+        Field::new(8, "subresource", String).omit_empty(),
+    ]),
+    Schema::new("meta.v1.MicroTime", &[
+        Field::new(1, "seconds", Int64),
+        Field::new(2, "nanos", Int32),
+    ]),
+    Schema::new("meta.v1.ObjectMeta", &[
+        Field::new(1, "name", String).omit_empty(),
+        Field::new(2, "generateName", String).omit_empty(),
+        Field::new(3, "namespace", String).omit_empty(),
+        Field::new(4, "selfLink", String).omit_empty(),
+        Field::new(5, "uid", String).omit_empty(),
+        Field::new(6, "resourceVersion", String).omit_empty(),
+        Field::new(7, "generation", Int64),
+        Field::new(8, "creationTimestamp", Message("meta.v1.Time")),
+        Field::new(9, "deletionTimestamp", Message("meta.v1.Time")),
+        Field::new(10, "deletionGracePeriodSeconds", Int64),
+        Field::new(11, "labels", Map(&String)),
+        Field::new(12, "annotations", Map(&String)),
+        Field::new(13, "ownerReferences", Message("meta.v1.OwnerReference")).repeated(),
+        Field::new(14, "finalizers", String).repeated(),
+        Field::new(17, "managedFields", Message("meta.v1.ManagedFieldsEntry")).repeated(),
+    ]),
+    Schema::new("meta.v1.OwnerReference", &[
+        Field::new(1, "kind", String),
+        Field::new(3, "name", String),
+        Field::new(4, "uid", String),
+        Field::new(5, "apiVersion", String),
+        Field::new(6, "controller", Bool),
+        Field::new(7, "blockOwnerDeletion", Bool),
+    ]),
+    Schema::new("meta.v1.PartialObjectMetadata", &[
+        Field::new(1, "metadata", Message("meta.v1.ObjectMeta")),
+    ]),
+    Schema::new("meta.v1.PartialObjectMetadataList", &[
+        Field::new(1, "metadata", Message("meta.v1.ListMeta")),
+        Field::new(2, "items", Message("meta.v1.PartialObjectMetadata")).repeated(),
+    ]),
+    Schema::new("meta.v1.Patch", &[
+        Field::new(1, "dryRun", String).repeated(),
+        Field::new(2, "force", Bool),
+        Field::new(3, "fieldManager", String).omit_empty(),
+        Field::new(4, "fieldValidation", String).omit_empty(),
+    ]),
+    Schema::new("meta.v1.Preconditions", &[
+        Field::new(1, "uid", String).omit_empty(),
+        Field::new(2, "resourceVersion", String).omit_empty(),
+    ]),
+    Schema::new("meta.v1.RootPaths", &[
+        Field::new(1, "paths", String).repeated(),
+    ]),
+    Schema::new("meta.v1.ServerAddressByClientCIDR", &[
+        Field::new(1, "clientCIDR", String),
+        Field::new(2, "serverAddress", String),
+    ]),
+    Schema::new("meta.v1.Status", &[
+        Field::new(1, "metadata", Message("meta.v1.ListMeta")),
+        Field::new(2, "status", String).omit_empty(),
+        Field::new(3, "message", String).omit_empty(),
+        Field::new(4, "reason", String).omit_empty(),
+        Field::new(5, "details", Message("meta.v1.StatusDetails")),
+        Field::new(6, "code", Int32),
+    ]),
+    Schema::new("meta.v1.StatusCause", &[
+        Field::new(1, "reason", String).omit_empty(),
+        Field::new(2, "message", String).omit_empty(),
+        Field::new(3, "field", String).omit_empty(),
+    ]),
+    Schema::new("meta.v1.StatusDetails", &[
+        Field::new(1, "name", String).omit_empty(),
+        Field::new(2, "group", String).omit_empty(),
+        Field::new(3, "kind", String).omit_empty(),
+        Field::new(4, "causes", Message("meta.v1.StatusCause")).repeated(),
+        Field::new(5, "retryAfterSeconds", Int32),
+        Field::new(6, "uid", String).omit_empty(),
+    ]),
+    Schema::new("meta.v1.TableOptions", &[
+        Field::new(1, "includeObject", String).omit_empty(),
+    ]),
+    Schema::new("meta.v1.Time", &[
+        Field::new(1, "seconds", Int64),
+        Field::new(2, "nanos", Int32),
+    ]),
+    Schema::new("meta.v1.Timestamp", &[
+        Field::new(1, "seconds", Int64),
+        Field::new(2, "nanos", Int32),
+    ]),
+    Schema::new("meta.v1.TypeMeta", &[
+        Field::new(1, "kind", String).omit_empty(),
+        Field::new(2, "apiVersion", String).omit_empty(),
+    ]),
+    Schema::new("meta.v1.UpdateOptions", &[
+        Field::new(1, "dryRun", String).repeated(),
+        Field::new(2, "fieldManager", String).omit_empty(),
+        Field::new(3, "fieldValidation", String).omit_empty(),
+    ]),
+    Schema::new("meta.v1.Verbs", &[
+        Field::new(1, "items", String).repeated(),
+    ]),
+    Schema::new("meta.v1.WatchEvent", &[
+        Field::new(1, "type", String).omit_empty(),
+        Field::new(2, "object", Message("runtime.RawExtension")),
+    ]),
+    Schema::new("resource.Quantity", &[
+        Field::new(1, "string", String).omit_empty(),
+    ]),
+    Schema::new("resource.QuantityValue", &[
+        Field::new(1, "string", String).omit_empty(),
+    ]),
+    Schema::new("runtime.RawExtension", &[
+        Field::new(1, "raw", Bytes),
+    ]),
+    Schema::new("runtime.TypeMeta", &[
+        Field::new(1, "apiVersion", String).omit_empty(),
+        Field::new(2, "kind", String).omit_empty(),
+    ]),
+    Schema::new("runtime.Unknown", &[
+        Field::new(1, "typeMeta", Message("runtime.TypeMeta")),
+        Field::new(2, "raw", Bytes),
+        Field::new(3, "contentEncoding", String).omit_empty(),
+        Field::new(4, "contentType", String).omit_empty(),
+    ]),
+];
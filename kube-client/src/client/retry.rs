@@ -30,9 +30,13 @@
 //! # }
 //! ```
 
-use std::time::{Duration, Instant};
+use std::{
+    error::Error as StdError,
+    io::ErrorKind,
+    time::{Duration, Instant},
+};
 
-use http::{Request, Response, StatusCode};
+use http::{Method, Request, Response, StatusCode};
 use tower::{
     BoxError,
     retry::{
@@ -47,6 +51,31 @@ use super::Body;
 /// Backoff configuration validation error.
 pub use tower::retry::backoff::InvalidBackoff;
 
+/// Header set by API Priority and Fairness on the responses it handled
+const APF_PRIORITY_LEVEL_HEADER: &str = "X-Kubernetes-PF-PriorityLevel-UID";
+
+/// Retry metadata added to the extensions of responses that went through a [`RetryPolicy`]
+///
+/// # Example
+///
+/// ```no_run
+/// # async fn example(client: kube::Client) -> Result<(), kube::Error> {
+/// use kube::client::{Body, retry::RetryMetadata};
+/// let req = http::Request::get("/version").body(Body::empty()).unwrap();
+/// let res = client.send(req).await?;
+/// if let Some(retry) = res.extensions().get::<RetryMetadata>() {
+///     println!("took {} attempts", retry.attempts);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct RetryMetadata {
+    /// Number of times the request was sent, including the first attempt
+    pub attempts: u32,
+}
+
 /// A retry policy for Kubernetes API requests.
 ///
 /// This policy retries requests that fail with:
@@ -54,6 +83,9 @@ pub use tower::retry::backoff::InvalidBackoff;
 /// - 503 Service Unavailable
 /// - 504 Gateway Timeout
 ///
+/// Safe requests (`GET`, and `DELETE` with preconditions) are also retried on transport errors,
+/// such as refused or reset connections during an apiserver rollout, and timeouts.
+///
 /// Uses exponential backoff starting from `min_delay` up to `max_delay`,
 /// with a configurable maximum number of retries.
 #[derive(Clone)]
@@ -117,6 +149,91 @@ impl RetryPolicy {
             StatusCode::TOO_MANY_REQUESTS | StatusCode::SERVICE_UNAVAILABLE | StatusCode::GATEWAY_TIMEOUT
         )
     }
+
+    /// Check if the error is a transport error, where the request may not have reached the apiserver.
+    fn is_retryable_error(err: &(dyn StdError + 'static)) -> bool {
+        let mut source = Some(err);
+        while let Some(err) = source {
+            if let Some(err) = err.downcast_ref::<hyper_util::client::legacy::Error>()
+                && err.is_connect()
+            {
+                return true;
+            }
+            if let Some(err) = err.downcast_ref::<hyper::Error>()
+                && (err.is_incomplete_message() || err.is_canceled() || err.is_timeout())
+            {
+                return true;
+            }
+            if let Some(err) = err.downcast_ref::<std::io::Error>() {
+                return matches!(
+                    err.kind(),
+                    ErrorKind::ConnectionRefused
+                        | ErrorKind::ConnectionReset
+                        | ErrorKind::ConnectionAborted
+                        | ErrorKind::NotConnected
+                        | ErrorKind::BrokenPipe
+                        | ErrorKind::TimedOut
+                        | ErrorKind::UnexpectedEof
+                );
+            }
+            source = err.source();
+        }
+        false
+    }
+
+    /// Check if the request can be sent again without changing its outcome.
+    ///
+    /// A `DELETE` is only safe with preconditions, which stop it from deleting a recreated object.
+    fn is_safe_request(req: &Request<Body>) -> bool {
+        match *req.method() {
+            Method::GET | Method::HEAD => true,
+            Method::DELETE => req
+                .body()
+                .bytes()
+                .and_then(|body| serde_json::from_slice::<serde_json::Value>(body).ok())
+                .and_then(|body| body.get("preconditions").and_then(serde_json::Value::as_object).cloned())
+                .is_some_and(|preconditions| preconditions.values().any(|value| !value.is_null())),
+            _ => false,
+        }
+    }
+
+    /// Backoff before running an operation again after it failed with `err`, if it should be retried
+    ///
+    /// Transport errors, 503 and 504 are only retried for `idempotent` operations, since the apiserver may have
+    /// handled them. Other operations are only retried when throttled with 429, which is returned before handling.
+    pub(crate) fn retry_error(&mut self, err: &crate::Error, idempotent: bool) -> Option<tokio::time::Sleep> {
+        let retryable = match err {
+            crate::Error::Api(status) => StatusCode::from_u16(status.code).is_ok_and(|code| {
+                if idempotent {
+                    Self::is_retryable_status(code)
+                } else {
+                    code == StatusCode::TOO_MANY_REQUESTS
+                }
+            }),
+            crate::Error::HyperError(err) => idempotent && Self::is_retryable_error(err),
+            crate::Error::Service(err) => idempotent && Self::is_retryable_error(&**err),
+            _ => false,
+        };
+        if !retryable || self.current_attempt >= self.max_retries {
+            return None;
+        }
+        self.current_attempt += 1;
+        Some(self.backoff.next_backoff())
+    }
+
+    /// Delay requested by the apiserver through `Retry-After`
+    ///
+    /// Only honoured by server-aware policies, or when the request was rejected by API Priority and Fairness.
+    fn server_delay<Res>(&self, response: &Response<Res>) -> Option<Duration> {
+        let apf_rejected = response.status() == StatusCode::TOO_MANY_REQUESTS
+            && response.headers().contains_key(APF_PRIORITY_LEVEL_HEADER);
+        if !self.server_aware && !apf_rejected {
+            return None;
+        }
+        let retry_after = response.headers().get(http::header::RETRY_AFTER)?;
+        let seconds = retry_after.to_str().ok()?.parse::<u64>().ok()?;
+        Some(Duration::from_secs(seconds))
+    }
 }
 
 impl Default for RetryPolicy {
@@ -137,7 +254,7 @@ impl<Res> Policy<Request<Body>, Response<Res>, BoxError> for RetryPolicy {
 
     fn retry(
         &mut self,
-        _req: &mut Request<Body>,
+        req: &mut Request<Body>,
         result: &mut Result<Response<Res>, BoxError>,
     ) -> Option<Self::Future> {
         match result {
@@ -148,12 +265,7 @@ impl<Res> Policy<Request<Body>, Response<Res>, BoxError> for RetryPolicy {
                 self.current_attempt += 1;
                 // Tick the backoff retry anyways
                 let backoff = self.backoff.next_backoff();
-                if self.server_aware
-                    && let Some(retry_after) = response.headers().get("Retry-After")
-                    && let Some(retry_after) = retry_after.to_str().ok()
-                    && let Some(retry_after) = retry_after.parse::<u64>().ok()
-                {
-                    let server_delay = Duration::from_secs(retry_after);
+                if let Some(server_delay) = self.server_delay(response) {
                     let retry_after = Instant::now() + server_delay;
                     if backoff.deadline().le(&retry_after.into()) {
                         return Some(tokio::time::sleep(server_delay));
@@ -161,7 +273,27 @@ impl<Res> Policy<Request<Body>, Response<Res>, BoxError> for RetryPolicy {
                 }
                 Some(backoff)
             }
-            _ => None,
+            Ok(response) => {
+                response.extensions_mut().insert(RetryMetadata {
+                    attempts: self.current_attempt + 1,
+                });
+                None
+            }
+            Err(err)
+                if Self::is_retryable_error(&**err)
+                    && Self::is_safe_request(req)
+                    && self.current_attempt < self.max_retries =>
+            {
+                self.current_attempt += 1;
+                tracing::debug!(
+                    "retrying {} {} after transport error (attempt {}): {err}",
+                    req.method(),
+                    req.uri(),
+                    self.current_attempt
+                );
+                Some(self.backoff.next_backoff())
+            }
+            Err(_) => None,
         }
     }
 
@@ -223,4 +355,92 @@ mod tests {
         assert!(policy.server_aware);
         assert_eq!(policy.max_retries, 15);
     }
+
+    #[test]
+    fn test_retryable_error() {
+        let refused: BoxError = Box::new(std::io::Error::from(ErrorKind::ConnectionRefused));
+        assert!(RetryPolicy::is_retryable_error(&*refused));
+        let wrapped: BoxError = Box::new(crate::Error::Service(Box::new(std::io::Error::from(
+            ErrorKind::ConnectionReset,
+        ))));
+        assert!(RetryPolicy::is_retryable_error(&*wrapped));
+
+        let denied: BoxError = Box::new(std::io::Error::from(ErrorKind::PermissionDenied));
+        assert!(!RetryPolicy::is_retryable_error(&*denied));
+        let other: BoxError = "invalid bearer token".into();
+        assert!(!RetryPolicy::is_retryable_error(&*other));
+    }
+
+    #[test]
+    fn test_safe_request() {
+        use kube_core::params::{DeleteParams, PostParams, Preconditions};
+
+        let request = kube_core::Request::new("/api/v1/namespaces/ns/pods");
+        let safe = |req: http::Request<Vec<u8>>| RetryPolicy::is_safe_request(&req.map(Body::from));
+
+        assert!(safe(request.get("a", &Default::default()).unwrap()));
+        assert!(safe(request.list(&Default::default()).unwrap()));
+        assert!(!safe(request.create(&PostParams::default(), vec![]).unwrap()));
+        assert!(!safe(request.delete("a", &DeleteParams::default()).unwrap()));
+        let dp = DeleteParams {
+            preconditions: Some(Preconditions {
+                uid: Some("2f4b".into()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        assert!(safe(request.delete("a", &dp).unwrap()));
+    }
+
+    async fn send_after_reset(req: Request<Body>) -> Result<Response<Body>, BoxError> {
+        use std::pin::pin;
+        use tower::{Layer, ServiceExt, retry::RetryLayer};
+        use tower_test::mock;
+
+        let policy = RetryPolicy::new(Duration::from_millis(1), Duration::from_millis(1), 3, false).unwrap();
+        let (svc, handle) = mock::pair::<Request<Body>, Response<Body>>();
+        let spawned = tokio::spawn(async move {
+            let mut handle = pin!(handle);
+            let (_, send) = handle.next_request().await.expect("service not called");
+            send.send_error(std::io::Error::from(ErrorKind::ConnectionReset));
+            if let Some((_, send)) = handle.next_request().await {
+                send.send_response(Response::new(Body::empty()));
+            }
+        });
+        let res = RetryLayer::new(policy).layer(svc).oneshot(req).await;
+        spawned.await.unwrap();
+        res
+    }
+
+    #[tokio::test]
+    async fn test_retries_safe_request_on_transport_error() {
+        let req = Request::get("/api/v1/pods").body(Body::empty()).unwrap();
+        let res = send_after_reset(req).await.unwrap();
+        assert_eq!(
+            res.extensions().get::<RetryMetadata>(),
+            Some(&RetryMetadata { attempts: 2 })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_does_not_retry_unsafe_request_on_transport_error() {
+        let req = Request::post("/api/v1/namespaces/ns/pods").body(Body::empty()).unwrap();
+        assert!(send_after_reset(req).await.is_err());
+    }
+
+    #[test]
+    fn test_honours_apf_retry_after() {
+        let policy = RetryPolicy::default();
+        let response = |apf: bool| {
+            let mut res = Response::builder()
+                .status(StatusCode::TOO_MANY_REQUESTS)
+                .header(http::header::RETRY_AFTER, "2");
+            if apf {
+                res = res.header(APF_PRIORITY_LEVEL_HEADER, "7c1f");
+            }
+            res.body(()).unwrap()
+        };
+        assert_eq!(policy.server_delay(&response(false)), None);
+        assert_eq!(policy.server_delay(&response(true)), Some(Duration::from_secs(2)));
+    }
 }
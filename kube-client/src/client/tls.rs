@@ -435,6 +435,10 @@ pub mod openssl_tls {
         /// Failed to add a root certificate
         #[error("failed to add a root certificate: {0}")]
         AddRootCertificate(#[source] openssl::error::ErrorStack),
+
+        /// Failed to set the ALPN protocols
+        #[error("failed to set the ALPN protocols: {0}")]
+        SetAlpnProtocols(#[source] openssl::error::ErrorStack),
     }
 
     /// Create `openssl::ssl::SslConnectorBuilder` required for `hyper_openssl::HttpsConnector`.
@@ -476,4 +480,14 @@ pub mod openssl_tls {
 
         Ok(builder)
     }
+
+    /// Offer `h2` ahead of `http/1.1` through ALPN.
+    ///
+    /// `hyper_openssl::HttpsConnector` speaks HTTP/2 on connections where the server selected `h2`.
+    #[cfg(feature = "http2")]
+    pub fn offer_http2(builder: &mut SslConnectorBuilder) -> Result<(), SslConnectorError> {
+        builder
+            .set_alpn_protos(b"\x02h2\x08http/1.1")
+            .map_err(SslConnectorError::SetAlpnProtocols)
+    }
 }
@@ -0,0 +1,296 @@
+//! Warnings returned by the apiserver
+//!
+//! The apiserver adds [RFC 7234] `Warning` headers to responses for requests that use deprecated APIs,
+//! set unknown fields with `fieldValidation=Warn`, or were warned about by an admission webhook.
+//!
+//! The [`Client`](crate::Client) passes every warning to its [`WarningHandler`], which by default
+//! logs each distinct warning once, like `client-go`. Use [`Client::with_warning_handler`](crate::Client::with_warning_handler)
+//! to collect them instead, or [`Api::create_with_warnings`](crate::Api::create_with_warnings) and
+//! [`Api::patch_with_warnings`](crate::Api::patch_with_warnings) to get the warnings of a single request.
+//!
+//! ```no_run
+//! # async fn wrapper() -> Result<(), Box<dyn std::error::Error>> {
+//! use kube::client::warning::CollectWarnings;
+//!
+//! let warnings = CollectWarnings::default();
+//! let client = kube::Client::try_default().await?.with_warning_handler(warnings.clone());
+//! // ... use the client
+//! for warning in warnings.take() {
+//!     eprintln!("apiserver warned: {}", warning.text);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [RFC 7234]: https://www.rfc-editor.org/rfc/rfc7234#section-5.5
+use std::{
+    cell::RefCell,
+    collections::HashSet,
+    sync::{Arc, Mutex},
+};
+
+use http::HeaderMap;
+
+/// A warning sent by the apiserver in a `Warning` header
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Warning {
+    /// The warning code, always `299` (miscellaneous persistent warning) for Kubernetes
+    pub code: u16,
+    /// The agent that added the warning, `-` when unknown
+    pub agent: String,
+    /// The warning message
+    pub text: String,
+}
+
+impl Warning {
+    /// Parse all warnings in the `Warning` headers of a response
+    ///
+    /// Malformed warnings are skipped.
+    pub fn from_headers(headers: &HeaderMap) -> Vec<Self> {
+        headers
+            .get_all(http::header::WARNING)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(parse)
+            .collect()
+    }
+}
+
+/// Parse a header value holding a comma separated list of `warn-code warn-agent "warn-text" ["warn-date"]`
+fn parse(mut value: &str) -> Vec<Warning> {
+    let mut warnings = Vec::new();
+    loop {
+        value = value.trim_start_matches([' ', ',']);
+        if value.is_empty() {
+            break;
+        }
+        let Some(warning) = parse_one(&mut value) else {
+            tracing::debug!("skipping malformed warning header: {value}");
+            break;
+        };
+        warnings.push(warning);
+    }
+    warnings
+}
+
+fn parse_one(value: &mut &str) -> Option<Warning> {
+    let (code, rest) = value.split_once(' ')?;
+    let (agent, rest) = rest.trim_start().split_once(' ')?;
+    let (text, mut rest) = quoted(rest.trim_start())?;
+    // Skip the optional warn-date
+    if rest.trim_start().starts_with('"') {
+        rest = quoted(rest.trim_start())?.1;
+    }
+    *value = rest;
+    Some(Warning {
+        code: code.parse().ok()?,
+        agent: agent.to_owned(),
+        text,
+    })
+}
+
+/// Split a leading quoted-string off `value`, unescaping it
+fn quoted(value: &str) -> Option<(String, &str)> {
+    let value = value.strip_prefix('"')?;
+    let mut text = String::new();
+    let mut chars = value.char_indices();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '\\' => text.push(chars.next()?.1),
+            '"' => return Some((text, &value[i + 1..])),
+            c => text.push(c),
+        }
+    }
+    None
+}
+
+/// Receives the warnings sent by the apiserver
+///
+/// Implemented for closures taking a [`Warning`].
+pub trait WarningHandler: Send + Sync + 'static {
+    /// Handle a warning from a response
+    fn handle(&self, warning: &Warning);
+}
+
+impl<F> WarningHandler for F
+where
+    F: Fn(&Warning) + Send + Sync + 'static,
+{
+    fn handle(&self, warning: &Warning) {
+        self(warning);
+    }
+}
+
+/// Logs every warning at the `WARN` level
+#[derive(Clone, Copy, Debug, Default)]
+pub struct LogWarnings;
+
+impl WarningHandler for LogWarnings {
+    fn handle(&self, warning: &Warning) {
+        tracing::warn!("apiserver warning: {}", warning.text);
+    }
+}
+
+/// Collects warnings until they are [taken](Self::take)
+///
+/// Clones share the collected warnings.
+#[derive(Clone, Debug, Default)]
+pub struct CollectWarnings(Arc<Mutex<Vec<Warning>>>);
+
+impl CollectWarnings {
+    /// Take the warnings collected so far
+    pub fn take(&self) -> Vec<Warning> {
+        std::mem::take(&mut *self.0.lock().unwrap())
+    }
+}
+
+impl WarningHandler for CollectWarnings {
+    fn handle(&self, warning: &Warning) {
+        self.0.lock().unwrap().push(warning.clone());
+    }
+}
+
+/// Passes each distinct warning to the inner handler only once
+#[derive(Debug, Default)]
+pub struct DedupWarnings<H> {
+    inner: H,
+    seen: Mutex<HashSet<Warning>>,
+}
+
+impl<H: WarningHandler> DedupWarnings<H> {
+    /// Deduplicate the warnings passed to `inner`
+    pub fn new(inner: H) -> Self {
+        Self {
+            inner,
+            seen: Mutex::default(),
+        }
+    }
+}
+
+impl<H: WarningHandler> WarningHandler for DedupWarnings<H> {
+    fn handle(&self, warning: &Warning) {
+        if self.seen.lock().unwrap().insert(warning.clone()) {
+            self.inner.handle(warning);
+        }
+    }
+}
+
+/// A response along with the warnings the apiserver sent for it
+#[derive(Clone, Debug)]
+pub struct WithWarnings<T> {
+    /// The response
+    pub value: T,
+    /// The warnings sent with the response
+    pub warnings: Vec<Warning>,
+}
+
+tokio::task_local! {
+    static CAPTURED: RefCell<Vec<Warning>>;
+}
+
+/// Run `fut`, capturing the warnings of the requests it sends
+pub(crate) async fn capture<F: Future>(fut: F) -> (F::Output, Vec<Warning>) {
+    CAPTURED
+        .scope(RefCell::default(), async {
+            let output = fut.await;
+            (output, CAPTURED.with(RefCell::take))
+        })
+        .await
+}
+
+/// Record a warning for an enclosing [`capture`]
+pub(crate) fn record(warning: &Warning) {
+    let _ = CAPTURED.try_with(|captured| captured.borrow_mut().push(warning.clone()));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::pin::pin;
+
+    use http::{HeaderValue, Request, Response};
+    use k8s_openapi::api::core::v1::ConfigMap;
+    use kube_core::params::{Patch, PatchParams, PostParams};
+    use tower_test::mock;
+
+    use crate::{Api, Client, client::Body};
+
+    fn warning(text: &str) -> Warning {
+        Warning {
+            code: 299,
+            agent: "-".into(),
+            text: text.into(),
+        }
+    }
+
+    #[test]
+    fn parses_warning_headers() {
+        let mut headers = HeaderMap::new();
+        headers.append(
+            http::header::WARNING,
+            HeaderValue::from_static(r#"299 - "unknown field \"spec.foo\"", 299 - "a, b""#),
+        );
+        headers.append(
+            http::header::WARNING,
+            HeaderValue::from_static(r#"299 apiserver "dated" "Sat, 25 Aug 2012 23:34:45 GMT""#),
+        );
+        headers.append(http::header::WARNING, HeaderValue::from_static("malformed"));
+        assert_eq!(Warning::from_headers(&headers), [
+            warning(r#"unknown field "spec.foo""#),
+            warning("a, b"),
+            Warning {
+                agent: "apiserver".into(),
+                ..warning("dated")
+            },
+        ]);
+    }
+
+    #[test]
+    fn dedup_passes_each_warning_once() {
+        let collected = CollectWarnings::default();
+        let dedup = DedupWarnings::new(collected.clone());
+        dedup.handle(&warning("a"));
+        dedup.handle(&warning("b"));
+        dedup.handle(&warning("a"));
+        assert_eq!(collected.take(), [warning("a"), warning("b")]);
+        assert!(collected.take().is_empty());
+    }
+
+    #[tokio::test]
+    async fn delivers_warnings_to_handler_and_caller() {
+        let (mock_service, handle) = mock::pair::<Request<Body>, Response<Body>>();
+        let spawned = tokio::spawn(async move {
+            let mut handle = pin!(handle);
+            for text in ["deprecated", "unknown field"] {
+                let (_, send) = handle.next_request().await.expect("service not called");
+                let body = serde_json::json!({
+                    "apiVersion": "v1", "kind": "ConfigMap", "metadata": { "name": "a" }
+                });
+                send.send_response(
+                    Response::builder()
+                        .header(http::header::WARNING, format!("299 - \"{text}\""))
+                        .body(Body::from(serde_json::to_vec(&body).unwrap()))
+                        .unwrap(),
+                );
+            }
+        });
+
+        let collected = CollectWarnings::default();
+        let client = Client::new(mock_service, "default").with_warning_handler(collected.clone());
+        let api: Api<ConfigMap> = Api::default_namespaced(client);
+        let cm = ConfigMap::default();
+        let created = api.create_with_warnings(&PostParams::default(), &cm).await.unwrap();
+        assert_eq!(created.warnings, [warning("deprecated")]);
+        let patch = Patch::Merge(serde_json::json!({}));
+        let patched = api
+            .patch_with_warnings("a", &PatchParams::default(), &patch)
+            .await
+            .unwrap();
+        assert_eq!(patched.value.metadata.name.as_deref(), Some("a"));
+        assert_eq!(patched.warnings, [warning("unknown field")]);
+
+        assert_eq!(collected.take(), [warning("deprecated"), warning("unknown field")]);
+        spawned.await.unwrap();
+    }
+}
@@ -173,6 +173,22 @@ pub struct Config {
     pub headers: Vec<(HeaderName, HeaderValue)>,
     /// Whether to enable default retrying requests on transient failures (429, 503, 504).
     pub default_retry: bool,
+    /// Whether to prefer the CBOR wire format (would only have an effect when the `cbor` feature is enabled)
+    ///
+    /// Defaults to the `KUBE_FEATURE_ClientsPreferCBOR` environment variable, like `client-go`.
+    pub prefer_cbor: bool,
+    /// Whether to negotiate HTTP/2 through ALPN (would only have an effect when the `http2` feature is enabled)
+    ///
+    /// All requests and watches then share a single connection to the apiserver, which is kept alive with pings.
+    /// A [`read_timeout`](Self::read_timeout) applies to that whole connection, so should be left unset.
+    pub http2: bool,
+    /// Average number of requests per second to allow to the apiserver, like `QPS` in `client-go`
+    ///
+    /// Watches are not limited. Defaults to `None`, which disables client-side rate limiting.
+    /// See [`RateLimitLayer`](crate::client::middleware::RateLimitLayer) to also limit individual verbs.
+    pub qps: Option<f32>,
+    /// Number of requests that can be made in a burst above [`qps`](Self::qps), like `Burst` in `client-go`
+    pub burst: u32,
 }
 
 impl Config {
@@ -197,6 +213,10 @@ impl Config {
             tls_server_name: None,
             headers: Vec::new(),
             default_retry: true,
+            prefer_cbor: prefer_cbor_from_env(),
+            http2: false,
+            qps: None,
+            burst: DEFAULT_BURST,
         }
     }
 
@@ -282,6 +302,10 @@ impl Config {
             tls_server_name: None,
             headers: Vec::new(),
             default_retry: true,
+            prefer_cbor: prefer_cbor_from_env(),
+            http2: false,
+            qps: None,
+            burst: DEFAULT_BURST,
         })
     }
 
@@ -345,6 +369,10 @@ impl Config {
             tls_server_name: loader.cluster.tls_server_name,
             headers: Vec::new(),
             default_retry: true,
+            prefer_cbor: prefer_cbor_from_env(),
+            http2: false,
+            qps: None,
+            burst: DEFAULT_BURST,
         })
     }
 
@@ -405,6 +433,11 @@ pub(crate) fn certs(data: &[u8]) -> Result<Vec<Vec<u8>>, pem::PemError> {
         .collect::<Vec<_>>())
 }
 
+/// Whether `KUBE_FEATURE_ClientsPreferCBOR` enables CBOR
+fn prefer_cbor_from_env() -> bool {
+    std::env::var("KUBE_FEATURE_ClientsPreferCBOR").is_ok_and(|value| value.eq_ignore_ascii_case("true"))
+}
+
 impl TryFrom<Kubeconfig> for Config {
     type Error = KubeconfigError;
 
@@ -417,6 +450,8 @@ impl TryFrom<Kubeconfig> for Config {
 // https://github.com/kube-rs/kube/issues/146#issuecomment-590924397
 const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(30);
 const DEFAULT_WRITE_TIMEOUT: Duration = Duration::from_secs(295);
+// Matches client-go
+const DEFAULT_BURST: u32 = 10;
 
 // Expose raw config structs
 pub use file_config::{
@@ -64,6 +64,18 @@ pub enum Error {
     #[error("Error deserializing response: {0}")]
     SerdeError(#[source] serde_json::Error),
 
+    /// Error decoding a protobuf response
+    #[cfg(feature = "protobuf")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "protobuf")))]
+    #[error("Error decoding protobuf response: {0}")]
+    ProtobufDecode(#[source] crate::client::protobuf::DecodeError),
+
+    /// Error decoding a CBOR response
+    #[cfg(feature = "cbor")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "cbor")))]
+    #[error("Error decoding cbor response: {0}")]
+    CborDecode(#[source] ciborium::de::Error<std::io::Error>),
+
     /// Failed to build request
     #[error("Failed to build request: {0}")]
     BuildRequest(#[source] kube_core::request::Error),
@@ -41,6 +41,8 @@ pub mod labels;
 
 #[cfg(feature = "kubelet-debug")] pub mod kubelet_debug;
 
+pub mod managed_fields;
+
 pub mod object;
 pub use object::{NotUsed, Object, ObjectList};
 
@@ -64,6 +66,8 @@ pub use labels::{Expression, ParseExpressionError, Selector, SelectorExt};
 #[cfg(feature = "schema")]
 pub mod schema;
 
+pub mod strategic_merge;
+
 pub mod subresource;
 
 pub mod util;
@@ -0,0 +1,728 @@
+//! Decoding of managed fields and field ownership queries
+//!
+//! Server-side apply records which field manager owns which fields of an object in
+//! [`ObjectMeta::managed_fields`](crate::ObjectMeta::managed_fields), as `FieldsV1` sets.
+//! [`FieldSet`] decodes them, and [`ManagedFieldsExt`] answers ownership questions about an object:
+//!
+//! ```
+//! use k8s_openapi::api::apps::v1::Deployment;
+//! use kube_core::managed_fields::{FieldPath, ManagedFieldsExt};
+//!
+//! let deployment: Deployment = serde_json::from_value(serde_json::json!({
+//!     "metadata": {
+//!         "name": "web",
+//!         "managedFields": [{
+//!             "manager": "hpa",
+//!             "operation": "Update",
+//!             "fieldsType": "FieldsV1",
+//!             "fieldsV1": { "f:spec": { "f:replicas": {} } }
+//!         }]
+//!     },
+//!     "spec": { "replicas": 3, "selector": {}, "template": {} }
+//! }))?;
+//!
+//! let replicas: FieldPath = ".spec.replicas".parse()?;
+//! let owners = deployment.managers_of(&replicas);
+//! assert_eq!(owners[0].manager.as_deref(), Some("hpa"));
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+use std::{collections::BTreeMap, fmt, str::FromStr};
+
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::{FieldsV1, ManagedFieldsEntry};
+use serde::Serialize;
+use serde_json::{Map, Value};
+use thiserror::Error;
+
+use crate::{Resource, ResourceExt};
+
+/// Failed to parse a `FieldsV1` set or a [`FieldPath`]
+#[derive(Debug, Error)]
+#[error("failed to parse field path: {0}")]
+pub struct ParseFieldsError(pub String);
+
+/// A step in a [`FieldPath`]
+#[derive(Clone, Debug, PartialEq)]
+pub enum PathElement {
+    /// A field of an object, `f:<name>` in `FieldsV1`
+    Field(String),
+    /// An item of an associative list identified by its key fields, `k:<json>` in `FieldsV1`
+    Key(BTreeMap<String, Value>),
+    /// An item of a set identified by its value, `v:<json>` in `FieldsV1`
+    Value(Value),
+    /// An item of an atomic list identified by its position, `i:<index>` in `FieldsV1`
+    Index(usize),
+}
+
+impl PathElement {
+    fn from_key(key: &str) -> Result<Self, ParseFieldsError> {
+        let invalid = || ParseFieldsError(format!("invalid FieldsV1 key {key:?}"));
+        match key.split_once(':').ok_or_else(invalid)? {
+            ("f", name) => Ok(Self::Field(name.to_owned())),
+            ("k", keys) => serde_json::from_str(keys).map(Self::Key).map_err(|_| invalid()),
+            ("v", value) => serde_json::from_str(value)
+                .map(Self::Value)
+                .map_err(|_| invalid()),
+            ("i", index) => index.parse().map(Self::Index).map_err(|_| invalid()),
+            _ => Err(invalid()),
+        }
+    }
+
+    /// The `FieldsV1` key of the element, with the key fields of list items in a canonical order
+    fn to_key(&self) -> String {
+        match self {
+            Self::Field(name) => format!("f:{name}"),
+            Self::Key(keys) => format!("k:{}", Value::from_iter(keys.clone())),
+            Self::Value(value) => format!("v:{value}"),
+            Self::Index(index) => format!("i:{index}"),
+        }
+    }
+
+    /// Whether `item` is the list item this element identifies
+    fn matches_item(&self, position: usize, item: &Value) -> bool {
+        match self {
+            Self::Field(_) => false,
+            Self::Key(keys) => keys.iter().all(|(key, value)| item.get(key) == Some(value)),
+            Self::Value(value) => item == value,
+            Self::Index(index) => *index == position,
+        }
+    }
+}
+
+impl fmt::Display for PathElement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Field(name) if name.contains(['.', '[', '"']) => {
+                write!(f, ".{}", Value::from(name.as_str()))
+            }
+            Self::Field(name) => write!(f, ".{name}"),
+            Self::Key(keys) => {
+                let keys = keys.iter().map(|(key, value)| format!("{key}={value}"));
+                write!(f, "[{}]", keys.collect::<Vec<_>>().join(","))
+            }
+            Self::Value(value) => write!(f, "[={value}]"),
+            Self::Index(index) => write!(f, "[{index}]"),
+        }
+    }
+}
+
+/// A path to a field, like `.spec.containers[name="app"].image`
+///
+/// Parsed from and displayed as a sequence of:
+/// - `.name` or `."name.with.dots"` for the fields of an object
+/// - `[key=value,...]` for the items of an associative list, with JSON values (strings may be unquoted)
+/// - `[=value]` for the items of a set
+/// - `[index]` for the items of an atomic list
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FieldPath(pub Vec<PathElement>);
+
+impl fmt::Display for FieldPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.iter().try_for_each(|element| element.fmt(f))
+    }
+}
+
+impl FromStr for FieldPath {
+    type Err = ParseFieldsError;
+
+    fn from_str(path: &str) -> Result<Self, Self::Err> {
+        let invalid = || ParseFieldsError(format!("invalid field path {path:?}"));
+        let mut elements = Vec::new();
+        let mut rest = path;
+        while !rest.is_empty() {
+            if let Some(field) = rest.strip_prefix('.') {
+                if field.starts_with('"') {
+                    let end = closing_quote(field).ok_or_else(invalid)?;
+                    let name = serde_json::from_str(&field[..=end]).map_err(|_| invalid())?;
+                    elements.push(PathElement::Field(name));
+                    rest = &field[end + 1..];
+                } else {
+                    let end = field.find(['.', '[']).unwrap_or(field.len());
+                    if end == 0 {
+                        return Err(invalid());
+                    }
+                    elements.push(PathElement::Field(field[..end].to_owned()));
+                    rest = &field[end..];
+                }
+            } else if let Some(selector) = rest.strip_prefix('[') {
+                let end = split_unquoted(selector, ']')
+                    .next()
+                    .map(str::len)
+                    .ok_or_else(invalid)?;
+                if end == selector.len() {
+                    return Err(invalid());
+                }
+                elements.push(parse_selector(&selector[..end]).ok_or_else(invalid)?);
+                rest = &selector[end + 1..];
+            } else {
+                return Err(invalid());
+            }
+        }
+        Ok(Self(elements))
+    }
+}
+
+/// Parse the inside of a `[...]` path element
+fn parse_selector(selector: &str) -> Option<PathElement> {
+    if let Ok(index) = selector.parse() {
+        return Some(PathElement::Index(index));
+    }
+    if let Some(value) = selector.strip_prefix('=') {
+        return Some(PathElement::Value(parse_value(value)));
+    }
+    let keys = split_unquoted(selector, ',')
+        .map(|pair| {
+            let (key, value) = pair.split_once('=')?;
+            Some((key.trim().to_owned(), parse_value(value)))
+        })
+        .collect::<Option<BTreeMap<_, _>>>()?;
+    Some(PathElement::Key(keys))
+}
+
+fn parse_value(value: &str) -> Value {
+    let value = value.trim();
+    serde_json::from_str(value).unwrap_or_else(|_| Value::from(value))
+}
+
+/// Index of the quote closing the JSON string that `quoted` starts with
+fn closing_quote(quoted: &str) -> Option<usize> {
+    let mut escaped = false;
+    quoted.char_indices().skip(1).find_map(|(i, c)| {
+        match c {
+            '\\' if !escaped => escaped = true,
+            '"' if !escaped => return Some(i),
+            _ => escaped = false,
+        }
+        None
+    })
+}
+
+/// Split `s` at every `separator` outside of JSON strings
+fn split_unquoted(s: &str, separator: char) -> impl Iterator<Item = &str> {
+    let mut rest = Some(s);
+    std::iter::from_fn(move || {
+        let s = rest?;
+        let (mut quoted, mut escaped) = (false, false);
+        for (i, c) in s.char_indices() {
+            match c {
+                '\\' if quoted && !escaped => {
+                    escaped = true;
+                    continue;
+                }
+                '"' if !escaped => quoted = !quoted,
+                c if c == separator && !quoted => {
+                    rest = Some(&s[i + c.len_utf8()..]);
+                    return Some(&s[..i]);
+                }
+                _ => {}
+            }
+            escaped = false;
+        }
+        rest = None;
+        Some(s)
+    })
+}
+
+/// A set of field paths, decoded from `FieldsV1`
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FieldSet {
+    /// Whether the path leading to this node is in the set
+    member: bool,
+    /// Child nodes by their canonical `FieldsV1` key
+    children: BTreeMap<String, FieldSet>,
+}
+
+impl FieldSet {
+    /// Decode a `FieldsV1` set
+    pub fn from_fields_v1(fields: &FieldsV1) -> Result<Self, ParseFieldsError> {
+        let mut set = Self::from_json(&fields.0)?;
+        set.member = false;
+        Ok(set)
+    }
+
+    fn from_json(value: &Value) -> Result<Self, ParseFieldsError> {
+        let Value::Object(object) = value else {
+            return Err(ParseFieldsError(format!(
+                "FieldsV1 node is not an object: {value}"
+            )));
+        };
+        let mut set = Self {
+            member: object.is_empty(),
+            children: BTreeMap::new(),
+        };
+        for (key, child) in object {
+            if key == "." {
+                set.member = true;
+            } else {
+                let key = PathElement::from_key(key)?.to_key();
+                set.children.insert(key, Self::from_json(child)?);
+            }
+        }
+        Ok(set)
+    }
+
+    /// Encode the set as `FieldsV1`
+    pub fn to_fields_v1(&self) -> FieldsV1 {
+        FieldsV1(self.to_json())
+    }
+
+    fn to_json(&self) -> Value {
+        let mut object: Map<String, Value> = self
+            .children
+            .iter()
+            .map(|(key, child)| (key.clone(), child.to_json()))
+            .collect();
+        if self.member && !object.is_empty() {
+            object.insert(".".into(), Value::Object(Map::new()));
+        }
+        Value::Object(object)
+    }
+
+    /// The set of the leaf fields of `value`, treating lists as atomic
+    ///
+    /// The apiserver tracks the items of associative lists and sets individually, which requires the schema of the
+    /// object. Insert the [`FieldPath`]s of list items to build such a set.
+    pub fn from_value(value: &Value) -> Self {
+        let mut set = Self::default();
+        set.insert_value(value);
+        set.member = false;
+        set
+    }
+
+    fn insert_value(&mut self, value: &Value) {
+        match value {
+            Value::Object(object) if !object.is_empty() => {
+                for (name, value) in object {
+                    self.child(&PathElement::Field(name.clone())).insert_value(value);
+                }
+            }
+            _ => self.member = true,
+        }
+    }
+
+    fn child(&mut self, element: &PathElement) -> &mut Self {
+        self.children.entry(element.to_key()).or_default()
+    }
+
+    fn get(&self, path: &FieldPath) -> Option<&Self> {
+        path.0
+            .iter()
+            .try_fold(self, |set, element| set.children.get(&element.to_key()))
+    }
+
+    /// Add a path to the set
+    pub fn insert(&mut self, path: &FieldPath) {
+        path.0.iter().fold(self, |set, element| set.child(element)).member = true;
+    }
+
+    /// Whether the set contains `path`
+    pub fn contains(&self, path: &FieldPath) -> bool {
+        self.get(path).is_some_and(|set| set.member)
+    }
+
+    /// Whether the set contains `path` or any path below it
+    pub fn contains_prefix(&self, path: &FieldPath) -> bool {
+        self.get(path).is_some_and(|set| !set.is_empty())
+    }
+
+    /// Whether the set contains no paths
+    pub fn is_empty(&self) -> bool {
+        !self.member && self.children.values().all(Self::is_empty)
+    }
+
+    /// All paths in the set
+    pub fn paths(&self) -> Vec<FieldPath> {
+        let mut paths = Vec::new();
+        self.collect_paths(&mut Vec::new(), &mut paths);
+        paths
+    }
+
+    fn collect_paths(&self, prefix: &mut Vec<PathElement>, paths: &mut Vec<FieldPath>) {
+        if self.member {
+            paths.push(FieldPath(prefix.clone()));
+        }
+        for (key, child) in &self.children {
+            prefix.push(PathElement::from_key(key).expect("keys are canonical"));
+            child.collect_paths(prefix, paths);
+            prefix.pop();
+        }
+    }
+
+    /// The paths in either set
+    #[must_use]
+    pub fn union(&self, other: &Self) -> Self {
+        let mut union = self.clone();
+        union.member |= other.member;
+        for (key, child) in &other.children {
+            let merged = match union.children.get(key) {
+                Some(existing) => existing.union(child),
+                None => child.clone(),
+            };
+            union.children.insert(key.clone(), merged);
+        }
+        union
+    }
+
+    /// The paths in both sets
+    #[must_use]
+    pub fn intersection(&self, other: &Self) -> Self {
+        Self {
+            member: self.member && other.member,
+            children: self
+                .children
+                .iter()
+                .filter_map(|(key, child)| {
+                    let common = child.intersection(other.children.get(key)?);
+                    (!common.is_empty()).then(|| (key.clone(), common))
+                })
+                .collect(),
+        }
+    }
+
+    /// The paths in this set but not in `other`
+    #[must_use]
+    pub fn difference(&self, other: &Self) -> Self {
+        Self {
+            member: self.member && !other.member,
+            children: self
+                .children
+                .iter()
+                .filter_map(|(key, child)| {
+                    let remaining = match other.children.get(key) {
+                        Some(other) => child.difference(other),
+                        None => child.clone(),
+                    };
+                    (!remaining.is_empty()).then(|| (key.clone(), remaining))
+                })
+                .collect(),
+        }
+    }
+
+    /// The parts of `value` in the set, or `None` if it has none
+    fn extract(&self, value: &Value) -> Option<Value> {
+        if self.children.is_empty() {
+            return self.member.then(|| value.clone());
+        }
+        let extracted = match value {
+            Value::Object(object) => {
+                let fields = self.children.iter().filter_map(|(key, child)| {
+                    let PathElement::Field(name) = PathElement::from_key(key).ok()? else {
+                        return None;
+                    };
+                    Some((name.clone(), child.extract(object.get(&name)?)?))
+                });
+                Value::Object(fields.collect())
+            }
+            Value::Array(items) => {
+                let elements = self
+                    .children
+                    .iter()
+                    .filter_map(|(key, child)| Some((PathElement::from_key(key).ok()?, child)))
+                    .collect::<Vec<_>>();
+                let items = items.iter().enumerate().filter_map(|(position, item)| {
+                    let (element, child) = elements
+                        .iter()
+                        .find(|(element, _)| element.matches_item(position, item))?;
+                    let mut extracted = child.extract(item).unwrap_or_else(|| Value::Object(Map::new()));
+                    // Items of associative lists are identified by their keys, so these are always kept
+                    if let (PathElement::Key(keys), Value::Object(object)) = (element, &mut extracted) {
+                        object.extend(keys.clone());
+                    }
+                    Some(extracted)
+                });
+                Value::Array(items.collect())
+            }
+            _ => return self.member.then(|| value.clone()),
+        };
+        let is_empty = match &extracted {
+            Value::Object(object) => object.is_empty(),
+            Value::Array(items) => items.is_empty(),
+            _ => false,
+        };
+        (!is_empty || self.member).then_some(extracted)
+    }
+}
+
+/// A field owned by another manager that an apply would take over
+#[derive(Clone, Debug, PartialEq)]
+pub struct Conflict {
+    /// The manager owning the field
+    pub manager: String,
+    /// The conflicting field
+    pub path: FieldPath,
+}
+
+/// Field ownership queries on the managed fields of an object
+pub trait ManagedFieldsExt {
+    /// The managed fields entries owning `path`
+    ///
+    /// Entries with a malformed `fieldsV1` are skipped.
+    fn managers_of(&self, path: &FieldPath) -> Vec<&ManagedFieldsEntry>;
+
+    /// The fields owned by `manager`, through any operation
+    fn fields_owned_by(&self, manager: &str) -> FieldSet;
+
+    /// The fields in `fields` owned by managers other than `manager`
+    ///
+    /// These would conflict with a server-side apply of `fields` by `manager`, unless it is forced.
+    /// The apiserver does not report conflicts for fields that are applied with their current value,
+    /// so this is conservative.
+    fn apply_conflicts(&self, manager: &str, fields: &FieldSet) -> Vec<Conflict>;
+
+    /// The subset of the object applied by `manager`, like `Extract*` in `client-go`
+    ///
+    /// Contains the fields `manager` owns through server-side apply, along with the `apiVersion`, `kind`,
+    /// name and namespace of the object. Applying it unchanged is a no-op, which keeps read-modify-apply loops
+    /// from dropping fields or taking over fields of other managers.
+    fn extract(&self, manager: &str) -> Result<Value, serde_json::Error>
+    where
+        Self: Serialize;
+}
+
+/// The field sets of the entries of the main resource, skipping malformed ones
+fn entry_fields(entry: &ManagedFieldsEntry) -> Option<FieldSet> {
+    if entry
+        .subresource
+        .as_deref()
+        .is_some_and(|subresource| !subresource.is_empty())
+    {
+        return None;
+    }
+    FieldSet::from_fields_v1(entry.fields_v1.as_ref()?).ok()
+}
+
+impl<K: Resource> ManagedFieldsExt for K {
+    fn managers_of(&self, path: &FieldPath) -> Vec<&ManagedFieldsEntry> {
+        self.managed_fields()
+            .iter()
+            .filter(|entry| {
+                entry
+                    .fields_v1
+                    .as_ref()
+                    .and_then(|fields| FieldSet::from_fields_v1(fields).ok())
+                    .is_some_and(|fields| fields.contains(path))
+            })
+            .collect()
+    }
+
+    fn fields_owned_by(&self, manager: &str) -> FieldSet {
+        self.managed_fields()
+            .iter()
+            .filter(|entry| entry.manager.as_deref() == Some(manager))
+            .filter_map(|entry| FieldSet::from_fields_v1(entry.fields_v1.as_ref()?).ok())
+            .fold(FieldSet::default(), |owned, fields| owned.union(&fields))
+    }
+
+    fn apply_conflicts(&self, manager: &str, fields: &FieldSet) -> Vec<Conflict> {
+        self.managed_fields()
+            .iter()
+            .filter(|entry| entry.manager.as_deref() != Some(manager))
+            .filter_map(|entry| Some((entry.manager.clone().unwrap_or_default(), entry_fields(entry)?)))
+            .flat_map(|(other, owned)| {
+                owned
+                    .intersection(fields)
+                    .paths()
+                    .into_iter()
+                    .map(move |path| Conflict {
+                        manager: other.clone(),
+                        path,
+                    })
+            })
+            .collect()
+    }
+
+    fn extract(&self, manager: &str) -> Result<Value, serde_json::Error>
+    where
+        Self: Serialize,
+    {
+        let applied = self
+            .managed_fields()
+            .iter()
+            .filter(|entry| entry.manager.as_deref() == Some(manager))
+            .filter(|entry| entry.operation.as_deref() == Some("Apply"))
+            .filter_map(entry_fields)
+            .fold(FieldSet::default(), |applied, fields| applied.union(&fields));
+
+        let live = serde_json::to_value(self)?;
+        let mut extracted = match applied.extract(&live) {
+            Some(Value::Object(object)) => object,
+            _ => Map::new(),
+        };
+        for field in ["apiVersion", "kind"] {
+            if let Some(value) = live.get(field) {
+                extracted.insert(field.into(), value.clone());
+            }
+        }
+        let metadata = extracted
+            .entry("metadata")
+            .or_insert_with(|| Value::Object(Map::new()));
+        if let Value::Object(metadata) = metadata {
+            metadata.insert("name".into(), self.name_any().into());
+            if let Some(namespace) = self.namespace() {
+                metadata.insert("namespace".into(), namespace.into());
+            }
+        }
+        Ok(Value::Object(extracted))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use k8s_openapi::api::apps::v1::Deployment;
+    use serde_json::json;
+
+    fn deployment() -> Deployment {
+        serde_json::from_value(json!({
+            "apiVersion": "apps/v1",
+            "kind": "Deployment",
+            "metadata": {
+                "name": "web",
+                "namespace": "apps",
+                "managedFields": [
+                    {
+                        "manager": "ci",
+                        "operation": "Apply",
+                        "fieldsType": "FieldsV1",
+                        "fieldsV1": {
+                            "f:metadata": { "f:labels": { "f:app": {} } },
+                            "f:spec": {
+                                "f:selector": {},
+                                "f:template": {
+                                    "f:spec": {
+                                        "f:containers": {
+                                            "k:{\"name\":\"app\"}": { ".": {}, "f:image": {}, "f:name": {} }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    },
+                    {
+                        "manager": "hpa",
+                        "operation": "Update",
+                        "fieldsType": "FieldsV1",
+                        "fieldsV1": { "f:spec": { "f:replicas": {} } }
+                    },
+                    {
+                        "manager": "kubelet",
+                        "operation": "Update",
+                        "subresource": "status",
+                        "fieldsType": "FieldsV1",
+                        "fieldsV1": { "f:status": { "f:replicas": {} } }
+                    }
+                ]
+            },
+            "spec": {
+                "replicas": 3,
+                "selector": { "matchLabels": { "app": "web" } },
+                "template": {
+                    "metadata": { "labels": { "app": "web" } },
+                    "spec": {
+                        "containers": [
+                            { "name": "sidecar", "image": "proxy:1" },
+                            { "name": "app", "image": "web:2", "ports": [{ "containerPort": 80 }] }
+                        ]
+                    }
+                }
+            }
+        }))
+        .unwrap()
+    }
+
+    fn path(path: &str) -> FieldPath {
+        path.parse().unwrap()
+    }
+
+    #[test]
+    fn field_paths_round_trip() {
+        for p in [
+            ".spec.replicas",
+            r#".spec.template.spec.containers[name="app"].image"#,
+            r#".metadata.finalizers[="kube.rs/cleanup"]"#,
+            ".spec.args[2]",
+            r#".metadata.labels."app.kubernetes.io/name""#,
+            r#".spec.ports[port=80,protocol="TCP"]"#,
+        ] {
+            assert_eq!(path(p).to_string(), p);
+        }
+        assert_eq!(
+            path(".spec.containers[name=app]"),
+            path(r#".spec.containers[name="app"]"#)
+        );
+        assert!(".spec[".parse::<FieldPath>().is_err());
+        assert!("spec".parse::<FieldPath>().is_err());
+    }
+
+    #[test]
+    fn fields_v1_round_trip() {
+        let fields = FieldsV1(json!({
+            "f:metadata": { "f:finalizers": { ".": {}, "v:\"kube.rs/cleanup\"": {} } },
+            "f:spec": { "f:ports": { "k:{\"protocol\":\"TCP\",\"port\":80}": { ".": {}, "f:port": {} } } }
+        }));
+        let set = FieldSet::from_fields_v1(&fields).unwrap();
+        assert!(set.contains(&path(".metadata.finalizers")));
+        assert!(set.contains(&path(r#".metadata.finalizers[="kube.rs/cleanup"]"#)));
+        assert!(set.contains(&path(r#".spec.ports[protocol="TCP",port=80].port"#)));
+        assert!(!set.contains(&path(".spec")));
+        assert!(set.contains_prefix(&path(".spec")));
+        assert_eq!(set.paths().len(), 4);
+        assert_eq!(FieldSet::from_fields_v1(&set.to_fields_v1()).unwrap(), set);
+
+        assert!(FieldSet::from_fields_v1(&FieldsV1(json!({ "x:spec": {} }))).is_err());
+    }
+
+    #[test]
+    fn set_operations() {
+        let a = FieldSet::from_value(&json!({ "spec": { "replicas": 1, "paused": true } }));
+        let b = FieldSet::from_value(&json!({ "spec": { "replicas": 2, "strategy": {} } }));
+        assert_eq!(a.intersection(&b).paths(), [path(".spec.replicas")]);
+        assert_eq!(a.difference(&b).paths(), [path(".spec.paused")]);
+        assert_eq!(a.union(&b).paths().len(), 3);
+        assert!(a.difference(&a).is_empty());
+    }
+
+    #[test]
+    fn ownership_queries() {
+        let deployment = deployment();
+        let owners = deployment.managers_of(&path(".spec.replicas"));
+        assert_eq!(owners.len(), 1);
+        assert_eq!(owners[0].manager.as_deref(), Some("hpa"));
+
+        let owned = deployment.fields_owned_by("ci");
+        assert!(owned.contains(&path(r#".spec.template.spec.containers[name="app"].image"#)));
+        assert!(!owned.contains(&path(".spec.replicas")));
+
+        let applied =
+            FieldSet::from_value(&json!({ "spec": { "replicas": 1 }, "status": { "replicas": 1 } }));
+        assert_eq!(deployment.apply_conflicts("ci", &applied), [Conflict {
+            manager: "hpa".into(),
+            path: path(".spec.replicas"),
+        }]);
+        assert!(deployment.apply_conflicts("hpa", &applied).is_empty());
+    }
+
+    #[test]
+    fn extracts_applied_fields() {
+        let extracted = deployment().extract("ci").unwrap();
+        assert_eq!(
+            extracted,
+            json!({
+                "apiVersion": "apps/v1",
+                "kind": "Deployment",
+                "metadata": { "name": "web", "namespace": "apps" },
+                "spec": {
+                    "selector": { "matchLabels": { "app": "web" } },
+                    "template": { "spec": { "containers": [{ "name": "app", "image": "web:2" }] } }
+                }
+            })
+        );
+
+        // Only applied fields are extracted
+        let extracted = deployment().extract("hpa").unwrap();
+        assert_eq!(
+            extracted["metadata"],
+            json!({ "name": "web", "namespace": "apps" })
+        );
+        assert!(extracted.get("spec").is_none());
+    }
+}
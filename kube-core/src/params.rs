@@ -659,6 +659,138 @@ impl<T: Serialize> Patch<T> {
     }
 }
 
+impl Patch<serde_json::Value> {
+    /// A strategic merge patch turning `original` into `modified`
+    ///
+    /// Lists are merged with the merge keys of [`PatchSchema::kubernetes`](crate::strategic_merge::PatchSchema::kubernetes),
+    /// which only covers a subset of the built-in types; other lists are replaced as a whole.
+    ///
+    /// ```
+    /// use k8s_openapi::api::core::v1::{Container, Pod, PodSpec};
+    /// use kube::api::Patch;
+    /// let container = |name: &str, image: &str| Container {
+    ///     name: name.into(),
+    ///     image: Some(image.into()),
+    ///     ..Container::default()
+    /// };
+    /// let original = Pod {
+    ///     spec: Some(PodSpec { containers: vec![container("app", "app:1"), container("proxy", "envoy")], ..PodSpec::default() }),
+    ///     ..Pod::default()
+    /// };
+    /// let mut modified = original.clone();
+    /// modified.spec.as_mut().unwrap().containers[0].image = Some("app:2".into());
+    /// let patch = Patch::strategic_from_diff(&original, &modified)?;
+    /// assert_eq!(patch, Patch::Strategic(serde_json::json!({ "spec": {
+    ///     "$setElementOrder/containers": [{ "name": "app" }, { "name": "proxy" }],
+    ///     "containers": [{ "name": "app", "image": "app:2" }],
+    /// }})));
+    /// # Ok::<(), serde_json::Error>(())
+    /// ```
+    pub fn strategic_from_diff<K: Serialize>(original: &K, modified: &K) -> Result<Self, serde_json::Error> {
+        let original = serde_json::to_value(original)?;
+        let modified = serde_json::to_value(modified)?;
+        let schema = crate::strategic_merge::PatchSchema::kubernetes();
+        Ok(Self::Strategic(crate::strategic_merge::diff(
+            &original, &modified, &schema,
+        )))
+    }
+
+    /// A [JSON merge patch](https://www.rfc-editor.org/rfc/rfc7386) turning `old` into `new`
+    ///
+    /// Removed fields are set to `null` and lists are replaced as a whole. Fields that are `null` in `new`
+    /// are removed, as merge patches cannot set a `null` value.
+    ///
+    /// ```
+    /// use k8s_openapi::api::core::v1::ConfigMap;
+    /// use kube::api::Patch;
+    /// let old = ConfigMap {
+    ///     data: Some([("a".into(), "1".into()), ("b".into(), "2".into())].into()),
+    ///     ..ConfigMap::default()
+    /// };
+    /// let mut new = old.clone();
+    /// new.data = Some([("a".into(), "3".into())].into());
+    /// let patch = Patch::merge_from_diff(&old, &new)?;
+    /// assert_eq!(patch, Patch::Merge(serde_json::json!({ "data": { "a": "3", "b": null } })));
+    /// # Ok::<(), serde_json::Error>(())
+    /// ```
+    pub fn merge_from_diff<K: Serialize>(old: &K, new: &K) -> Result<Self, serde_json::Error> {
+        let old = serde_json::to_value(old)?;
+        let new = serde_json::to_value(new)?;
+        Ok(Self::Merge(merge_diff(&old, &new)))
+    }
+}
+
+/// The JSON merge patch turning `old` into `new`
+fn merge_diff(old: &serde_json::Value, new: &serde_json::Value) -> serde_json::Value {
+    use serde_json::Value;
+    let (Value::Object(old), Value::Object(new)) = (old, new) else {
+        return new.clone();
+    };
+    let mut patch = serde_json::Map::new();
+    for key in old.keys().filter(|key| !new.contains_key(*key)) {
+        patch.insert(key.clone(), Value::Null);
+    }
+    for (key, value) in new {
+        match old.get(key) {
+            Some(old) if old == value => {}
+            Some(old @ Value::Object(_)) if value.is_object() => {
+                patch.insert(key.clone(), merge_diff(old, value));
+            }
+            _ => {
+                patch.insert(key.clone(), value.clone());
+            }
+        }
+    }
+    Value::Object(patch)
+}
+
+#[cfg(feature = "jsonpatch")]
+#[cfg_attr(docsrs, doc(cfg(feature = "jsonpatch")))]
+impl Patch<serde_json::Value> {
+    /// A [JSON patch](https://www.rfc-editor.org/rfc/rfc6902) turning `old` into `new`
+    ///
+    /// ```
+    /// use k8s_openapi::api::core::v1::ConfigMap;
+    /// use kube::api::Patch;
+    /// let old = ConfigMap::default();
+    /// let mut new = old.clone();
+    /// new.data = Some([("a".into(), "1".into())].into());
+    /// let Patch::Json(patch) = Patch::json_from_diff(&old, &new)? else { unreachable!() };
+    /// assert_eq!(
+    ///     serde_json::to_value(patch)?,
+    ///     serde_json::json!([{ "op": "add", "path": "/data", "value": { "a": "1" } }])
+    /// );
+    /// # Ok::<(), serde_json::Error>(())
+    /// ```
+    pub fn json_from_diff<K: Serialize>(old: &K, new: &K) -> Result<Self, serde_json::Error> {
+        let old = serde_json::to_value(old)?;
+        let new = serde_json::to_value(new)?;
+        Ok(Self::Json(json_patch::diff(&old, &new)))
+    }
+
+    /// A [JSON patch](https://www.rfc-editor.org/rfc/rfc6902) turning `old` into `new`, which only applies
+    /// to the resource version of `old`
+    ///
+    /// The patch starts with a `test` of `metadata.resourceVersion`, so the apiserver rejects it if the object
+    /// changed since `old` was read. Without a resource version in `old`, this is [`Patch::json_from_diff`].
+    pub fn json_from_diff_guarded<K: Serialize>(old: &K, new: &K) -> Result<Self, serde_json::Error> {
+        use json_patch::{PatchOperation, TestOperation, jsonptr::PointerBuf};
+        let old = serde_json::to_value(old)?;
+        let new = serde_json::to_value(new)?;
+        let mut patch = json_patch::diff(&old, &new);
+        if let Some(resource_version) = old.pointer("/metadata/resourceVersion") {
+            patch.0.insert(
+                0,
+                PatchOperation::Test(TestOperation {
+                    path: PointerBuf::from_tokens(["metadata", "resourceVersion"]),
+                    value: resource_version.clone(),
+                }),
+            );
+        }
+        Ok(Self::Json(patch))
+    }
+}
+
 /// Common query parameters for patch calls
 #[derive(Default, Clone, Debug)]
 pub struct PatchParams {
@@ -943,6 +1075,46 @@ mod test {
         let labels = wp.label_selector.unwrap();
         assert_eq!(labels, "env in (development,sandbox)");
     }
+
+    #[test]
+    fn merge_patch_from_diff() {
+        use super::Patch;
+        use serde_json::json;
+
+        let old =
+            json!({ "metadata": { "labels": { "a": "1", "b": "2" } }, "spec": { "ports": [1, 2], "x": 1 } });
+        let new = json!({ "metadata": { "labels": { "a": "1", "c": "3" } }, "spec": { "ports": [1] } });
+        assert_eq!(
+            Patch::merge_from_diff(&old, &new).unwrap(),
+            Patch::Merge(
+                json!({ "metadata": { "labels": { "b": null, "c": "3" } }, "spec": { "ports": [1], "x": null } })
+            )
+        );
+        assert_eq!(
+            Patch::merge_from_diff(&old, &old).unwrap(),
+            Patch::Merge(json!({}))
+        );
+    }
+
+    #[cfg(feature = "jsonpatch")]
+    #[test]
+    fn guarded_json_patch_tests_resource_version() {
+        use super::Patch;
+        use serde_json::json;
+
+        let old = json!({ "metadata": { "name": "a", "resourceVersion": "12" }, "data": { "x": "1" } });
+        let new = json!({ "metadata": { "name": "a", "resourceVersion": "12" }, "data": { "x": "2" } });
+        let Patch::Json(patch) = Patch::json_from_diff_guarded(&old, &new).unwrap() else {
+            unreachable!()
+        };
+        assert_eq!(
+            serde_json::to_value(patch).unwrap(),
+            json!([
+                { "op": "test", "path": "/metadata/resourceVersion", "value": "12" },
+                { "op": "replace", "path": "/data/x", "value": "2" },
+            ])
+        );
+    }
 }
 
 /// Preconditions must be fulfilled before an operation (update, delete, etc.) is carried out.
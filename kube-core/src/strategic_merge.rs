@@ -0,0 +1,513 @@
+//! Generation of strategic merge patches
+//!
+//! A [strategic merge patch] merges lists of objects by a key field (like containers by `name`) instead of
+//! replacing them, and marks the removed items with directives. How each list merges is not part of the objects,
+//! so it is looked up in a [`PatchSchema`]; [`PatchSchema::kubernetes`] holds the merge keys of a subset of the
+//! built-in types. It is a hand-maintained table rather than the `x-kubernetes-patch-merge-key` extensions of
+//! the OpenAPI schema, so patches for fields it does not cover can differ from the ones `kubectl` computes.
+//!
+//! [`diff`] computes the patch from an original to a modified object, while [`three_way`] also takes the
+//! current object into account, so fields last applied by someone else are kept:
+//!
+//! ```
+//! use kube_core::strategic_merge::{self, PatchSchema};
+//! use serde_json::json;
+//!
+//! let original = json!({ "spec": { "containers": [{ "name": "app", "image": "app:1" }, { "name": "sidecar" }] } });
+//! let modified = json!({ "spec": { "containers": [{ "name": "app", "image": "app:2" }] } });
+//! let patch = strategic_merge::diff(&original, &modified, &PatchSchema::kubernetes());
+//! assert_eq!(patch, json!({ "spec": {
+//!     "$setElementOrder/containers": [{ "name": "app" }],
+//!     "containers": [{ "name": "app", "image": "app:2" }, { "name": "sidecar", "$patch": "delete" }],
+//! }}));
+//! ```
+//!
+//! [strategic merge patch]: https://github.com/kubernetes/community/blob/master/contributors/devel/sig-api-machinery/strategic-merge-patch.md
+use serde_json::{Map, Value};
+
+const DIRECTIVE: &str = "$patch";
+const SET_ELEMENT_ORDER: &str = "$setElementOrder/";
+const DELETE_FROM_PRIMITIVE_LIST: &str = "$deleteFromPrimitiveList/";
+
+/// How a field is merged by a strategic merge patch
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Strategy {
+    /// A list of objects merged by the value of a key field
+    MergeKey(String),
+    /// A list of primitives merged as a set
+    MergeSet,
+    /// An object or list replaced as a whole, with a `$patch: replace` directive
+    Replace,
+}
+
+/// The merge strategies of the fields of a type
+///
+/// Fields are identified by the path of field names leading to them, without list indices, and match every
+/// field whose path ends with it. Lists of fields without a strategy are replaced as a whole, like the
+/// server does.
+#[derive(Clone, Debug, Default)]
+pub struct PatchSchema {
+    fields: Vec<(Vec<String>, Strategy)>,
+}
+
+impl PatchSchema {
+    /// A schema without any strategy
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The strategies of a subset of the built-in Kubernetes types
+    ///
+    /// Covers:
+    /// - `metadata.finalizers` and `metadata.ownerReferences` of every object
+    /// - `status.conditions`, node `status.addresses`, and pod `status.podIPs` and `status.hostIPs`
+    /// - pod specs, also within templates: volumes, image pull secrets, host aliases, topology spread
+    ///   constraints, resource claims, scheduling gates, readiness gates, and (init and ephemeral) containers with
+    ///   their ports, env, volume mounts, volume devices and resize policy
+    /// - service `spec.ports` and service account `secrets`
+    ///
+    /// Other lists are replaced as a whole. Fields are matched by the suffix of their path, so these
+    /// strategies also apply to fields of custom resources that happen to end the same way, although
+    /// custom resources cannot be patched with strategic merge patches.
+    pub fn kubernetes() -> Self {
+        let key = |key: &str| Strategy::MergeKey(key.to_owned());
+        let mut schema = Self::new()
+            .with("metadata.finalizers", Strategy::MergeSet)
+            .with("metadata.ownerReferences", key("uid"))
+            .with("status.conditions", key("type"))
+            .with("status.addresses", key("type"))
+            .with("status.podIPs", key("ip"))
+            .with("status.hostIPs", key("ip"))
+            .with("spec.volumes", key("name"))
+            .with("spec.imagePullSecrets", key("name"))
+            .with("spec.hostAliases", key("ip"))
+            .with("spec.topologySpreadConstraints", key("topologyKey"))
+            .with("spec.resourceClaims", key("name"))
+            .with("spec.schedulingGates", key("name"))
+            .with("spec.readinessGates", key("conditionType"))
+            .with("spec.ports", key("port"))
+            .with("secrets", key("name"));
+        for containers in ["containers", "initContainers", "ephemeralContainers"] {
+            schema = schema
+                .with(&format!("spec.{containers}"), key("name"))
+                .with(&format!("{containers}.ports"), key("containerPort"))
+                .with(&format!("{containers}.env"), key("name"))
+                .with(&format!("{containers}.volumeMounts"), key("mountPath"))
+                .with(&format!("{containers}.volumeDevices"), key("devicePath"))
+                .with(&format!("{containers}.resizePolicy"), key("resourceName"));
+        }
+        schema
+    }
+
+    /// Merge the fields whose path ends with the dot separated `path` with `strategy`
+    #[must_use]
+    pub fn with(mut self, path: &str, strategy: Strategy) -> Self {
+        self.fields
+            .push((path.split('.').map(ToOwned::to_owned).collect(), strategy));
+        self
+    }
+
+    /// The strategy of the field at `path`, from its longest matching suffix
+    pub fn strategy(&self, path: &[&str]) -> Option<&Strategy> {
+        self.fields
+            .iter()
+            .filter(|(suffix, _)| {
+                suffix.len() <= path.len() && suffix.iter().rev().zip(path.iter().rev()).all(|(a, b)| a == b)
+            })
+            .max_by_key(|(suffix, _)| suffix.len())
+            .map(|(_, strategy)| strategy)
+    }
+}
+
+/// Which differences end up in a patch
+#[derive(Clone, Copy)]
+struct Mode {
+    changes: bool,
+    deletions: bool,
+}
+
+const ALL: Mode = Mode {
+    changes: true,
+    deletions: true,
+};
+
+/// Compute the strategic merge patch turning `original` into `modified`
+pub fn diff(original: &Value, modified: &Value, schema: &PatchSchema) -> Value {
+    match (original, modified) {
+        (Value::Object(original), Value::Object(modified)) => {
+            Value::Object(diff_maps(original, modified, &mut Vec::new(), schema, ALL))
+        }
+        _ => modified.clone(),
+    }
+}
+
+/// Compute the strategic merge patch turning `current` into `modified`, where `original` is what was last applied
+///
+/// Only the fields removed between `original` and `modified` are deleted, so fields of `current` that
+/// others set are kept. `original` is typically the `kubectl.kubernetes.io/last-applied-configuration`
+/// annotation.
+pub fn three_way(original: &Value, modified: &Value, current: &Value, schema: &PatchSchema) -> Value {
+    let (Value::Object(original), Value::Object(modified), Value::Object(current)) =
+        (original, modified, current)
+    else {
+        return modified.clone();
+    };
+    let deletions = diff_maps(original, modified, &mut Vec::new(), schema, Mode {
+        changes: false,
+        deletions: true,
+    });
+    let changes = diff_maps(current, modified, &mut Vec::new(), schema, Mode {
+        changes: true,
+        deletions: false,
+    });
+    Value::Object(merge_patches(deletions, changes, &[], schema))
+}
+
+fn diff_maps<'a>(
+    original: &'a Map<String, Value>,
+    modified: &'a Map<String, Value>,
+    path: &mut Vec<&'a str>,
+    schema: &PatchSchema,
+    mode: Mode,
+) -> Map<String, Value> {
+    let mut patch = Map::new();
+    if mode.deletions {
+        for key in original.keys().filter(|key| !modified.contains_key(*key)) {
+            patch.insert(key.clone(), Value::Null);
+        }
+    }
+    for (key, value) in modified {
+        let Some(original) = original.get(key) else {
+            if mode.changes {
+                patch.insert(key.clone(), value.clone());
+            }
+            continue;
+        };
+        if original == value {
+            continue;
+        }
+        path.push(key);
+        match (schema.strategy(path), original, value) {
+            (Some(Strategy::Replace), ..) => {
+                if mode.changes {
+                    patch.insert(key.clone(), replace(value));
+                }
+            }
+            (_, Value::Object(original), Value::Object(value)) => {
+                let nested = diff_maps(original, value, path, schema, mode);
+                if !nested.is_empty() {
+                    patch.insert(key.clone(), Value::Object(nested));
+                }
+            }
+            (Some(Strategy::MergeKey(merge_key)), Value::Array(original), Value::Array(value)) => {
+                diff_keyed_lists(key, merge_key, original, value, path, schema, mode, &mut patch);
+            }
+            (Some(Strategy::MergeSet), Value::Array(original), Value::Array(value)) => {
+                diff_sets(key, original, value, mode, &mut patch);
+            }
+            _ => {
+                if mode.changes {
+                    patch.insert(key.clone(), value.clone());
+                }
+            }
+        }
+        path.pop();
+    }
+    patch
+}
+
+/// Mark a value to replace the current one instead of being merged into it
+fn replace(value: &Value) -> Value {
+    let directive = (DIRECTIVE.to_owned(), Value::from("replace"));
+    match value {
+        Value::Object(map) => Value::Object([directive].into_iter().chain(map.clone()).collect()),
+        Value::Array(items) => Value::Array(
+            [Value::Object([directive].into_iter().collect())]
+                .into_iter()
+                .chain(items.clone())
+                .collect(),
+        ),
+        _ => value.clone(),
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn diff_keyed_lists<'a>(
+    field: &str,
+    merge_key: &str,
+    original: &'a [Value],
+    modified: &'a [Value],
+    path: &mut Vec<&'a str>,
+    schema: &PatchSchema,
+    mode: Mode,
+    patch: &mut Map<String, Value>,
+) {
+    let key_of = |item: &'a Value| item.get(merge_key).filter(|key| !key.is_null());
+    let find = |items: &'a [Value], key: Option<&Value>| {
+        key.and_then(|key| items.iter().find(|item| key_of(item) == Some(key)))
+    };
+
+    let mut items = Vec::new();
+    for item in modified {
+        match (find(original, key_of(item)), item) {
+            (Some(Value::Object(original)), Value::Object(map)) => {
+                let mut nested = diff_maps(original, map, path, schema, mode);
+                if !nested.is_empty() {
+                    nested.insert(merge_key.to_owned(), map[merge_key].clone());
+                    items.push(Value::Object(nested));
+                }
+            }
+            (Some(original), _) if original == item => {}
+            _ => {
+                if mode.changes {
+                    items.push(item.clone());
+                }
+            }
+        }
+    }
+    if mode.deletions {
+        for item in original {
+            if let Some(key) = key_of(item)
+                && find(modified, Some(key)).is_none()
+            {
+                let deleted = [
+                    (merge_key.to_owned(), key.clone()),
+                    (DIRECTIVE.to_owned(), "delete".into()),
+                ];
+                items.push(Value::Object(deleted.into_iter().collect()));
+            }
+        }
+    }
+
+    // Whether the items kept from the original are in another order
+    let order = |items: &'a [Value], within: &'a [Value]| {
+        items
+            .iter()
+            .filter_map(key_of)
+            .filter(|key| find(within, Some(key)).is_some())
+            .collect::<Vec<_>>()
+    };
+    let reordered = mode.changes && order(original, modified) != order(modified, original);
+    if items.is_empty() && !reordered {
+        return;
+    }
+    if mode.changes {
+        let order = modified
+            .iter()
+            .filter_map(key_of)
+            .map(|key| Value::Object([(merge_key.to_owned(), key.clone())].into_iter().collect()))
+            .collect();
+        patch.insert(format!("{SET_ELEMENT_ORDER}{field}"), Value::Array(order));
+    }
+    if !items.is_empty() {
+        patch.insert(field.to_owned(), Value::Array(items));
+    }
+}
+
+fn diff_sets(
+    field: &str,
+    original: &[Value],
+    modified: &[Value],
+    mode: Mode,
+    patch: &mut Map<String, Value>,
+) {
+    let added: Vec<_> = modified
+        .iter()
+        .filter(|item| !original.contains(item))
+        .cloned()
+        .collect();
+    let removed: Vec<_> = original
+        .iter()
+        .filter(|item| !modified.contains(item))
+        .cloned()
+        .collect();
+    if mode.changes {
+        patch.insert(
+            format!("{SET_ELEMENT_ORDER}{field}"),
+            Value::Array(modified.to_vec()),
+        );
+        if !added.is_empty() {
+            patch.insert(field.to_owned(), Value::Array(added));
+        }
+    }
+    if mode.deletions && !removed.is_empty() {
+        patch.insert(
+            format!("{DELETE_FROM_PRIMITIVE_LIST}{field}"),
+            Value::Array(removed),
+        );
+    }
+}
+
+/// Merge two patches of the same object, with `changes` winning over `deletions`
+fn merge_patches(
+    mut deletions: Map<String, Value>,
+    changes: Map<String, Value>,
+    path: &[&str],
+    schema: &PatchSchema,
+) -> Map<String, Value> {
+    for (key, change) in changes {
+        let mut path = path.to_vec();
+        path.push(&key);
+        let merge_key = match schema.strategy(&path) {
+            Some(Strategy::MergeKey(merge_key)) => Some(merge_key),
+            _ => None,
+        };
+        let merged = match (deletions.remove(&key), change, merge_key) {
+            (Some(Value::Object(deletion)), Value::Object(change), _) => {
+                Value::Object(merge_patches(deletion, change, &path, schema))
+            }
+            (Some(Value::Array(mut items)), Value::Array(change), Some(merge_key)) => {
+                for change in change {
+                    let existing = items.iter_mut().find(|item| {
+                        item.get(merge_key).is_some() && item.get(merge_key) == change.get(merge_key)
+                    });
+                    match (existing, change) {
+                        (Some(Value::Object(item)), Value::Object(change)) => {
+                            *item = merge_patches(std::mem::take(item), change, &path, schema);
+                        }
+                        (_, change) => items.push(change),
+                    }
+                }
+                Value::Array(items)
+            }
+            (_, change, _) => change,
+        };
+        deletions.insert(key, merged);
+    }
+    deletions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    #[test]
+    fn matches_longest_suffix() {
+        let schema = PatchSchema::kubernetes();
+        assert_eq!(
+            schema.strategy(&["spec", "template", "spec", "containers", "ports"]),
+            Some(&Strategy::MergeKey("containerPort".into()))
+        );
+        assert_eq!(
+            schema.strategy(&["spec", "ports"]),
+            Some(&Strategy::MergeKey("port".into()))
+        );
+        assert_eq!(
+            schema.strategy(&["spec", "template", "spec", "readinessGates"]),
+            Some(&Strategy::MergeKey("conditionType".into()))
+        );
+        assert_eq!(
+            schema.strategy(&["status", "addresses"]),
+            Some(&Strategy::MergeKey("type".into()))
+        );
+        assert_eq!(
+            schema.strategy(&["secrets"]),
+            Some(&Strategy::MergeKey("name".into()))
+        );
+        assert_eq!(schema.strategy(&["spec", "tolerations"]), None);
+    }
+
+    #[test]
+    fn two_way_patch_with_directives() {
+        let original = json!({
+            "metadata": { "labels": { "app": "web", "tier": "frontend" }, "finalizers": ["a", "b"] },
+            "spec": {
+                "replicas": 1,
+                "template": { "spec": {
+                    "containers": [
+                        { "name": "app", "image": "app:1", "env": [{ "name": "A", "value": "1" }, { "name": "B", "value": "2" }] },
+                        { "name": "sidecar", "image": "proxy" },
+                    ],
+                    "tolerations": [{ "key": "a" }],
+                }},
+            },
+        });
+        let modified = json!({
+            "metadata": { "labels": { "app": "web" }, "finalizers": ["a", "c"] },
+            "spec": {
+                "replicas": 1,
+                "template": { "spec": {
+                    "containers": [
+                        { "name": "app", "image": "app:2", "env": [{ "name": "A", "value": "1" }] },
+                        { "name": "logs", "image": "fluentd" },
+                    ],
+                    "tolerations": [{ "key": "b" }],
+                }},
+            },
+        });
+        let patch = diff(&original, &modified, &PatchSchema::kubernetes());
+        assert_eq!(
+            patch,
+            json!({
+                "metadata": {
+                    "labels": { "tier": null },
+                    "$setElementOrder/finalizers": ["a", "c"],
+                    "finalizers": ["c"],
+                    "$deleteFromPrimitiveList/finalizers": ["b"],
+                },
+                "spec": { "template": { "spec": {
+                    "$setElementOrder/containers": [{ "name": "app" }, { "name": "logs" }],
+                    "containers": [
+                        {
+                            "name": "app",
+                            "image": "app:2",
+                            "$setElementOrder/env": [{ "name": "A" }],
+                            "env": [{ "name": "B", "$patch": "delete" }],
+                        },
+                        { "name": "logs", "image": "fluentd" },
+                        { "name": "sidecar", "$patch": "delete" },
+                    ],
+                    "tolerations": [{ "key": "b" }],
+                }}},
+            })
+        );
+        assert_eq!(diff(&modified, &modified, &PatchSchema::kubernetes()), json!({}));
+    }
+
+    #[test]
+    fn replaces_with_directive() {
+        let schema = PatchSchema::new().with("spec.selector", Strategy::Replace);
+        let patch = diff(
+            &json!({ "spec": { "selector": { "a": "1" } } }),
+            &json!({ "spec": { "selector": { "b": "2" } } }),
+            &schema,
+        );
+        assert_eq!(
+            patch,
+            json!({ "spec": { "selector": { "$patch": "replace", "b": "2" } } })
+        );
+    }
+
+    #[test]
+    fn three_way_patch_keeps_fields_set_by_others() {
+        let original = json!({
+            "metadata": { "labels": { "app": "web", "old": "x" } },
+            "spec": { "containers": [{ "name": "app", "image": "app:1" }, { "name": "debug" }] },
+        });
+        let modified = json!({
+            "metadata": { "labels": { "app": "web" } },
+            "spec": { "containers": [{ "name": "app", "image": "app:2" }] },
+        });
+        let current = json!({
+            "metadata": { "labels": { "app": "web", "old": "x", "team": "a" } },
+            "spec": {
+                "replicas": 3,
+                "containers": [{ "name": "app", "image": "app:1", "imagePullPolicy": "Always" }, { "name": "debug" }],
+            },
+        });
+        let patch = three_way(&original, &modified, &current, &PatchSchema::kubernetes());
+        assert_eq!(
+            patch,
+            json!({
+                "metadata": { "labels": { "old": null } },
+                "spec": {
+                    "$setElementOrder/containers": [{ "name": "app" }],
+                    "containers": [{ "name": "debug", "$patch": "delete" }, { "name": "app", "image": "app:2" }],
+                },
+            })
+        );
+    }
+}
@@ -5,13 +5,22 @@
 //!
 //! Run with: `cargo bench -p kube-runtime --bench memory`
 
-use std::collections::BTreeMap;
+use std::{collections::BTreeMap, convert::Infallible};
 
-use futures::{StreamExt, stream};
+use bytes::Bytes;
+use futures::{Stream, StreamExt, stream, stream::BoxStream};
+use http::{Request, Response};
+use http_body::Frame;
+use http_body_util::StreamBody;
 use k8s_openapi::{
     api::core::v1::ConfigMap,
     apimachinery::pkg::apis::meta::v1::{ManagedFieldsEntry, ObjectMeta},
 };
+use kube::{
+    Api, Client,
+    api::{ListParams, ObjectList},
+    client::Body,
+};
 use kube_runtime::{
     reflector::{self, store},
     watcher,
@@ -25,40 +34,40 @@ static ALLOC: dhat::Alloc = dhat::Alloc;
 // ---------------------------------------------------------------------------
 
 fn generate_configmaps(count: usize) -> Vec<ConfigMap> {
-    (0..count)
-        .map(|i| {
-            let mut labels = BTreeMap::new();
-            labels.insert("app".to_string(), "bench".to_string());
-            labels.insert("instance".to_string(), format!("cm-{i}"));
-
-            let mut annotations = BTreeMap::new();
-            annotations.insert(
-                "kubectl.kubernetes.io/last-applied-configuration".to_string(),
-                format!("{{\"kind\":\"ConfigMap\",\"metadata\":{{\"name\":\"cm-{i}\"}},\"data\":{{}}}}"),
-            );
-
-            let mut data = BTreeMap::new();
-            data.insert(
-                "config.yaml".to_string(),
-                format!("setting: value-{i}\ncount: {i}"),
-            );
-            data.insert("extra".to_string(), "x".repeat(128));
-
-            ConfigMap {
-                metadata: ObjectMeta {
-                    name: Some(format!("cm-{i}")),
-                    namespace: Some("bench".to_string()),
-                    resource_version: Some(format!("{}", 1000 + i)),
-                    uid: Some(format!("uid-{i}")),
-                    labels: Some(labels),
-                    annotations: Some(annotations),
-                    ..ObjectMeta::default()
-                },
-                data: Some(data),
-                ..ConfigMap::default()
-            }
-        })
-        .collect()
+    (0..count).map(generate_configmap).collect()
+}
+
+fn generate_configmap(i: usize) -> ConfigMap {
+    let mut labels = BTreeMap::new();
+    labels.insert("app".to_string(), "bench".to_string());
+    labels.insert("instance".to_string(), format!("cm-{i}"));
+
+    let mut annotations = BTreeMap::new();
+    annotations.insert(
+        "kubectl.kubernetes.io/last-applied-configuration".to_string(),
+        format!("{{\"kind\":\"ConfigMap\",\"metadata\":{{\"name\":\"cm-{i}\"}},\"data\":{{}}}}"),
+    );
+
+    let mut data = BTreeMap::new();
+    data.insert(
+        "config.yaml".to_string(),
+        format!("setting: value-{i}\ncount: {i}"),
+    );
+    data.insert("extra".to_string(), "x".repeat(128));
+
+    ConfigMap {
+        metadata: ObjectMeta {
+            name: Some(format!("cm-{i}")),
+            namespace: Some("bench".to_string()),
+            resource_version: Some(format!("{}", 1000 + i)),
+            uid: Some(format!("uid-{i}")),
+            labels: Some(labels),
+            annotations: Some(annotations),
+            ..ObjectMeta::default()
+        },
+        data: Some(data),
+        ..ConfigMap::default()
+    }
 }
 
 fn generate_configmaps_with_managed_fields(count: usize) -> Vec<ConfigMap> {
@@ -152,15 +161,52 @@ fn relist_events(cms: &[ConfigMap], update_count: usize) -> Vec<watcher::Result<
 // ---------------------------------------------------------------------------
 
 async fn run_reflector(events: Vec<watcher::Result<watcher::Event<ConfigMap>>>) -> store::Store<ConfigMap> {
+    run_reflector_stream(stream::iter(events)).await
+}
+
+async fn run_reflector_stream(
+    events: impl Stream<Item = watcher::Result<watcher::Event<ConfigMap>>>,
+) -> store::Store<ConfigMap> {
     let store_w = store::Writer::default();
     let store = store_w.as_reader();
-    reflector::reflector(store_w, stream::iter(events))
+    reflector::reflector(store_w, events)
         .map(|_| ())
         .collect::<()>()
         .await;
     store
 }
 
+// ---------------------------------------------------------------------------
+// List responses
+// ---------------------------------------------------------------------------
+
+type ChunkedBody = StreamBody<BoxStream<'static, Result<Frame<Bytes>, Infallible>>>;
+
+/// A client answering a single list request with `count` generated objects, serialized lazily in one chunk per
+/// object, like a response body arriving from the network
+fn list_client(count: usize) -> Client {
+    let (mock_service, handle) = tower_test::mock::pair::<Request<Body>, Response<ChunkedBody>>();
+    tokio::spawn(async move {
+        let mut handle = std::pin::pin!(handle);
+        let (_, send) = handle.next_request().await.expect("service not called");
+        let chunks = stream::iter(0..count).map(move |i| {
+            let mut chunk = if i == 0 {
+                br#"{"apiVersion":"v1","kind":"ConfigMapList","metadata":{"resourceVersion":"1"},"items":["#
+                    .to_vec()
+            } else {
+                b",".to_vec()
+            };
+            serde_json::to_writer(&mut chunk, &generate_configmap(i)).expect("failed to serialize ConfigMap");
+            if i == count - 1 {
+                chunk.extend_from_slice(b"]}");
+            }
+            Ok(Frame::data(Bytes::from(chunk)))
+        });
+        send.send_response(Response::new(StreamBody::new(chunks.boxed())));
+    });
+    Client::new(mock_service, "bench")
+}
+
 async fn run_reflector_with_modify(
     events: Vec<watcher::Result<watcher::Event<ConfigMap>>>,
 ) -> store::Store<ConfigMap> {
@@ -301,6 +347,48 @@ async fn bench_init_with_modify(results: &mut Vec<BenchMetric>) {
     collect_stats("init_with_modify", results);
 }
 
+/// Initial list decoded from the full response body, then reflected
+async fn bench_list_buffered(results: &mut Vec<BenchMetric>) {
+    let _profiler = dhat::Profiler::builder().testing().build();
+
+    let client = list_client(NUM_OBJECTS);
+    let request = Request::get("/api/v1/configmaps").body(vec![]).unwrap();
+    let list: ObjectList<ConfigMap> = client.request(request).await.expect("list failed");
+    let events = stream::iter(
+        std::iter::once(watcher::Event::Init)
+            .chain(list.items.into_iter().map(watcher::Event::InitApply))
+            .chain(std::iter::once(watcher::Event::InitDone))
+            .map(Ok),
+    );
+    let store = run_reflector_stream(events).await;
+
+    assert_eq!(
+        store.state().len(),
+        NUM_OBJECTS,
+        "store should contain all listed objects"
+    );
+    collect_stats("list_buffered", results);
+}
+
+/// Initial list reflected as its objects are decoded from the response body, like the watcher does
+async fn bench_list_streamed(results: &mut Vec<BenchMetric>) {
+    let _profiler = dhat::Profiler::builder().testing().build();
+
+    let api = Api::<ConfigMap>::all(list_client(NUM_OBJECTS));
+    let items = api.list_items(&ListParams::default()).await.expect("list failed");
+    let events = stream::once(async { Ok(watcher::Event::Init) })
+        .chain(items.map(|cm| Ok(watcher::Event::InitApply(cm.expect("decoding failed")))))
+        .chain(stream::once(async { Ok(watcher::Event::InitDone) }));
+    let store = run_reflector_stream(events).await;
+
+    assert_eq!(
+        store.state().len(),
+        NUM_OBJECTS,
+        "store should contain all listed objects"
+    );
+    collect_stats("list_streamed", results);
+}
+
 fn main() {
     let rt = tokio::runtime::Builder::new_current_thread()
         .enable_all()
@@ -314,6 +402,8 @@ fn main() {
         bench_relist(&mut results).await;
         bench_init_without_modify(&mut results).await;
         bench_init_with_modify(&mut results).await;
+        bench_list_buffered(&mut results).await;
+        bench_list_streamed(&mut results).await;
     });
 
     println!(
@@ -44,6 +44,16 @@ where
     pub fn len(&self) -> usize {
         self.futures.len()
     }
+
+    /// Returns the keys of all futures that are currently running
+    pub fn keys(&self) -> impl Iterator<Item = &K> {
+        self.futures.keys()
+    }
+
+    /// Drops all running futures, returning their keys
+    pub fn cancel_all(&mut self) -> Vec<K> {
+        self.futures.drain().map(|(key, _)| key).collect()
+    }
 }
 
 impl<K, F> Stream for FutureHashMap<K, F>
@@ -7,7 +7,7 @@ use crate::{
         self, ObjectRef, reflector,
         store::{Store, Writer},
     },
-    scheduler::{ScheduleRequest, debounced_scheduler},
+    scheduler::{ScheduleRequest, SchedulerProbe, debounced_scheduler},
     utils::{
         Backoff, CancelableJoinHandle, KubeRuntimeStreamExt, StreamBackoff, WatchStreamExt, trystream_try_via,
     },
@@ -394,15 +394,53 @@ const APPLIER_REQUEUE_BUF_SIZE: usize = 100;
 ///
 /// This is the "hard-mode" version of [`Controller`], which allows you some more customization
 /// (such as triggering from arbitrary [`Stream`]s), at the cost of being a bit more verbose.
-#[allow(clippy::needless_pass_by_value)]
 #[allow(clippy::type_complexity)]
 pub fn applier<K, QueueStream, ReconcilerFut, Ctx>(
+    reconciler: impl FnMut(Arc<K>, Arc<Ctx>) -> ReconcilerFut,
+    error_policy: impl Fn(Arc<K>, &ReconcilerFut::Error, Arc<Ctx>) -> Action,
+    context: Arc<Ctx>,
+    store: Store<K>,
+    queue: QueueStream,
+    config: Config,
+) -> impl Stream<Item = Result<(ObjectRef<K>, Action), Error<ReconcilerFut::Error, QueueStream::Error>>>
+where
+    K: Clone + Resource + 'static,
+    K::DynamicType: Debug + Eq + Hash + Clone + Unpin,
+    ReconcilerFut: TryFuture<Ok = Action> + Unpin,
+    ReconcilerFut::Error: std::error::Error + 'static,
+    QueueStream: TryStream,
+    QueueStream::Ok: Into<ReconcileRequest<K>>,
+    QueueStream::Error: std::error::Error + 'static,
+{
+    applier_with_hooks(
+        reconciler,
+        error_policy,
+        context,
+        store,
+        queue,
+        config,
+        None,
+        None,
+    )
+}
+
+/// Callback that receives the [`ShutdownReport`] once a graceful shutdown has finished draining
+type ShutdownHook<K> = Box<dyn FnOnce(ShutdownReport<K>) + Send>;
+
+/// Same as [`applier`], but calls `on_shutdown` once the applier has finished draining after a graceful shutdown,
+/// and keeps `scheduler_probe` up to date with the state of the scheduler
+#[allow(clippy::needless_pass_by_value)]
+#[allow(clippy::type_complexity)]
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn applier_with_hooks<K, QueueStream, ReconcilerFut, Ctx>(
     mut reconciler: impl FnMut(Arc<K>, Arc<Ctx>) -> ReconcilerFut,
     error_policy: impl Fn(Arc<K>, &ReconcilerFut::Error, Arc<Ctx>) -> Action,
     context: Arc<Ctx>,
     store: Store<K>,
     queue: QueueStream,
     config: Config,
+    on_shutdown: Option<ShutdownHook<K>>,
+    scheduler_probe: Option<SchedulerProbe>,
 ) -> impl Stream<Item = Result<(ObjectRef<K>, Action), Error<ReconcilerFut::Error, QueueStream::Error>>>
 where
     K: Clone + Resource + 'static,
@@ -443,7 +481,7 @@ where
         // all the Oks from the select gets passed through the scheduler stream, and are then executed
         move |s| {
             Runner::new(
-                debounced_scheduler(s, config.debounce),
+                debounced_scheduler(s, config.debounce).with_probe(scheduler_probe),
                 config.concurrency,
                 move |request| {
                     let request = request.clone();
@@ -488,6 +526,12 @@ where
                 tracing::debug!("store is ready, starting runner");
                 res
             })
+            .drain_timeout(config.drain_timeout)
+            .on_drained(
+                on_shutdown.map(|on_shutdown| -> runner::DrainHook<ReconcileRequest<K>> {
+                    Box::new(move |report| on_shutdown(ShutdownReport::from(report)))
+                }),
+            )
             .map(|runner_res| runner_res.unwrap_or_else(|err| Err(Error::RunnerError(err))))
             .on_complete(async { tracing::debug!("applier runner terminated") })
         },
@@ -577,11 +621,46 @@ where
     }
 }
 
+/// Summary of a graceful shutdown, passed to the hook registered with [`Controller::on_shutdown`]
+///
+/// Objects in `cancelled` and `dropped` have not been reconciled since their last trigger,
+/// and may need to be handed off to another instance of the controller.
+#[derive(Educe)]
+#[educe(
+    Debug(bound("K::DynamicType: Debug")),
+    Clone(bound("K::DynamicType: Clone")),
+    PartialEq(bound("K::DynamicType: PartialEq"))
+)]
+pub struct ShutdownReport<K: Resource> {
+    /// Objects that were being reconciled when the graceful shutdown started
+    pub in_flight: Vec<ObjectRef<K>>,
+    /// Objects whose reconciliations were still running when the [drain timeout](Config::drain_timeout)
+    /// expired, and that were cancelled
+    pub cancelled: Vec<ObjectRef<K>>,
+    /// Objects that were queued for reconciliation, but never started before the shutdown
+    pub dropped: Vec<ObjectRef<K>>,
+    /// How long it took for running reconciliations to finish (or be cancelled)
+    pub drain_duration: Duration,
+}
+
+impl<K: Resource> From<runner::DrainReport<ReconcileRequest<K>>> for ShutdownReport<K> {
+    fn from(report: runner::DrainReport<ReconcileRequest<K>>) -> Self {
+        let obj_refs = |requests: Vec<ReconcileRequest<K>>| requests.into_iter().map(|r| r.obj_ref).collect();
+        Self {
+            in_flight: obj_refs(report.in_flight),
+            cancelled: obj_refs(report.cancelled),
+            dropped: obj_refs(report.dropped),
+            drain_duration: report.duration,
+        }
+    }
+}
+
 /// Accumulates all options that can be used on a [`Controller`] invocation.
 #[derive(Clone, Debug, Default)]
 pub struct Config {
     debounce: Duration,
     concurrency: u16,
+    drain_timeout: Option<Duration>,
 }
 
 impl Config {
@@ -615,6 +694,19 @@ impl Config {
         self.concurrency = concurrency;
         self
     }
+
+    /// The maximum time to wait for running reconciliations to finish after a graceful shutdown has started.
+    ///
+    /// Once the timeout expires, any reconciliations that are still running are cancelled
+    /// (aborted at their next yield point), and the controller terminates. By default there is no timeout,
+    /// and the controller waits for all running reconciliations to finish.
+    ///
+    /// The cancelled objects are listed in the [`ShutdownReport`], see [`Controller::on_shutdown`].
+    #[must_use]
+    pub fn drain_timeout(mut self, timeout: Duration) -> Self {
+        self.drain_timeout = Some(timeout);
+        self
+    }
 }
 
 /// Controller for a Resource `K`
@@ -703,6 +795,8 @@ where
     /// However, note that they *will* keep running until their next yield point (`.await`),
     /// blocking [`tokio::runtime::Runtime`] destruction (unless you follow up by calling [`std::process::exit`] after `run`).
     forceful_shutdown_selector: Vec<BoxFuture<'static, ()>>,
+    /// Called with a summary of the unfinished work once a graceful shutdown has finished draining.
+    shutdown_hook: Option<ShutdownHook<K>>,
     dyntype: K::DynamicType,
     reader: Store<K>,
     config: Config,
@@ -763,6 +857,7 @@ where
                 // Fallback future, ensuring that we never terminate if no additional futures are added to the selector
                 future::pending().boxed(),
             ],
+            shutdown_hook: None,
             dyntype,
             reader,
             config: Default::default(),
@@ -845,6 +940,7 @@ where
                 // Fallback future, ensuring that we never terminate if no additional futures are added to the selector
                 future::pending().boxed(),
             ],
+            shutdown_hook: None,
             dyntype,
             reader,
             config: Default::default(),
@@ -948,6 +1044,7 @@ where
                 // Fallback future, ensuring that we never terminate if no additional futures are added to the selector
                 future::pending().boxed(),
             ],
+            shutdown_hook: None,
             dyntype,
             reader,
             config: Default::default(),
@@ -1580,7 +1677,7 @@ where
     ///
     /// - No new reconciliations are started from the scheduler
     /// - The underlying Kubernetes watch is terminated
-    /// - All running reconciliations are allowed to finish
+    /// - All running reconciliations are allowed to finish (or are cancelled once the [`Config::drain_timeout`] expires)
     /// - [`Controller::run`]'s [`Stream`] terminates once all running reconciliations are done.
     ///
     /// For example, to stop the reconciler whenever the user presses Ctrl+C:
@@ -1675,6 +1772,56 @@ where
         self
     }
 
+    /// Call `hook` with a [`ShutdownReport`] once a graceful shutdown has finished draining
+    ///
+    /// The report lists the objects that were still being reconciled when the shutdown started,
+    /// the ones that were cancelled because they outlived the [drain timeout](Config::drain_timeout),
+    /// and the ones that were queued but never reconciled, along with how long the drain took.
+    ///
+    /// For example, to log the unfinished work before exiting:
+    ///
+    /// ```no_run
+    /// # async {
+    /// use futures::StreamExt;
+    /// use k8s_openapi::api::core::v1::ConfigMap;
+    /// use kube::{Api, Client};
+    /// use kube_runtime::{
+    ///     controller::{Action, Config, Controller},
+    ///     watcher,
+    /// };
+    /// use std::{convert::Infallible, sync::Arc, time::Duration};
+    /// Controller::new(
+    ///     Api::<ConfigMap>::all(Client::try_default().await.unwrap()),
+    ///     watcher::Config::default(),
+    /// )
+    /// .with_config(Config::default().drain_timeout(Duration::from_secs(20)))
+    /// .shutdown_on_signal()
+    /// .on_shutdown(|report| {
+    ///     tracing::info!(
+    ///         cancelled = ?report.cancelled,
+    ///         dropped = ?report.dropped,
+    ///         "drained in {:?}",
+    ///         report.drain_duration
+    ///     );
+    /// })
+    /// .run(
+    ///     |_, _| async move { Ok(Action::await_change()) },
+    ///     |_, err: &Infallible, _| Err(err).unwrap(),
+    ///     Arc::new(()),
+    /// )
+    /// .for_each(|_| std::future::ready(()))
+    /// .await;
+    /// # };
+    /// ```
+    ///
+    /// The hook is not called if the [`Controller`] is terminated by a forceful shutdown, or if the
+    /// [`Stream`] returned by [`Controller::run`] is dropped before it has finished.
+    #[must_use]
+    pub fn on_shutdown(mut self, hook: impl FnOnce(ShutdownReport<K>) + Send + 'static) -> Self {
+        self.shutdown_hook = Some(Box::new(hook));
+        self
+    }
+
     /// Consume all the parameters of the Controller and start the applier stream
     ///
     /// This creates a stream from all builder calls and starts an applier with
@@ -1691,7 +1838,7 @@ where
         ReconcilerFut: TryFuture<Ok = Action> + Send + 'static,
         ReconcilerFut::Error: std::error::Error + Send + 'static,
     {
-        applier(
+        applier_with_hooks(
             move |obj, ctx| {
                 CancelableJoinHandle::spawn(
                     TryFutureExt::into_future(reconciler(obj, ctx)).in_current_span(),
@@ -1704,6 +1851,8 @@ where
             StreamBackoff::new(self.trigger_selector, self.trigger_backoff)
                 .take_until(future::select_all(self.graceful_shutdown_selector)),
             self.config,
+            self.shutdown_hook,
+            None,
         )
         .take_until(futures::future::select_all(self.forceful_shutdown_selector))
     }
@@ -1713,7 +1862,7 @@ where
 mod tests {
     use std::{convert::Infallible, pin::pin, sync::Arc, time::Duration};
 
-    use super::{APPLIER_REQUEUE_BUF_SIZE, Action};
+    use super::{APPLIER_REQUEUE_BUF_SIZE, Action, applier_with_hooks};
     use crate::{
         Config, Controller, applier,
         reflector::{self, ObjectRef},
@@ -1836,4 +1985,50 @@ mod tests {
         .expect("applier cleanup timeout expired, individual reconciler likely deadlocked?")
         .unwrap();
     }
+
+    #[tokio::test]
+    async fn applier_should_report_cancelled_reconciles_on_shutdown() {
+        tokio::time::pause();
+        let (report_tx, report_rx) = futures::channel::oneshot::channel();
+        let (queue_tx, queue_rx) = futures::channel::mpsc::unbounded::<ObjectRef<ConfigMap>>();
+        let (store_rx, mut store_tx) = reflector::store();
+        let mut applier = pin!(applier_with_hooks(
+            |_obj, _| Box::pin(futures::future::pending::<Result<Action, Infallible>>()),
+            |_: Arc<ConfigMap>, _: &Infallible, _| todo!(),
+            Arc::new(()),
+            store_rx,
+            queue_rx.map(Result::<_, Infallible>::Ok),
+            Config::default().drain_timeout(Duration::from_secs(5)),
+            Some(Box::new(move |report| {
+                let _ = report_tx.send(report);
+            })),
+            None,
+        ));
+        store_tx.apply_watcher_event(&watcher::Event::InitDone);
+        let obj = ConfigMap {
+            metadata: ObjectMeta {
+                name: Some("cm".to_string()),
+                namespace: Some("default".to_string()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        store_tx.apply_watcher_event(&watcher::Event::Apply(obj.clone()));
+        queue_tx.unbounded_send(ObjectRef::from_obj(&obj)).unwrap();
+        assert!(futures::poll!(applier.next()).is_pending());
+
+        drop(queue_tx);
+        timeout(
+            Duration::from_secs(10),
+            applier.try_for_each(|_| async { Ok(()) }),
+        )
+        .await
+        .expect("applier should terminate once the drain timeout expires")
+        .unwrap();
+        let report = report_rx.await.unwrap();
+        assert_eq!(report.in_flight, vec![ObjectRef::from_obj(&obj)]);
+        assert_eq!(report.cancelled, vec![ObjectRef::from_obj(&obj)]);
+        assert!(report.dropped.is_empty());
+        assert!(report.drain_duration >= Duration::from_secs(5));
+    }
 }
@@ -8,8 +8,10 @@ use std::{
     hash::Hash,
     pin::Pin,
     task::{Context, Poll},
+    time::Duration,
 };
 use thiserror::Error;
+use tokio::time::{Instant, Sleep, sleep};
 
 #[derive(Debug, Error)]
 pub enum Error<ReadyErr> {
@@ -17,12 +19,39 @@ pub enum Error<ReadyErr> {
     Readiness(#[source] ReadyErr),
 }
 
+/// Summary of the work that was left once a [`Runner`] finished draining
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DrainReport<T> {
+    /// Items that were running when the drain started
+    pub in_flight: Vec<T>,
+    /// Items that were still running when the drain deadline expired, and were cancelled
+    pub cancelled: Vec<T>,
+    /// Items that were scheduled (or held pending) but never started
+    pub dropped: Vec<T>,
+    /// How long it took from the start of the drain until the [`Runner`] terminated
+    pub duration: Duration,
+}
+
+/// Callback that is invoked with the [`DrainReport`] once a [`Runner`] has finished draining
+pub type DrainHook<T> = Box<dyn FnOnce(DrainReport<T>) + Send>;
+
+/// Bookkeeping for a [`Runner`] that has stopped accepting new items
+struct Drain<T> {
+    started_at: Instant,
+    in_flight: Vec<T>,
+    dropped: Vec<T>,
+}
+
 /// Pulls items from a [`Scheduler`], and runs an action for each item in parallel,
 /// while making sure to not process [equal](`Eq`) items multiple times at once.
 ///
 /// If an item is to be emitted from the [`Scheduler`] while an equal item is
 /// already being processed then it will be held pending until the current item
 /// is finished.
+///
+/// Once the [`Scheduler`] terminates, the [`Runner`] starts draining: no new items are
+/// started, and it terminates once all running items have finished (or once the drain
+/// timeout has expired, whichever comes first).
 #[pin_project]
 pub struct Runner<T, R, F, MkF, Ready = future::Ready<Result<(), Infallible>>> {
     #[pin]
@@ -34,6 +63,11 @@ pub struct Runner<T, R, F, MkF, Ready = future::Ready<Result<(), Infallible>>> {
     is_ready_to_execute: bool,
     stopped: bool,
     max_concurrent_executions: u16,
+    drain_timeout: Option<Duration>,
+    // Boxed to keep the `Runner` `Unpin`
+    drain_deadline: Option<Pin<Box<Sleep>>>,
+    drain: Option<Drain<T>>,
+    on_drained: Option<DrainHook<T>>,
 }
 
 impl<T, R, F, MkF> Runner<T, R, F, MkF>
@@ -53,6 +87,10 @@ where
             is_ready_to_execute: false,
             stopped: false,
             max_concurrent_executions,
+            drain_timeout: None,
+            drain_deadline: None,
+            drain: None,
+            on_drained: None,
         }
     }
 
@@ -74,10 +112,33 @@ where
             is_ready_to_execute: false,
             stopped: false,
             max_concurrent_executions: self.max_concurrent_executions,
+            drain_timeout: self.drain_timeout,
+            drain_deadline: None,
+            drain: None,
+            on_drained: self.on_drained,
         }
     }
 }
 
+impl<T, R, F, MkF, Ready> Runner<T, R, F, MkF, Ready> {
+    /// Cancel all running items if they have not finished within `timeout` after the [`Runner`] starts draining.
+    ///
+    /// `None` (the default) waits for running items indefinitely.
+    pub fn drain_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.drain_timeout = timeout;
+        self
+    }
+
+    /// Call `hook` with a [`DrainReport`] once the [`Runner`] has finished draining.
+    ///
+    /// The hook is not called if the [`Runner`] is dropped before the drain has finished,
+    /// or if it terminates because the readiness gate failed.
+    pub fn on_drained(mut self, hook: Option<DrainHook<T>>) -> Self {
+        self.on_drained = hook;
+        self
+    }
+}
+
 impl<T, R, F, MkF, Ready, ReadyErr> Stream for Runner<T, R, F, MkF, Ready>
 where
     T: Eq + Hash + Clone + Unpin,
@@ -100,6 +161,18 @@ where
             Poll::Ready(None) => false,
             Poll::Pending => true,
         };
+        if let Some(deadline) = this.drain_deadline
+            && deadline.as_mut().poll(cx).is_ready()
+        {
+            tracing::debug!(
+                cancelled = slots.len(),
+                "runner drain timeout expired, cancelling running tasks"
+            );
+            let cancelled = slots.cancel_all();
+            *this.stopped = true;
+            finish_drain(this.drain, this.on_drained, cancelled);
+            return Poll::Ready(None);
+        }
         match this.ready_to_execute_after.poll(cx) {
             Poll::Ready(Ok(())) => *this.is_ready_to_execute = true,
             Poll::Ready(Err(err)) => {
@@ -117,7 +190,20 @@ where
                 || !*this.is_ready_to_execute
             {
                 match scheduler.as_mut().hold().poll_next_unpin(cx) {
-                    Poll::Pending | Poll::Ready(None) => break Poll::Pending,
+                    Poll::Pending => break Poll::Pending,
+                    Poll::Ready(None) => {
+                        if this.drain.is_none() {
+                            start_drain(
+                                scheduler.as_mut(),
+                                slots,
+                                this.drain,
+                                *this.drain_timeout,
+                                this.drain_deadline,
+                                cx,
+                            );
+                        }
+                        break Poll::Pending;
+                    }
                     // The above future never returns Poll::Ready(Some(_)).
                     Poll::Ready(_) => unreachable!(),
                 }
@@ -140,11 +226,23 @@ where
                     cx.waker().wake_by_ref();
                 }
                 Poll::Ready(None) => {
+                    if this.drain.is_none() {
+                        start_drain(
+                            scheduler.as_mut(),
+                            slots,
+                            this.drain,
+                            *this.drain_timeout,
+                            this.drain_deadline,
+                            cx,
+                        );
+                    }
                     break if has_active_slots {
                         // We're done listening for new messages, but still have some that
                         // haven't finished quite yet
                         Poll::Pending
                     } else {
+                        *this.stopped = true;
+                        finish_drain(this.drain, this.on_drained, Vec::new());
                         Poll::Ready(None)
                     };
                 }
@@ -154,9 +252,51 @@ where
     }
 }
 
+/// Records the state of the [`Runner`] at the point where its [`Scheduler`] terminated
+fn start_drain<T, R, F>(
+    scheduler: Pin<&mut Scheduler<T, R>>,
+    slots: &FutureHashMap<T, F>,
+    drain: &mut Option<Drain<T>>,
+    drain_timeout: Option<Duration>,
+    drain_deadline: &mut Option<Pin<Box<Sleep>>>,
+    cx: &mut Context<'_>,
+) where
+    T: Eq + Hash + Clone,
+    R: Stream<Item = ScheduleRequest<T>>,
+{
+    let dropped = scheduler.take_remaining();
+    tracing::debug!(
+        in_flight = slots.len(),
+        dropped = dropped.len(),
+        "runner scheduler terminated, draining running tasks"
+    );
+    *drain = Some(Drain {
+        started_at: Instant::now(),
+        in_flight: slots.keys().cloned().collect(),
+        dropped,
+    });
+    if let Some(timeout) = drain_timeout {
+        *drain_deadline = Some(Box::pin(sleep(timeout)));
+        // Make sure that the new deadline gets polled (and registers its waker)
+        cx.waker().wake_by_ref();
+    }
+}
+
+/// Hands the final [`DrainReport`] to the hook, if one was registered
+fn finish_drain<T>(drain: &mut Option<Drain<T>>, on_drained: &mut Option<DrainHook<T>>, cancelled: Vec<T>) {
+    if let (Some(drain), Some(on_drained)) = (drain.take(), on_drained.take()) {
+        on_drained(DrainReport {
+            in_flight: drain.in_flight,
+            cancelled,
+            dropped: drain.dropped,
+            duration: drain.started_at.elapsed(),
+        });
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{Error, Runner};
+    use super::{DrainReport, Error, Runner};
     use crate::{
         scheduler::{ScheduleRequest, scheduler},
         utils::delayed_init::{self, DelayedInit},
@@ -480,4 +620,80 @@ mod tests {
         drop(sched_tx);
         assert_eq!(poll!(runner.as_mut()), Poll::Pending);
     }
+
+    #[tokio::test]
+    async fn runner_should_report_unfinished_work_after_draining() {
+        pause();
+        let report = Arc::new(Mutex::new(None));
+        let (mut sched_tx, sched_rx) = mpsc::unbounded();
+        let mut runner = Box::pin(
+            Runner::new(scheduler(sched_rx), 1, |msg: &u8| {
+                DurationalFuture::new(Duration::from_secs(u64::from(*msg) * 10))
+            })
+            .drain_timeout(Some(Duration::from_secs(15)))
+            .on_drained(Some(Box::new({
+                let report = report.clone();
+                move |r| *report.lock().unwrap() = Some(r)
+            }))),
+        );
+        for msg in [1, 3] {
+            sched_tx
+                .send(ScheduleRequest {
+                    message: msg,
+                    run_at: Instant::now(),
+                })
+                .await
+                .unwrap();
+            assert!(poll!(runner.next()).is_pending());
+        }
+        // Shut down while 1 is running and 3 is waiting for a free slot
+        drop(sched_tx);
+        assert!(poll!(runner.next()).is_pending());
+        advance(Duration::from_secs(11)).await;
+        assert!(matches!(poll!(runner.next()), Poll::Ready(Some(Ok(())))));
+        assert!(report.lock().unwrap().is_none());
+        // 3 was never started, so there is nothing left to wait for
+        assert!(matches!(poll!(runner.next()), Poll::Ready(None)));
+        let report = report.lock().unwrap().take().unwrap();
+        assert_eq!(report, DrainReport {
+            in_flight: vec![1],
+            cancelled: Vec::new(),
+            dropped: vec![3],
+            duration: report.duration,
+        });
+        assert!(report.duration >= Duration::from_secs(11));
+    }
+
+    #[tokio::test]
+    async fn runner_should_report_cancelled_tasks() {
+        pause();
+        let report = Arc::new(Mutex::new(None));
+        let (mut sched_tx, sched_rx) = mpsc::unbounded();
+        let mut runner = Box::pin(
+            Runner::new(scheduler(sched_rx), 0, |_: &u8| {
+                DurationalFuture::new(Duration::from_secs(60))
+            })
+            .drain_timeout(Some(Duration::from_secs(5)))
+            .on_drained(Some(Box::new({
+                let report = report.clone();
+                move |r| *report.lock().unwrap() = Some(r)
+            }))),
+        );
+        sched_tx
+            .send(ScheduleRequest {
+                message: 1,
+                run_at: Instant::now(),
+            })
+            .await
+            .unwrap();
+        assert!(poll!(runner.next()).is_pending());
+        drop(sched_tx);
+        assert!(poll!(runner.next()).is_pending());
+        advance(Duration::from_secs(6)).await;
+        assert!(matches!(poll!(runner.next()), Poll::Ready(None)));
+        let report = report.lock().unwrap().take().unwrap();
+        assert_eq!(report.in_flight, vec![1]);
+        assert_eq!(report.cancelled, vec![1]);
+        assert!(report.dropped.is_empty());
+    }
 }
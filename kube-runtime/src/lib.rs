@@ -25,8 +25,10 @@ pub mod controller;
 pub mod events;
 
 pub mod finalizer;
+#[cfg(feature = "unstable-runtime-manifests")] pub mod manifests;
 pub mod reflector;
 pub mod scheduler;
+#[cfg(feature = "unstable-runtime-testing")] pub mod testing;
 pub mod utils;
 pub mod wait;
 pub mod watcher;
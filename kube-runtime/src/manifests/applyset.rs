@@ -0,0 +1,521 @@
+//! [ApplySets] group the objects applied together, so the ones dropped from the manifests can be pruned
+//!
+//! A set is recorded on a parent object, by default a `Secret`, which lists the kinds and namespaces
+//! of its members in annotations. Every member is labelled with the id of the set, which lets the objects
+//! that were not applied again be found and deleted.
+//!
+//! This follows the specification that `kubectl apply --applyset` implements, so sets can be managed by both
+//! as long as they agree on the [tooling](ApplySet::tooling).
+//!
+//! ```no_run
+//! use kube::runtime::manifests::{self, ApplySet, ManifestApplier};
+//! # async fn wrapper() -> Result<(), Box<dyn std::error::Error>> {
+//! # let client: kube::Client = todo!();
+//! let objects = manifests::parse(&std::fs::read_to_string("deploy.yaml")?)?;
+//! let report = ManifestApplier::new(client, "my-tool")
+//!     .applyset(ApplySet::secret("my-app", "default"))
+//!     .apply(objects)
+//!     .await;
+//! for pruned in &report.pruned {
+//!     println!("pruned {pruned}");
+//! }
+//! report.into_result()?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [ApplySets]: https://github.com/kubernetes/enhancements/tree/master/keps/sig-cli/3659-kubectl-apply-prune
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use k8s_openapi::api::core::v1::{ConfigMap, Secret};
+use kube_client::{
+    Api, Client, ResourceExt,
+    api::{DeleteParams, DynamicObject, ListParams, Patch},
+    core::{GroupVersion, GroupVersionKind},
+    discovery::{self, ApiCapabilities, ApiGroup, ApiResource, Scope},
+    error::DiscoveryError,
+};
+use sha2::{Digest, Sha256};
+
+use super::{Error, ManifestApplier, Outcome, Report};
+
+/// Label on the parent holding the id of the set
+pub const ID_LABEL: &str = "applyset.kubernetes.io/id";
+/// Label on every member holding the id of its set
+pub const PART_OF_LABEL: &str = "applyset.kubernetes.io/part-of";
+/// Annotation on the parent naming the tool that manages the set, as `name/version`
+pub const TOOLING_ANNOTATION: &str = "applyset.kubernetes.io/tooling";
+/// Annotation on the parent listing the kinds of the members, as `Kind.group`
+pub const CONTAINS_GROUP_KINDS_ANNOTATION: &str = "applyset.kubernetes.io/contains-group-kinds";
+/// Annotation on the parent listing the namespaces of the members, besides the namespace of the parent
+pub const ADDITIONAL_NAMESPACES_ANNOTATION: &str = "applyset.kubernetes.io/additional-namespaces";
+
+/// A set of objects applied together, recorded on a parent object
+#[derive(Clone, Debug)]
+pub struct ApplySet {
+    resource: ApiResource,
+    name: String,
+    namespace: Option<String>,
+    tooling: String,
+    id: String,
+}
+
+impl ApplySet {
+    /// A set recorded on the parent `name` of the `resource`, in `namespace` unless it is cluster-scoped
+    ///
+    /// Custom parent types need to be marked as such with the `applyset.kubernetes.io/is-parent-type` label
+    /// on their `CustomResourceDefinition` to be usable by `kubectl`.
+    #[must_use]
+    pub fn new(resource: ApiResource, name: &str, namespace: Option<&str>) -> Self {
+        let key = [
+            name,
+            namespace.unwrap_or_default(),
+            &resource.kind,
+            &resource.group,
+        ]
+        .join(".");
+        Self {
+            id: format!("applyset-{}-v1", URL_SAFE_NO_PAD.encode(Sha256::digest(key))),
+            resource,
+            name: name.to_owned(),
+            namespace: namespace.map(ToOwned::to_owned),
+            tooling: concat!("kube/v", env!("CARGO_PKG_VERSION")).to_owned(),
+        }
+    }
+
+    /// A set recorded on a `Secret`
+    #[must_use]
+    pub fn secret(name: &str, namespace: &str) -> Self {
+        Self::new(ApiResource::erase::<Secret>(&()), name, Some(namespace))
+    }
+
+    /// A set recorded on a `ConfigMap`
+    #[must_use]
+    pub fn config_map(name: &str, namespace: &str) -> Self {
+        Self::new(ApiResource::erase::<ConfigMap>(&()), name, Some(namespace))
+    }
+
+    /// The tool managing the set, as `name/version`
+    ///
+    /// Sets managed by a tool with another name are refused. Defaults to `kube/v<version>`, and needs to be
+    /// `kubectl/<version>` for `kubectl` to manage the same set.
+    #[must_use]
+    pub fn tooling(mut self, tooling: &str) -> Self {
+        tooling.clone_into(&mut self.tooling);
+        self
+    }
+
+    /// The id of the set, as set on the parent and the members
+    #[must_use]
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn api(&self, client: Client) -> Api<DynamicObject> {
+        match &self.namespace {
+            Some(namespace) => Api::namespaced_with(client, namespace, &self.resource),
+            None => Api::all_with(client, &self.resource),
+        }
+    }
+}
+
+/// The kinds and namespaces of the members of a set
+#[derive(Debug, Default)]
+pub(super) struct Inventory {
+    group_kinds: BTreeSet<String>,
+    namespaces: BTreeSet<String>,
+}
+
+impl Inventory {
+    fn from_parent(applyset: &ApplySet, parent: &DynamicObject) -> Result<Self, Error> {
+        if let Some(id) = parent.labels().get(ID_LABEL)
+            && *id != applyset.id
+        {
+            return Err(Error::ApplySetConflict(format!(
+                "parent belongs to applyset {id}"
+            )));
+        }
+        let annotations = parent.annotations();
+        if let Some(tooling) = annotations.get(TOOLING_ANNOTATION)
+            && tool_name(tooling) != tool_name(&applyset.tooling)
+        {
+            return Err(Error::ApplySetConflict(format!(
+                "applyset is managed by {tooling}"
+            )));
+        }
+        let list = |annotation| {
+            annotations
+                .get(annotation)
+                .into_iter()
+                .flat_map(|value| value.split(','))
+                .filter(|item| !item.is_empty())
+                .map(ToOwned::to_owned)
+                .collect()
+        };
+        Ok(Self {
+            group_kinds: list(CONTAINS_GROUP_KINDS_ANNOTATION),
+            namespaces: list(ADDITIONAL_NAMESPACES_ANNOTATION),
+        })
+    }
+
+    fn union(&self, other: &Self) -> Self {
+        Self {
+            group_kinds: self.group_kinds.union(&other.group_kinds).cloned().collect(),
+            namespaces: self.namespaces.union(&other.namespaces).cloned().collect(),
+        }
+    }
+}
+
+fn tool_name(tooling: &str) -> &str {
+    tooling.split_once('/').map_or(tooling, |(name, _)| name)
+}
+
+/// Format a kind as `Kind.group`, or `Kind` in the core group
+fn group_kind(group: &str, kind: &str) -> String {
+    if group.is_empty() {
+        kind.to_owned()
+    } else {
+        format!("{kind}.{group}")
+    }
+}
+
+/// The group and kind of a `Kind.group`
+fn split_group_kind(group_kind: &str) -> (&str, &str) {
+    group_kind
+        .split_once('.')
+        .map_or(("", group_kind), |(kind, group)| (group, kind))
+}
+
+impl ManifestApplier {
+    /// Label the members and widen the parent to them before anything is applied, so an interrupted apply
+    /// can still prune them
+    ///
+    /// Returns the inventory the parent had.
+    pub(super) async fn prepare_applyset(
+        &self,
+        applyset: &ApplySet,
+        objects: &mut [DynamicObject],
+    ) -> Result<Inventory, Error> {
+        let parent = applyset
+            .api(self.client.clone())
+            .get_opt(&applyset.name)
+            .await
+            .map_err(Error::ApplySetParent)?;
+        let previous = match &parent {
+            Some(parent) => Inventory::from_parent(applyset, parent)?,
+            None => Inventory::default(),
+        };
+
+        let mut members = Inventory::default();
+        for obj in objects.iter_mut() {
+            obj.labels_mut()
+                .insert(PART_OF_LABEL.to_owned(), applyset.id.clone());
+            if let Some(types) = &obj.types
+                && let Ok(gvk) = GroupVersionKind::try_from(types)
+            {
+                members.group_kinds.insert(group_kind(&gvk.group, &gvk.kind));
+            }
+            // The scope is not resolved yet, so cluster-scoped objects may add the default namespace
+            let namespace = obj.metadata.namespace.as_ref().unwrap_or(&self.namespace);
+            members.namespaces.insert(namespace.clone());
+        }
+        self.update_parent(applyset, &previous.union(&members)).await?;
+        Ok(previous)
+    }
+
+    /// Delete the members of the set that were not applied, and narrow the parent to the applied ones
+    pub(super) async fn prune_applyset(
+        &self,
+        applyset: &ApplySet,
+        previous: Inventory,
+        groups: &mut HashMap<GroupVersion, ApiGroup>,
+        report: &mut Report,
+    ) {
+        let mut current = Inventory::default();
+        let mut applied = HashSet::new();
+        for outcome in &report.outcomes {
+            if let Ok(obj) = &outcome.result
+                && let Some(types) = &obj.types
+                && let Ok(gvk) = GroupVersionKind::try_from(types)
+            {
+                let gk = group_kind(&gvk.group, &gvk.kind);
+                applied.insert((gk.clone(), outcome.namespace.clone(), outcome.name.clone()));
+                current.group_kinds.insert(gk);
+                current.namespaces.extend(outcome.namespace.clone());
+            }
+        }
+
+        let namespaces: BTreeSet<_> = previous
+            .namespaces
+            .iter()
+            .chain(&current.namespaces)
+            .chain(&applyset.namespace)
+            .collect();
+        let lp = ListParams::default().labels(&format!("{PART_OF_LABEL}={}", applyset.id));
+        let dp = DeleteParams {
+            dry_run: self.params.dry_run,
+            ..DeleteParams::default()
+        };
+        for gk in previous.group_kinds.union(&current.group_kinds) {
+            let (group, kind) = split_group_kind(gk);
+            let (ar, caps) = match self.resolve_group_kind(group, kind, groups).await {
+                Ok(resolved) => resolved,
+                Err(err) => {
+                    report.errors.push(Error::Prune(err));
+                    continue;
+                }
+            };
+            let apis = match caps.scope {
+                Scope::Cluster => vec![(None, Api::all_with(self.client.clone(), &ar))],
+                Scope::Namespaced => namespaces
+                    .iter()
+                    .map(|ns| {
+                        let api: Api<DynamicObject> = Api::namespaced_with(self.client.clone(), ns, &ar);
+                        (Some((*ns).clone()), api)
+                    })
+                    .collect(),
+            };
+            for (namespace, api) in apis {
+                let members = match api.list(&lp).await {
+                    Ok(members) => members,
+                    Err(err) => {
+                        report.errors.push(Error::Prune(err));
+                        continue;
+                    }
+                };
+                for obj in members {
+                    let name = obj.name_any();
+                    if applied.contains(&(gk.clone(), namespace.clone(), name.clone())) {
+                        continue;
+                    }
+                    tracing::debug!("pruning {kind} {name}");
+                    let result = api.delete(&name, &dp).await.map(|_| obj).map_err(Error::Prune);
+                    report.pruned.push(Outcome {
+                        kind: kind.to_owned(),
+                        name,
+                        namespace: namespace.clone(),
+                        result,
+                    });
+                }
+            }
+        }
+
+        if report.errors.is_empty()
+            && report.failed().next().is_none()
+            && let Err(err) = self.update_parent(applyset, &current).await
+        {
+            report.errors.push(err);
+        }
+    }
+
+    async fn update_parent(&self, applyset: &ApplySet, inventory: &Inventory) -> Result<(), Error> {
+        let mut parent = DynamicObject::new(&applyset.name, &applyset.resource);
+        parent.metadata.namespace.clone_from(&applyset.namespace);
+        parent
+            .labels_mut()
+            .insert(ID_LABEL.to_owned(), applyset.id.clone());
+        let annotations = parent.annotations_mut();
+        annotations.insert(TOOLING_ANNOTATION.to_owned(), applyset.tooling.clone());
+        let group_kinds = inventory.group_kinds.iter().cloned().collect::<Vec<_>>();
+        annotations.insert(CONTAINS_GROUP_KINDS_ANNOTATION.to_owned(), group_kinds.join(","));
+        let namespaces = inventory
+            .namespaces
+            .iter()
+            .filter(|ns| applyset.namespace.as_ref() != Some(*ns))
+            .cloned()
+            .collect::<Vec<_>>();
+        if !namespaces.is_empty() {
+            annotations.insert(ADDITIONAL_NAMESPACES_ANNOTATION.to_owned(), namespaces.join(","));
+        }
+        applyset
+            .api(self.client.clone())
+            .patch(&applyset.name, &self.params, &Patch::Apply(&parent))
+            .await
+            .map_err(Error::ApplySetParent)?;
+        Ok(())
+    }
+
+    /// Resolve a kind without a version, like the ones recorded on the parent
+    async fn resolve_group_kind(
+        &self,
+        group: &str,
+        kind: &str,
+        groups: &mut HashMap<GroupVersion, ApiGroup>,
+    ) -> kube_client::Result<(ApiResource, ApiCapabilities)> {
+        let known = self
+            .discovery
+            .as_ref()
+            .and_then(|discovery| discovery.get(group))
+            .into_iter()
+            .chain(groups.iter().filter(|(gv, _)| gv.group == group).map(|(_, g)| g))
+            .find_map(|g| g.recommended_kind(kind));
+        if let Some(found) = known {
+            return Ok(found);
+        }
+        let api_group = discovery::group(&self.client, group).await?;
+        let found = api_group.recommended_kind(kind);
+        let gv = GroupVersion::gv(group, api_group.preferred_version_or_latest());
+        groups.insert(gv, api_group);
+        found.ok_or_else(|| {
+            kube_client::Error::Discovery(DiscoveryError::MissingKind(group_kind(group, kind)))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use http::{Method, Request, Response, StatusCode};
+    use kube_client::client::Body;
+    use serde_json::json;
+    use tower_test::mock;
+
+    #[test]
+    fn id_matches_kubectl() {
+        let applyset = ApplySet::secret("set", "default");
+        assert_eq!(
+            applyset.id(),
+            "applyset-GzV5QNw0hpfp90Rc5ckxJmVoyyU-gDZeLYpJFDTwDHA-v1"
+        );
+        assert_eq!(split_group_kind("Deployment.apps"), ("apps", "Deployment"));
+        assert_eq!(split_group_kind("Secret"), ("", "Secret"));
+        assert_eq!(group_kind("apps", "Deployment"), "Deployment.apps");
+    }
+
+    fn configmap(name: &str, id: &str) -> serde_json::Value {
+        json!({
+            "apiVersion": "v1",
+            "kind": "ConfigMap",
+            "metadata": { "name": name, "namespace": "default", "labels": { PART_OF_LABEL: id } },
+        })
+    }
+
+    async fn body_json(request: Request<Body>) -> serde_json::Value {
+        serde_json::from_slice(&request.into_body().collect_bytes().await.unwrap()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn prunes_members_dropped_from_the_set() {
+        let applyset = ApplySet::secret("set", "default");
+        let id = applyset.id().to_owned();
+        let (mock_service, handle) = mock::pair::<Request<Body>, Response<Body>>();
+        let spawned = tokio::spawn(async move {
+            let mut handle = std::pin::pin!(handle);
+            let mut requests = Vec::new();
+            let mut parents = Vec::new();
+            while let Some((request, send)) = handle.next_request().await {
+                let (method, path) = (request.method().clone(), request.uri().path().to_owned());
+                requests.push(format!("{method} {path}"));
+                let (status, body) = match (method, path.as_str()) {
+                    (Method::GET, "/api/v1/namespaces/default/secrets/set") => (
+                        StatusCode::OK,
+                        json!({
+                            "apiVersion": "v1",
+                            "kind": "Secret",
+                            "metadata": { "name": "set", "namespace": "default", "annotations": {
+                                TOOLING_ANNOTATION: "kube/v0.1.0",
+                                CONTAINS_GROUP_KINDS_ANNOTATION: "ConfigMap",
+                            }},
+                        }),
+                    ),
+                    (Method::PATCH, "/api/v1/namespaces/default/secrets/set") => {
+                        let parent = body_json(request).await;
+                        parents.push(parent["metadata"]["annotations"].clone());
+                        (StatusCode::OK, parent)
+                    }
+                    (Method::GET, "/api/v1") => (
+                        StatusCode::OK,
+                        json!({
+                            "kind": "APIResourceList",
+                            "groupVersion": "v1",
+                            "resources": [{ "name": "configmaps", "singularName": "configmap", "namespaced": true, "kind": "ConfigMap", "verbs": ["list", "patch", "delete"] }],
+                        }),
+                    ),
+                    (Method::PATCH, "/api/v1/namespaces/default/configmaps/keep") => {
+                        let obj = body_json(request).await;
+                        assert_eq!(obj["metadata"]["labels"][PART_OF_LABEL], id.as_str());
+                        (StatusCode::OK, obj)
+                    }
+                    (Method::GET, "/api/v1/namespaces/default/configmaps") => {
+                        let query = request.uri().query().unwrap();
+                        assert!(query.contains("labelSelector=applyset.kubernetes.io%2Fpart-of%3Dapplyset-"));
+                        (
+                            StatusCode::OK,
+                            json!({
+                                "apiVersion": "v1",
+                                "kind": "ConfigMapList",
+                                "metadata": {},
+                                "items": [configmap("keep", &id), configmap("stale", &id)],
+                            }),
+                        )
+                    }
+                    (Method::DELETE, "/api/v1/namespaces/default/configmaps/stale") => {
+                        (StatusCode::OK, configmap("stale", &id))
+                    }
+                    (method, path) => panic!("unexpected request {method} {path}"),
+                };
+                send.send_response(
+                    Response::builder()
+                        .status(status)
+                        .body(Body::from(serde_json::to_vec(&body).unwrap()))
+                        .unwrap(),
+                );
+            }
+            (requests, parents)
+        });
+
+        let client = Client::new(mock_service, "default");
+        let objects = vec![serde_json::from_value(configmap("keep", "")).unwrap()];
+        let report = ManifestApplier::new(client, "tool")
+            .applyset(applyset)
+            .apply(objects)
+            .await;
+        let pruned: Vec<_> = report.pruned.iter().map(ToString::to_string).collect();
+        assert_eq!(pruned, ["ConfigMap default/stale"]);
+        assert!(report.into_result().is_ok());
+
+        let (requests, parents) = spawned.await.unwrap();
+        assert_eq!(requests, [
+            "GET /api/v1/namespaces/default/secrets/set",
+            "PATCH /api/v1/namespaces/default/secrets/set",
+            "GET /api/v1",
+            "PATCH /api/v1/namespaces/default/configmaps/keep",
+            "GET /api/v1/namespaces/default/configmaps",
+            "DELETE /api/v1/namespaces/default/configmaps/stale",
+            "PATCH /api/v1/namespaces/default/secrets/set",
+        ]);
+        assert_eq!(
+            parents[1],
+            json!({
+                TOOLING_ANNOTATION: concat!("kube/v", env!("CARGO_PKG_VERSION")),
+                CONTAINS_GROUP_KINDS_ANNOTATION: "ConfigMap",
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn refuses_sets_of_other_tools() {
+        let (mock_service, handle) = mock::pair::<Request<Body>, Response<Body>>();
+        let spawned = tokio::spawn(async move {
+            let mut handle = std::pin::pin!(handle);
+            let (_, send) = handle.next_request().await.expect("service not called");
+            let parent = json!({
+                "apiVersion": "v1",
+                "kind": "Secret",
+                "metadata": { "name": "set", "namespace": "default", "annotations": { TOOLING_ANNOTATION: "helm/v3" } },
+            });
+            send.send_response(Response::new(Body::from(serde_json::to_vec(&parent).unwrap())));
+        });
+
+        let client = Client::new(mock_service, "default");
+        let report = ManifestApplier::new(client, "tool")
+            .applyset(ApplySet::secret("set", "default"))
+            .apply(vec![serde_json::from_value(configmap("keep", "")).unwrap()])
+            .await;
+        assert!(report.outcomes.is_empty());
+        assert!(matches!(&report.errors[..], [Error::ApplySetConflict(_)]));
+        spawned.await.unwrap();
+    }
+}
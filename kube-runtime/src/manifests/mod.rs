@@ -0,0 +1,489 @@
+//! Applies multi-document YAML manifests, like `kubectl apply -f`
+//!
+//! Manifests are parsed into [`DynamicObject`]s, which are resolved through [`Discovery`] (or
+//! [`discovery::pinned_group`] for kinds it does not know) and server-side applied.
+//!
+//! ```no_run
+//! use kube::runtime::manifests::{self, ManifestApplier};
+//! # async fn wrapper() -> Result<(), Box<dyn std::error::Error>> {
+//! # let client: kube::Client = todo!();
+//! let objects = manifests::parse(&std::fs::read_to_string("deploy.yaml")?)?;
+//! let report = ManifestApplier::new(client, "my-tool").force().apply(objects).await;
+//! for outcome in &report.outcomes {
+//!     match &outcome.result {
+//!         Ok(_) => println!("applied {outcome}"),
+//!         Err(err) => println!("failed to apply {outcome}: {err}"),
+//!     }
+//! }
+//! report.into_result()?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Objects that were dropped from the manifests are only deleted when they are applied as an [`ApplySet`].
+use std::{
+    collections::{HashMap, hash_map::Entry},
+    fmt,
+    time::Duration,
+};
+
+use k8s_openapi::apiextensions_apiserver::pkg::apis::apiextensions::v1::CustomResourceDefinition;
+use kube_client::{
+    Api, Client, Discovery,
+    api::{DynamicObject, Patch, PatchParams},
+    core::{GroupVersion, GroupVersionKind, gvk::ParseGroupVersionError},
+    discovery::{self, ApiCapabilities, ApiGroup, ApiResource, Scope},
+    error::DiscoveryError,
+};
+use thiserror::Error;
+
+use crate::wait::{self, await_condition, conditions};
+
+pub mod applyset;
+pub use applyset::ApplySet;
+
+/// Errors from parsing and applying manifests
+#[derive(Debug, Error)]
+pub enum Error {
+    /// The manifests are not valid YAML objects
+    #[error("failed to parse manifests: {0}")]
+    Parse(#[source] serde_saphyr::Error),
+
+    /// An object has no `apiVersion` or `kind`
+    #[error("object is missing apiVersion or kind")]
+    MissingTypeMeta,
+
+    /// An object has an invalid `apiVersion`
+    #[error("object has an invalid apiVersion: {0}")]
+    InvalidApiVersion(#[source] ParseGroupVersionError),
+
+    /// An object has no `metadata.name`
+    #[error("object is missing metadata.name")]
+    MissingName,
+
+    /// The kind of an object could not be resolved
+    #[error("failed to resolve kind: {0}")]
+    Resolve(#[source] kube_client::Error),
+
+    /// The server-side apply failed
+    #[error("failed to apply: {0}")]
+    Apply(#[source] kube_client::Error),
+
+    /// Waiting for a `CustomResourceDefinition` to be established failed
+    #[error("failed to wait for the CustomResourceDefinition to be established: {0}")]
+    WaitEstablished(#[source] wait::Error),
+
+    /// A `CustomResourceDefinition` was not established in time
+    #[error("timed out waiting for the CustomResourceDefinition to be established")]
+    EstablishTimeout,
+
+    /// The parent of an [`ApplySet`] belongs to another tool or set
+    #[error("applyset parent conflict: {0}")]
+    ApplySetConflict(String),
+
+    /// The parent of an [`ApplySet`] could not be read or updated
+    #[error("failed to update the applyset parent: {0}")]
+    ApplySetParent(#[source] kube_client::Error),
+
+    /// Objects dropped from an [`ApplySet`] could not be listed or deleted
+    #[error("failed to prune: {0}")]
+    Prune(#[source] kube_client::Error),
+
+    /// Some objects failed to apply or prune
+    #[error("failed to apply {}", .0.failed().map(ToString::to_string).chain(.0.errors.iter().map(ToString::to_string)).collect::<Vec<_>>().join(", "))]
+    Failed(Box<Report>),
+}
+
+/// Parse a multi-document YAML manifest into objects
+///
+/// Empty documents are skipped.
+///
+/// # Errors
+///
+/// Fails if a document is not a valid object.
+pub fn parse(yaml: &str) -> Result<Vec<DynamicObject>, Error> {
+    serde_saphyr::from_multiple(yaml).map_err(Error::Parse)
+}
+
+/// The outcome of applying one object
+#[derive(Debug)]
+pub struct Outcome {
+    /// The kind of the object
+    pub kind: String,
+    /// The name of the object
+    pub name: String,
+    /// The namespace the object was applied to, `None` for cluster-scoped objects
+    pub namespace: Option<String>,
+    /// The object returned by the apiserver, or why it could not be applied
+    pub result: Result<DynamicObject, Error>,
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ", self.kind)?;
+        if let Some(namespace) = &self.namespace {
+            write!(f, "{namespace}/")?;
+        }
+        f.write_str(&self.name)
+    }
+}
+
+/// The outcomes of applying a set of objects, in the order they were applied
+#[derive(Debug, Default)]
+pub struct Report {
+    /// The outcome of every applied object
+    pub outcomes: Vec<Outcome>,
+    /// The outcome of every object pruned from the [`ApplySet`]
+    pub pruned: Vec<Outcome>,
+    /// Errors of the [`ApplySet`] that are not about a single object
+    pub errors: Vec<Error>,
+}
+
+impl Report {
+    /// The objects that failed to apply or prune
+    pub fn failed(&self) -> impl Iterator<Item = &Outcome> {
+        self.outcomes
+            .iter()
+            .chain(&self.pruned)
+            .filter(|outcome| outcome.result.is_err())
+    }
+
+    /// The applied objects, or an [`Error::Failed`] with the whole report if anything failed
+    ///
+    /// # Errors
+    ///
+    /// Fails if any object failed to apply or prune, or the [`ApplySet`] could not be updated.
+    pub fn into_result(self) -> Result<Vec<DynamicObject>, Error> {
+        if self.failed().next().is_some() || !self.errors.is_empty() {
+            return Err(Error::Failed(Box::new(self)));
+        }
+        Ok(self
+            .outcomes
+            .into_iter()
+            .filter_map(|outcome| outcome.result.ok())
+            .collect())
+    }
+}
+
+/// Server-side applies sets of objects
+///
+/// Objects are applied in order, except that `Namespace`s are applied first and
+/// `CustomResourceDefinition`s second, so that the objects using them can be applied.
+/// Each applied `CustomResourceDefinition` is waited on until it is established.
+///
+/// Namespaced objects without a namespace are applied to the default namespace of the [`Client`],
+/// and the namespace of cluster-scoped objects is ignored.
+pub struct ManifestApplier {
+    client: Client,
+    discovery: Option<Discovery>,
+    params: PatchParams,
+    namespace: String,
+    establish_timeout: Duration,
+    applyset: Option<ApplySet>,
+}
+
+impl ManifestApplier {
+    /// Apply objects as the `field_manager`
+    #[must_use]
+    pub fn new(client: Client, field_manager: &str) -> Self {
+        Self {
+            namespace: client.default_namespace().to_owned(),
+            client,
+            discovery: None,
+            params: PatchParams::apply(field_manager),
+            establish_timeout: Duration::from_secs(30),
+            applyset: None,
+        }
+    }
+
+    /// Resolve kinds through a [`Discovery`] that has already been run
+    ///
+    /// Kinds it does not know, such as the ones defined by applied `CustomResourceDefinition`s, are still
+    /// resolved with [`discovery::pinned_group`].
+    #[must_use]
+    pub fn discovery(mut self, discovery: Discovery) -> Self {
+        self.discovery = Some(discovery);
+        self
+    }
+
+    /// Apply namespaced objects without a namespace to `namespace`
+    #[must_use]
+    pub fn default_namespace(mut self, namespace: &str) -> Self {
+        namespace.clone_into(&mut self.namespace);
+        self
+    }
+
+    /// Force the apply, taking ownership of conflicting fields
+    #[must_use]
+    pub fn force(mut self) -> Self {
+        self.params = self.params.force();
+        self
+    }
+
+    /// Only validate the objects on the server, without persisting them
+    #[must_use]
+    pub fn dry_run(mut self) -> Self {
+        self.params = self.params.dry_run();
+        self
+    }
+
+    /// How long to wait for each `CustomResourceDefinition` to be established
+    ///
+    /// Defaults to 30 seconds.
+    #[must_use]
+    pub fn establish_timeout(mut self, timeout: Duration) -> Self {
+        self.establish_timeout = timeout;
+        self
+    }
+
+    /// Apply the objects as members of an [`ApplySet`], pruning the members that are no longer applied
+    #[must_use]
+    pub fn applyset(mut self, applyset: ApplySet) -> Self {
+        self.applyset = Some(applyset);
+        self
+    }
+
+    /// Apply the objects, reporting the outcome of each
+    ///
+    /// A failed object does not stop the others from being applied.
+    /// With an [`ApplySet`], objects are only pruned once every object was applied.
+    pub async fn apply(&self, mut objects: Vec<DynamicObject>) -> Report {
+        objects.sort_by_key(|obj| obj.types.as_ref().map_or(2, |types| priority(&types.kind)));
+        let mut groups = HashMap::new();
+        let mut report = Report::default();
+        let Some(applyset) = &self.applyset else {
+            for obj in objects {
+                let outcome = self.apply_one(obj, &mut groups).await;
+                report.outcomes.push(outcome);
+            }
+            return report;
+        };
+
+        let previous = match self.prepare_applyset(applyset, &mut objects).await {
+            Ok(previous) => previous,
+            Err(err) => {
+                report.errors.push(err);
+                return report;
+            }
+        };
+        for obj in objects {
+            let outcome = self.apply_one(obj, &mut groups).await;
+            report.outcomes.push(outcome);
+        }
+        if report.failed().next().is_none() {
+            self.prune_applyset(applyset, previous, &mut groups, &mut report)
+                .await;
+        }
+        report
+    }
+
+    async fn apply_one(
+        &self,
+        mut obj: DynamicObject,
+        groups: &mut HashMap<GroupVersion, ApiGroup>,
+    ) -> Outcome {
+        let mut outcome = Outcome {
+            kind: obj
+                .types
+                .as_ref()
+                .map(|types| types.kind.clone())
+                .unwrap_or_default(),
+            name: obj.metadata.name.clone().unwrap_or_default(),
+            namespace: None,
+            result: Err(Error::MissingTypeMeta),
+        };
+        let Some(types) = &obj.types else {
+            return outcome;
+        };
+        if outcome.name.is_empty() {
+            outcome.result = Err(Error::MissingName);
+            return outcome;
+        }
+        let gvk = match GroupVersionKind::try_from(types) {
+            Ok(gvk) => gvk,
+            Err(err) => {
+                outcome.result = Err(Error::InvalidApiVersion(err));
+                return outcome;
+            }
+        };
+        let (ar, caps) = match self.resolve(&gvk, groups).await {
+            Ok(resolved) => resolved,
+            Err(err) => {
+                outcome.result = Err(Error::Resolve(err));
+                return outcome;
+            }
+        };
+
+        let api = match caps.scope {
+            Scope::Namespaced => {
+                let namespace = obj
+                    .metadata
+                    .namespace
+                    .get_or_insert_with(|| self.namespace.clone());
+                outcome.namespace = Some(namespace.clone());
+                Api::namespaced_with(self.client.clone(), namespace, &ar)
+            }
+            Scope::Cluster => {
+                obj.metadata.namespace = None;
+                Api::all_with(self.client.clone(), &ar)
+            }
+        };
+        tracing::debug!("applying {outcome}");
+        outcome.result = api
+            .patch(&outcome.name, &self.params, &Patch::Apply(&obj))
+            .await
+            .map_err(Error::Apply);
+        if outcome.result.is_ok()
+            && gvk.group == "apiextensions.k8s.io"
+            && gvk.kind == "CustomResourceDefinition"
+            && let Err(err) = self.wait_established(&outcome.name).await
+        {
+            outcome.result = Err(err);
+        }
+        outcome
+    }
+
+    async fn resolve(
+        &self,
+        gvk: &GroupVersionKind,
+        groups: &mut HashMap<GroupVersion, ApiGroup>,
+    ) -> kube_client::Result<(ApiResource, ApiCapabilities)> {
+        if let Some(found) = self
+            .discovery
+            .as_ref()
+            .and_then(|discovery| discovery.resolve_gvk(gvk))
+        {
+            return Ok(found);
+        }
+        // Kinds of the same group version are resolved by a single discovery request
+        let gv = GroupVersion::gv(&gvk.group, &gvk.version);
+        let group = match groups.entry(gv) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => {
+                let group = discovery::pinned_group(&self.client, entry.key()).await?;
+                entry.insert(group)
+            }
+        };
+        group
+            .recommended_kind(&gvk.kind)
+            .ok_or_else(|| kube_client::Error::Discovery(DiscoveryError::MissingKind(format!("{gvk:?}"))))
+    }
+
+    async fn wait_established(&self, name: &str) -> Result<(), Error> {
+        if self.params.dry_run {
+            return Ok(());
+        }
+        let crds: Api<CustomResourceDefinition> = Api::all(self.client.clone());
+        let established = await_condition(crds, name, conditions::is_crd_established());
+        match tokio::time::timeout(self.establish_timeout, established).await {
+            Ok(Ok(_)) => Ok(()),
+            Ok(Err(err)) => Err(Error::WaitEstablished(err)),
+            Err(_) => Err(Error::EstablishTimeout),
+        }
+    }
+}
+
+/// The order in which kinds are applied, so that the objects they define come after them
+fn priority(kind: &str) -> u8 {
+    match kind {
+        "Namespace" => 0,
+        "CustomResourceDefinition" => 1,
+        _ => 2,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use http::{Request, Response};
+    use kube_client::client::Body;
+    use serde_json::json;
+    use tower_test::mock;
+
+    const MANIFEST: &str = r"
+apiVersion: v1
+kind: ConfigMap
+metadata:
+  name: config
+data:
+  a: b
+---
+---
+apiVersion: v1
+kind: Namespace
+metadata:
+  name: app
+---
+apiVersion: v1
+kind: ConfigMap
+metadata:
+  namespace: other
+";
+
+    #[test]
+    fn parses_documents_and_skips_empty_ones() {
+        let objects = parse(MANIFEST).unwrap();
+        let kinds: Vec<_> = objects
+            .iter()
+            .map(|obj| obj.types.as_ref().unwrap().kind.as_str())
+            .collect();
+        assert_eq!(kinds, ["ConfigMap", "Namespace", "ConfigMap"]);
+        assert!(matches!(parse("a: [").unwrap_err(), Error::Parse(_)));
+    }
+
+    fn api_resources() -> serde_json::Value {
+        json!({
+            "kind": "APIResourceList",
+            "groupVersion": "v1",
+            "resources": [
+                { "name": "configmaps", "singularName": "configmap", "namespaced": true, "kind": "ConfigMap", "verbs": ["get", "patch"] },
+                { "name": "namespaces", "singularName": "namespace", "namespaced": false, "kind": "Namespace", "verbs": ["get", "patch"] },
+            ],
+        })
+    }
+
+    #[tokio::test]
+    async fn applies_namespaces_first_and_reports_each_object() {
+        let (mock_service, handle) = mock::pair::<Request<Body>, Response<Body>>();
+        let spawned = tokio::spawn(async move {
+            let mut handle = std::pin::pin!(handle);
+            let mut paths = Vec::new();
+            while let Some((request, send)) = handle.next_request().await {
+                let path = request.uri().path().to_owned();
+                let body = if path == "/api/v1" {
+                    api_resources()
+                } else {
+                    assert_eq!(request.method(), http::Method::PATCH);
+                    assert!(request.uri().query().unwrap().contains("fieldManager=tool"));
+                    let body = request.into_body().collect_bytes().await.unwrap();
+                    serde_json::from_slice(&body).unwrap()
+                };
+                paths.push(path);
+                send.send_response(Response::new(Body::from(serde_json::to_vec(&body).unwrap())));
+            }
+            paths
+        });
+
+        let client = Client::new(mock_service, "default");
+        let report = ManifestApplier::new(client, "tool")
+            .apply(parse(MANIFEST).unwrap())
+            .await;
+        let outcomes: Vec<_> = report.outcomes.iter().map(ToString::to_string).collect();
+        assert_eq!(outcomes, [
+            "Namespace app",
+            "ConfigMap default/config",
+            "ConfigMap "
+        ]);
+        assert!(matches!(report.outcomes[2].result, Err(Error::MissingName)));
+        assert_eq!(report.failed().count(), 1);
+        assert!(matches!(report.into_result(), Err(Error::Failed(report)) if report.failed().count() == 1));
+
+        let paths = spawned.await.unwrap();
+        assert_eq!(paths, [
+            "/api/v1",
+            "/api/v1/namespaces/app",
+            "/api/v1/namespaces/default/configmaps/config",
+        ]);
+    }
+}
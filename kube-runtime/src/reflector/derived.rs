@@ -0,0 +1,367 @@
+//! Materialized views that join reflector stores
+use super::{Lookup, ObjectRef, store::Store};
+use crate::utils::ChangeEvent;
+use ahash::AHashMap;
+use educe::Educe;
+use futures::{
+    Stream, StreamExt,
+    stream::{self, BoxStream},
+};
+use parking_lot::RwLock;
+use std::{fmt::Debug, hash::Hash, sync::Arc};
+
+type Cache<K, T> = Arc<RwLock<AHashMap<ObjectRef<K>, Arc<T>>>>;
+
+/// A change to a [`DerivedStore`], as emitted by [`DerivedView::into_stream`]
+#[derive(Educe)]
+#[educe(
+    Debug(bound("K::DynamicType: Debug, T: Debug")),
+    Clone,
+    PartialEq(bound("T: PartialEq"))
+)]
+pub struct DerivedChange<K, T>
+where
+    K: Lookup,
+    K::DynamicType: Eq + Hash,
+{
+    /// The primary object that the view entry was derived from
+    pub key: ObjectRef<K>,
+    /// The change to the view entry
+    pub change: ChangeEvent<T>,
+}
+
+/// A read handle to the entries of a [`DerivedView`]
+///
+/// Like a [`Store`], but entries are keyed by the [`ObjectRef`] of the primary object that
+/// they were derived from.
+///
+/// Cloning will produce a new reference to the same backing view.
+#[derive(Educe)]
+#[educe(Debug(bound("K::DynamicType: Debug, T: Debug")), Clone)]
+pub struct DerivedStore<K, T>
+where
+    K: Lookup,
+    K::DynamicType: Eq + Hash,
+{
+    entries: Cache<K, T>,
+}
+
+impl<K, T> DerivedStore<K, T>
+where
+    K: Lookup,
+    K::DynamicType: Eq + Hash,
+{
+    /// Retrieve the entry derived from the primary object referred to by `key`, if any
+    #[must_use]
+    pub fn get(&self, key: &ObjectRef<K>) -> Option<Arc<T>> {
+        self.entries.read().get(key).cloned()
+    }
+
+    /// Return a full snapshot of the current entries
+    #[must_use]
+    pub fn state(&self) -> Vec<Arc<T>> {
+        self.entries.read().values().cloned().collect()
+    }
+
+    /// Return the number of entries in the view
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.read().len()
+    }
+
+    /// Return whether the view is empty
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.read().is_empty()
+    }
+}
+
+enum Trigger<K>
+where
+    K: Lookup,
+    K::DynamicType: Eq + Hash,
+{
+    Key(ObjectRef<K>),
+    All,
+}
+
+/// An incrementally updated join of a primary [`Store`] with any other state
+///
+/// Each primary object is passed through a user join function, which typically looks up related
+/// objects in other [`Store`]s (pods with their node, services with their endpoint slices, custom
+/// resources with their secrets). The results are kept in a [`DerivedStore`], and every change is
+/// emitted from [`DerivedView::into_stream`].
+///
+/// Entries are only recomputed when a trigger fires:
+/// - [`DerivedView::triggers`] recomputes the entries for a stream of primary [`ObjectRef`]s,
+///   such as the primary reflector's [`touched_objects`](crate::WatchStreamExt::touched_objects),
+///   or secondary objects mapped to the primary objects that reference them.
+/// - [`DerivedView::triggers_all`] recomputes every entry whenever a stream yields,
+///   which is useful for secondary resources that can't be mapped back to primary objects.
+///
+/// The triggers must fire _after_ the stores that the join function reads have been updated,
+/// which is the case for streams that come out of a [`reflector`](crate::reflector()).
+///
+/// ```no_run
+/// use futures::{StreamExt, future::ready};
+/// use k8s_openapi::api::core::v1::{Node, Pod};
+/// use kube::{Api, Client, ResourceExt};
+/// use kube_runtime::{
+///     reflector::{self, DerivedView, ObjectRef},
+///     utils::ChangeEvent,
+///     watcher, WatchStreamExt,
+/// };
+/// # async fn wrapper() -> Result<(), Box<dyn std::error::Error>> {
+/// # let client: Client = todo!();
+/// let (pods, pod_writer) = reflector::store::<Pod>();
+/// let (nodes, node_writer) = reflector::store::<Node>();
+/// let pod_changes = watcher(Api::<Pod>::all(client.clone()), watcher::Config::default())
+///     .reflect(pod_writer)
+///     .touched_objects()
+///     .filter_map(|pod| ready(pod.ok().map(|pod| ObjectRef::from_obj(&pod))));
+/// let node_changes = watcher(Api::<Node>::all(client), watcher::Config::default())
+///     .reflect(node_writer)
+///     .touched_objects();
+///
+/// let view = DerivedView::new(pods, move |pod: &Pod| {
+///     let node = nodes.get(&ObjectRef::new(pod.spec.as_ref()?.node_name.as_ref()?))?;
+///     Some((pod.name_any(), node.name_any(), node.labels().get("topology.kubernetes.io/zone").cloned()))
+/// })
+/// .triggers(pod_changes)
+/// .triggers_all(node_changes);
+/// let pod_zones = view.store();
+///
+/// view.into_stream()
+///     .for_each(|derived| {
+///         if let ChangeEvent::Added(entry) = &derived.change {
+///             println!("{} was scheduled to {} in {:?}", entry.0, entry.1, entry.2);
+///         }
+///         ready(())
+///     })
+///     .await;
+/// # Ok(())
+/// # }
+/// ```
+pub struct DerivedView<K, T, F>
+where
+    K: Lookup + 'static,
+    K::DynamicType: Eq + Hash,
+{
+    primary: Store<K>,
+    join: F,
+    entries: Cache<K, T>,
+    triggers: Vec<BoxStream<'static, Trigger<K>>>,
+}
+
+impl<K, T, F> Debug for DerivedView<K, T, F>
+where
+    K: Lookup + 'static,
+    K::DynamicType: Eq + Hash,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DerivedView")
+            .field("triggers", &self.triggers.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<K, T, F> DerivedView<K, T, F>
+where
+    K: Lookup + Clone + 'static,
+    K::DynamicType: Eq + Hash + Clone + Default,
+    T: PartialEq,
+    F: FnMut(&K) -> Option<T>,
+{
+    /// Create a view that derives an entry from every object in `primary` through `join`
+    ///
+    /// Primary objects for which `join` returns `None` have no entry in the view.
+    #[must_use]
+    pub fn new(primary: Store<K>, join: F) -> Self {
+        Self {
+            primary,
+            join,
+            entries: Arc::default(),
+            triggers: Vec::new(),
+        }
+    }
+
+    /// Recompute the entries for every primary [`ObjectRef`] yielded by `stream`
+    #[must_use]
+    pub fn triggers(mut self, stream: impl Stream<Item = ObjectRef<K>> + Send + 'static) -> Self {
+        self.triggers.push(stream.map(Trigger::Key).boxed());
+        self
+    }
+
+    /// Recompute all entries whenever `stream` yields
+    #[must_use]
+    pub fn triggers_all<S>(mut self, stream: S) -> Self
+    where
+        S: Stream + Send + 'static,
+    {
+        self.triggers.push(stream.map(|_| Trigger::All).boxed());
+        self
+    }
+
+    /// Return a read handle to the view
+    ///
+    /// The view is only updated while the stream returned by [`DerivedView::into_stream`] is polled.
+    #[must_use]
+    pub fn store(&self) -> DerivedStore<K, T> {
+        DerivedStore {
+            entries: self.entries.clone(),
+        }
+    }
+
+    /// Drive the view, yielding a [`DerivedChange`] for every entry that is added, modified, or removed
+    ///
+    /// Entries whose recomputed value is equal to the previous one are not emitted.
+    /// The stream ends once all the trigger streams have ended.
+    ///
+    /// Mapping the changes to their [`DerivedChange::key`] gives a stream that can be passed to
+    /// [`Controller::reconcile_on`](crate::Controller::reconcile_on) to reconcile primary objects whose joined data changed.
+    pub fn into_stream(mut self) -> impl Stream<Item = DerivedChange<K, T>> {
+        let triggers = stream::select_all(std::mem::take(&mut self.triggers));
+        triggers.flat_map(move |trigger| {
+            let changes = match trigger {
+                Trigger::Key(key) => self.recompute(key).into_iter().collect(),
+                Trigger::All => self.recompute_all(),
+            };
+            stream::iter(changes)
+        })
+    }
+
+    fn recompute_all(&mut self) -> Vec<DerivedChange<K, T>> {
+        let dyntype = K::DynamicType::default();
+        let mut keys = self
+            .primary
+            .state()
+            .iter()
+            .map(|obj| obj.to_object_ref(dyntype.clone()))
+            .collect::<Vec<_>>();
+        // Entries whose primary object is gone must be recomputed (and removed) too
+        keys.extend(
+            self.entries
+                .read()
+                .keys()
+                .filter(|key| self.primary.get(key).is_none())
+                .cloned(),
+        );
+        keys.into_iter().filter_map(|key| self.recompute(key)).collect()
+    }
+
+    fn recompute(&mut self, key: ObjectRef<K>) -> Option<DerivedChange<K, T>> {
+        let new = self.primary.get(&key).and_then(|obj| (self.join)(&obj));
+        let mut entries = self.entries.write();
+        let change = match (entries.get(&key), new) {
+            (None, None) => return None,
+            (Some(old), Some(new)) if **old == new => return None,
+            (Some(old), Some(new)) => ChangeEvent::Modified {
+                old: old.clone(),
+                new: Arc::new(new),
+            },
+            (None, Some(new)) => ChangeEvent::Added(Arc::new(new)),
+            (Some(old), None) => ChangeEvent::Deleted(old.clone()),
+        };
+        match &change {
+            ChangeEvent::Added(new) | ChangeEvent::Modified { new, .. } => {
+                entries.insert(key.clone(), new.clone());
+            }
+            ChangeEvent::Deleted(_) => {
+                entries.remove(&key);
+            }
+        }
+        Some(DerivedChange { key, change })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DerivedChange, DerivedView};
+    use crate::{
+        reflector::{self, ObjectRef},
+        utils::ChangeEvent,
+        watcher,
+    };
+    use futures::{StreamExt, channel::mpsc};
+    use k8s_openapi::api::core::v1::{ConfigMap, Secret};
+    use kube_client::api::ObjectMeta;
+    use std::sync::Arc;
+
+    fn meta(name: &str) -> ObjectMeta {
+        ObjectMeta {
+            name: Some(name.to_string()),
+            namespace: Some("ns".to_string()),
+            ..ObjectMeta::default()
+        }
+    }
+
+    fn configmap(name: &str, secret: &str) -> ConfigMap {
+        ConfigMap {
+            metadata: meta(name),
+            data: Some([("secret".to_string(), secret.to_string())].into()),
+            ..ConfigMap::default()
+        }
+    }
+
+    fn secret(name: &str, value: &str) -> Secret {
+        Secret {
+            metadata: meta(name),
+            string_data: Some([("value".to_string(), value.to_string())].into()),
+            ..Secret::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn derived_view_joins_and_tracks_changes() {
+        let (configmaps, mut cm_writer) = reflector::store::<ConfigMap>();
+        let (secrets, mut secret_writer) = reflector::store::<Secret>();
+        let (cm_tx, cm_rx) = mpsc::unbounded();
+        let (secret_tx, secret_rx) = mpsc::unbounded::<()>();
+
+        let view = DerivedView::new(configmaps, move |cm: &ConfigMap| {
+            let secret_name = cm.data.as_ref()?.get("secret")?;
+            let secret = secrets.get(&ObjectRef::new(secret_name).within("ns"))?;
+            secret.string_data.as_ref()?.get("value").cloned()
+        })
+        .triggers(cm_rx)
+        .triggers_all(secret_rx);
+        let store = view.store();
+        let mut changes = Box::pin(view.into_stream());
+        let key = ObjectRef::new("cm").within("ns");
+
+        // Nothing to join with yet
+        cm_writer.apply_watcher_event(&watcher::Event::Apply(configmap("cm", "creds")));
+        cm_tx.unbounded_send(key.clone()).unwrap();
+        secret_writer.apply_watcher_event(&watcher::Event::Apply(secret("creds", "hunter2")));
+        secret_tx.unbounded_send(()).unwrap();
+        assert_eq!(changes.next().await.unwrap(), DerivedChange {
+            key: key.clone(),
+            change: ChangeEvent::Added(Arc::new("hunter2".to_string())),
+        });
+        assert_eq!(store.get(&key).as_deref().map(String::as_str), Some("hunter2"));
+
+        // Unrelated changes are not emitted
+        secret_writer.apply_watcher_event(&watcher::Event::Apply(secret("other", "x")));
+        secret_tx.unbounded_send(()).unwrap();
+        secret_writer.apply_watcher_event(&watcher::Event::Apply(secret("creds", "hunter3")));
+        secret_tx.unbounded_send(()).unwrap();
+        assert_eq!(changes.next().await.unwrap(), DerivedChange {
+            key: key.clone(),
+            change: ChangeEvent::Modified {
+                old: Arc::new("hunter2".to_string()),
+                new: Arc::new("hunter3".to_string()),
+            },
+        });
+
+        cm_writer.apply_watcher_event(&watcher::Event::Delete(configmap("cm", "creds")));
+        cm_tx.unbounded_send(key.clone()).unwrap();
+        assert_eq!(changes.next().await.unwrap(), DerivedChange {
+            key: key.clone(),
+            change: ChangeEvent::Deleted(Arc::new("hunter3".to_string())),
+        });
+        assert!(store.is_empty());
+
+        drop((cm_tx, secret_tx));
+        assert!(changes.next().await.is_none());
+    }
+}
@@ -2,18 +2,64 @@ use core::{
     pin::Pin,
     task::{Context, Poll},
 };
-use std::{fmt::Debug, sync::Arc};
+use std::{
+    collections::VecDeque,
+    fmt::Debug,
+    sync::{
+        Arc, Weak,
+        atomic::{AtomicU64, Ordering},
+    },
+    task::Waker,
+};
 
+use ahash::AHashSet;
 use educe::Educe;
 use futures::Stream;
-use pin_project::pin_project;
+use parking_lot::Mutex;
 use std::task::ready;
 
 use crate::reflector::{ObjectRef, Store};
-use async_broadcast::{InactiveReceiver, Receiver, Sender};
+use async_broadcast::{InactiveReceiver, Receiver, RecvError, Sender};
 
 use super::Lookup;
 
+/// How a shared stream deals with subscribers that cannot keep up with the root stream
+///
+/// See [`store_shared_with_policy`](crate::reflector::store::store_shared_with_policy).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum BackpressurePolicy {
+    /// Wait for all subscribers to make room in the buffer before dispatching more events
+    ///
+    /// This is lossless, but a single slow subscriber stalls the root stream, and with it
+    /// every other subscriber.
+    #[default]
+    Block,
+    /// Never wait for subscribers; a subscriber that falls more than the buffer size behind
+    /// loses its pending events and is resynced from the [`Store`] instead
+    ///
+    /// After a resync, the subscriber yields every object in the [`Store`] once, before it
+    /// continues with the live events.
+    Resync,
+    /// Never wait for subscribers; pending events for the same [`ObjectRef`] are merged into one
+    ///
+    /// Each subscriber queues at most one pending event per object, so the buffer size is not used
+    /// and memory usage is bounded by the size of the [`Store`].
+    Coalesce,
+}
+
+/// Point-in-time statistics about a single [`ReflectHandle`]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct SubscriberMetrics {
+    /// The number of dispatched events that the subscriber has not received yet
+    pub lag: u64,
+    /// The number of times the subscriber fell behind and was resynced from the [`Store`]
+    pub resyncs: u64,
+    /// The number of events that were merged into an already pending event for the same object
+    pub coalesced: u64,
+}
+
 #[derive(Educe)]
 #[educe(Debug(bound("K: Debug, K::DynamicType: Debug")), Clone)]
 // A helper type that holds a broadcast transmitter and a broadcast receiver,
@@ -23,10 +69,25 @@ where
     K: Lookup + Clone + 'static,
     K::DynamicType: Eq + std::hash::Hash + Clone,
 {
-    dispatch_tx: Sender<ObjectRef<K>>,
-    // An inactive reader that prevents the channel from closing until the
-    // writer is dropped.
-    _dispatch_rx: InactiveReceiver<ObjectRef<K>>,
+    channel: DispatchChannel<K>,
+}
+
+#[derive(Educe)]
+#[educe(Debug(bound("K: Debug, K::DynamicType: Debug")), Clone)]
+enum DispatchChannel<K>
+where
+    K: Lookup + Clone + 'static,
+    K::DynamicType: Eq + std::hash::Hash + Clone,
+{
+    Broadcast {
+        dispatch_tx: Sender<ObjectRef<K>>,
+        // An inactive reader that prevents the channel from closing until the
+        // writer is dropped.
+        keepalive_rx: InactiveReceiver<ObjectRef<K>>,
+        // The number of events dispatched so far, used to compute the lag of subscribers
+        dispatched: Arc<AtomicU64>,
+    },
+    Coalesce(Arc<CoalesceDispatch<K>>),
 }
 
 impl<K> Dispatcher<K>
@@ -39,29 +100,49 @@ where
     ///
     /// A buffer size is required to create the underlying broadcast channel.
     /// Messages will be buffered until all active readers have received a copy
-    /// of the message. When the channel is full, senders will apply
-    /// backpressure by waiting for space to free up.
+    /// of the message. When the channel is full, the `policy` decides whether
+    /// senders apply backpressure by waiting for space to free up.
     //
     // N.B messages are eagerly broadcasted, meaning no active receivers are
     // required for a message to be broadcasted.
     #[cfg(feature = "unstable-runtime-subscribe")]
-    pub(crate) fn new(buf_size: usize) -> Dispatcher<K> {
-        // Create a broadcast (tx, rx) pair
-        let (mut dispatch_tx, dispatch_rx) = async_broadcast::broadcast(buf_size);
-        // The tx half will not wait for any receivers to be active before
-        // broadcasting events. If no receivers are active, events will be
-        // buffered.
-        dispatch_tx.set_await_active(false);
-        Self {
-            dispatch_tx,
-            _dispatch_rx: dispatch_rx.deactivate(),
-        }
+    pub(crate) fn new(buf_size: usize, policy: BackpressurePolicy) -> Dispatcher<K> {
+        let channel = match policy {
+            BackpressurePolicy::Block | BackpressurePolicy::Resync => {
+                // Create a broadcast (tx, rx) pair
+                let (mut dispatch_tx, dispatch_rx) = async_broadcast::broadcast(buf_size);
+                // The tx half will not wait for any receivers to be active before
+                // broadcasting events. If no receivers are active, events will be
+                // buffered.
+                dispatch_tx.set_await_active(false);
+                // Drop the oldest events rather than waiting for slow receivers,
+                // who then notice the overflow and resync from the store.
+                dispatch_tx.set_overflow(policy == BackpressurePolicy::Resync);
+                DispatchChannel::Broadcast {
+                    dispatch_tx,
+                    keepalive_rx: dispatch_rx.deactivate(),
+                    dispatched: Arc::default(),
+                }
+            }
+            BackpressurePolicy::Coalesce => DispatchChannel::Coalesce(Arc::default()),
+        };
+        Self { channel }
     }
 
     // Calls broadcast on the channel. Will return when the channel has enough
     // space to send an event.
     pub(crate) async fn broadcast(&mut self, obj_ref: ObjectRef<K>) {
-        let _ = self.dispatch_tx.broadcast_direct(obj_ref).await;
+        match &mut self.channel {
+            DispatchChannel::Broadcast {
+                dispatch_tx,
+                dispatched,
+                ..
+            } => {
+                dispatched.fetch_add(1, Ordering::Relaxed);
+                let _ = dispatch_tx.broadcast_direct(obj_ref).await;
+            }
+            DispatchChannel::Coalesce(dispatch) => dispatch.push(&obj_ref),
+        }
     }
 
     // Creates a `ReflectHandle` by creating a receiver from the tx half.
@@ -70,7 +151,250 @@ where
     // to be acked by listeners.
     #[cfg(feature = "unstable-runtime-subscribe")]
     pub(crate) fn subscribe(&self, reader: Store<K>) -> ReflectHandle<K> {
-        ReflectHandle::new(reader, self.dispatch_tx.new_receiver())
+        let subscription = match &self.channel {
+            DispatchChannel::Broadcast {
+                dispatch_tx,
+                dispatched,
+                ..
+            } => Subscription::Broadcast {
+                rx: dispatch_tx.new_receiver(),
+                dispatched: dispatched.clone(),
+                received: dispatched.load(Ordering::Relaxed),
+                resyncs: 0,
+            },
+            DispatchChannel::Coalesce(dispatch) => {
+                Subscription::Coalesce(dispatch.register(CoalesceQueue::default()))
+            }
+        };
+        ReflectHandle::new(reader, subscription)
+    }
+}
+
+/// Fan-out of events to per-subscriber queues for [`BackpressurePolicy::Coalesce`]
+struct CoalesceDispatch<K>
+where
+    K: Lookup + 'static,
+    K::DynamicType: Eq + std::hash::Hash,
+{
+    subscribers: Mutex<Vec<Weak<Mutex<CoalesceQueue<K>>>>>,
+}
+
+impl<K> Default for CoalesceDispatch<K>
+where
+    K: Lookup + 'static,
+    K::DynamicType: Eq + std::hash::Hash,
+{
+    fn default() -> Self {
+        Self {
+            subscribers: Mutex::default(),
+        }
+    }
+}
+
+impl<K> Debug for CoalesceDispatch<K>
+where
+    K: Lookup + 'static,
+    K::DynamicType: Eq + std::hash::Hash,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CoalesceDispatch").finish_non_exhaustive()
+    }
+}
+
+impl<K> CoalesceDispatch<K>
+where
+    K: Lookup + 'static,
+    K::DynamicType: Eq + std::hash::Hash + Clone,
+{
+    fn push(&self, obj_ref: &ObjectRef<K>) {
+        self.subscribers.lock().retain(|subscriber| {
+            let Some(subscriber) = subscriber.upgrade() else {
+                // The subscriber has been dropped
+                return false;
+            };
+            subscriber.lock().push(obj_ref);
+            true
+        });
+    }
+
+    #[cfg(feature = "unstable-runtime-subscribe")]
+    fn register(self: &Arc<Self>, queue: CoalesceQueue<K>) -> CoalesceSubscription<K> {
+        let queue = Arc::new(Mutex::new(queue));
+        self.subscribers.lock().push(Arc::downgrade(&queue));
+        CoalesceSubscription {
+            dispatch: Arc::downgrade(self),
+            queue,
+        }
+    }
+
+    /// Register a copy of the pending events of `source`
+    ///
+    /// The subscribers are locked before `source`, in the same order as [`Self::push`], so that a
+    /// concurrent push can neither deadlock with the copy nor be missed by it.
+    fn register_copy(self: &Arc<Self>, source: &Mutex<CoalesceQueue<K>>) -> CoalesceSubscription<K> {
+        let mut subscribers = self.subscribers.lock();
+        let queue = Arc::new(Mutex::new(source.lock().copy()));
+        subscribers.push(Arc::downgrade(&queue));
+        CoalesceSubscription {
+            dispatch: Arc::downgrade(self),
+            queue,
+        }
+    }
+}
+
+impl<K> Drop for CoalesceDispatch<K>
+where
+    K: Lookup + 'static,
+    K::DynamicType: Eq + std::hash::Hash,
+{
+    fn drop(&mut self) {
+        // The root stream is gone, let the subscribers terminate once they have drained their queues
+        for subscriber in self.subscribers.get_mut().drain(..) {
+            if let Some(subscriber) = subscriber.upgrade() {
+                let mut queue = subscriber.lock();
+                queue.closed = true;
+                if let Some(waker) = queue.waker.take() {
+                    waker.wake();
+                }
+            }
+        }
+    }
+}
+
+/// The pending events of a single subscriber for [`BackpressurePolicy::Coalesce`]
+struct CoalesceQueue<K>
+where
+    K: Lookup,
+    K::DynamicType: Eq + std::hash::Hash,
+{
+    pending: VecDeque<ObjectRef<K>>,
+    queued: AHashSet<ObjectRef<K>>,
+    coalesced: u64,
+    closed: bool,
+    waker: Option<Waker>,
+}
+
+impl<K> Default for CoalesceQueue<K>
+where
+    K: Lookup,
+    K::DynamicType: Eq + std::hash::Hash,
+{
+    fn default() -> Self {
+        Self {
+            pending: VecDeque::new(),
+            queued: AHashSet::new(),
+            coalesced: 0,
+            closed: false,
+            waker: None,
+        }
+    }
+}
+
+impl<K> CoalesceQueue<K>
+where
+    K: Lookup,
+    K::DynamicType: Eq + std::hash::Hash + Clone,
+{
+    /// A queue with the same pending events, for a new subscriber
+    fn copy(&self) -> Self {
+        Self {
+            pending: self.pending.clone(),
+            queued: self.queued.clone(),
+            coalesced: 0,
+            closed: self.closed,
+            waker: None,
+        }
+    }
+
+    fn push(&mut self, obj_ref: &ObjectRef<K>) {
+        if self.queued.insert(obj_ref.clone()) {
+            self.pending.push_back(obj_ref.clone());
+            if let Some(waker) = self.waker.take() {
+                waker.wake();
+            }
+        } else {
+            self.coalesced += 1;
+        }
+    }
+
+    fn poll_pop(&mut self, cx: &mut Context<'_>) -> Poll<Option<ObjectRef<K>>> {
+        if let Some(obj_ref) = self.pending.pop_front() {
+            self.queued.remove(&obj_ref);
+            Poll::Ready(Some(obj_ref))
+        } else if self.closed {
+            Poll::Ready(None)
+        } else {
+            self.waker = Some(cx.waker().clone());
+            Poll::Pending
+        }
+    }
+}
+
+/// A subscriber's registration with a [`CoalesceDispatch`]
+struct CoalesceSubscription<K>
+where
+    K: Lookup + 'static,
+    K::DynamicType: Eq + std::hash::Hash,
+{
+    dispatch: Weak<CoalesceDispatch<K>>,
+    queue: Arc<Mutex<CoalesceQueue<K>>>,
+}
+
+impl<K> Clone for CoalesceSubscription<K>
+where
+    K: Lookup + 'static,
+    K::DynamicType: Eq + std::hash::Hash + Clone,
+{
+    fn clone(&self) -> Self {
+        match self.dispatch.upgrade() {
+            Some(dispatch) => dispatch.register_copy(&self.queue),
+            // The root stream is gone, so the clone only needs to drain what was already queued
+            None => CoalesceSubscription {
+                dispatch: Weak::new(),
+                queue: Arc::new(Mutex::new(CoalesceQueue {
+                    closed: true,
+                    ..self.queue.lock().copy()
+                })),
+            },
+        }
+    }
+}
+
+#[cfg_attr(not(feature = "unstable-runtime-subscribe"), allow(dead_code))]
+enum Subscription<K>
+where
+    K: Lookup + Clone + 'static,
+    K::DynamicType: Eq + std::hash::Hash + Clone,
+{
+    Broadcast {
+        rx: Receiver<ObjectRef<K>>,
+        dispatched: Arc<AtomicU64>,
+        received: u64,
+        resyncs: u64,
+    },
+    Coalesce(CoalesceSubscription<K>),
+}
+
+impl<K> Clone for Subscription<K>
+where
+    K: Lookup + Clone + 'static,
+    K::DynamicType: Eq + std::hash::Hash + Clone,
+{
+    fn clone(&self) -> Self {
+        match self {
+            Subscription::Broadcast {
+                rx,
+                dispatched,
+                received,
+                resyncs,
+            } => Subscription::Broadcast {
+                rx: rx.clone(),
+                dispatched: dispatched.clone(),
+                received: *received,
+                resyncs: *resyncs,
+            },
+            Subscription::Coalesce(subscription) => Subscription::Coalesce(subscription.clone()),
+        }
     }
 }
 
@@ -80,23 +404,29 @@ where
 /// or by calling `clone()` on an already existing [`ReflectHandle`]. Each
 /// shared stream reader should be polled independently and driven to readiness
 /// to avoid deadlocks. When the [`Writer`]'s buffer is filled, backpressure
-/// will be applied on the root stream side.
+/// will be applied on the root stream side, unless the [`Writer`] was created
+/// with a different [`BackpressurePolicy`].
 ///
 /// When the root stream is dropped, or it ends, all [`ReflectHandle`]s
 /// subscribed to the stream will also terminate after all events yielded by
 /// the root stream have been observed. This means [`ReflectHandle`] streams
 /// can still be polled after the root stream has been dropped.
 ///
-/// [`Writer`]: crate::reflector::Writer
-#[pin_project]
+/// Objects that were deleted from the store by the time their event is received are skipped, and the
+/// handle moves on to the next event. Earlier versions returned `Poll::Pending` for them instead, which
+/// stalled the handle until it happened to be woken again.
+///
+/// [`Writer`]: crate::reflector::store::Writer
+/// [`subscribe()`]: crate::reflector::store::Writer::subscribe()
 pub struct ReflectHandle<K>
 where
     K: Lookup + Clone + 'static,
     K::DynamicType: Eq + std::hash::Hash + Clone,
 {
-    #[pin]
-    rx: Receiver<ObjectRef<K>>,
+    subscription: Subscription<K>,
     reader: Store<K>,
+    /// Objects that are still to be yielded after a resync
+    resync: VecDeque<Arc<K>>,
 }
 
 impl<K> Clone for ReflectHandle<K>
@@ -105,7 +435,11 @@ where
     K::DynamicType: Eq + std::hash::Hash + Clone,
 {
     fn clone(&self) -> Self {
-        ReflectHandle::new(self.reader.clone(), self.rx.clone())
+        Self {
+            subscription: self.subscription.clone(),
+            reader: self.reader.clone(),
+            resync: self.resync.clone(),
+        }
     }
 }
 
@@ -114,8 +448,13 @@ where
     K: Lookup + Clone,
     K::DynamicType: Eq + std::hash::Hash + Clone,
 {
-    pub(super) fn new(reader: Store<K>, rx: Receiver<ObjectRef<K>>) -> ReflectHandle<K> {
-        Self { rx, reader }
+    #[cfg(feature = "unstable-runtime-subscribe")]
+    fn new(reader: Store<K>, subscription: Subscription<K>) -> ReflectHandle<K> {
+        Self {
+            subscription,
+            reader,
+            resync: VecDeque::new(),
+        }
     }
 
     /// Get a reader from a reflect handle
@@ -123,6 +462,31 @@ where
     pub fn reader(&self) -> Store<K> {
         self.reader.clone()
     }
+
+    /// Get the current [`SubscriberMetrics`] for this subscriber
+    #[must_use]
+    pub fn metrics(&self) -> SubscriberMetrics {
+        match &self.subscription {
+            Subscription::Broadcast {
+                dispatched,
+                received,
+                resyncs,
+                ..
+            } => SubscriberMetrics {
+                lag: dispatched.load(Ordering::Relaxed).saturating_sub(*received),
+                resyncs: *resyncs,
+                coalesced: 0,
+            },
+            Subscription::Coalesce(subscription) => {
+                let queue = subscription.queue.lock();
+                SubscriberMetrics {
+                    lag: queue.pending.len() as u64,
+                    resyncs: 0,
+                    coalesced: queue.coalesced,
+                }
+            }
+        }
+    }
 }
 
 impl<K> Stream for ReflectHandle<K>
@@ -133,13 +497,43 @@ where
     type Item = Arc<K>;
 
     fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
-        let mut this = self.project();
-        match ready!(this.rx.as_mut().poll_next(cx)) {
-            Some(obj_ref) => this
-                .reader
-                .get(&obj_ref)
-                .map_or(Poll::Pending, |obj| Poll::Ready(Some(obj))),
-            None => Poll::Ready(None),
+        let this = self.get_mut();
+        loop {
+            if let Some(obj) = this.resync.pop_front() {
+                return Poll::Ready(Some(obj));
+            }
+            let obj_ref = match &mut this.subscription {
+                Subscription::Broadcast {
+                    rx,
+                    received,
+                    resyncs,
+                    ..
+                } => match ready!(Pin::new(rx).poll_recv(cx)) {
+                    Some(Ok(obj_ref)) => {
+                        *received += 1;
+                        obj_ref
+                    }
+                    Some(Err(RecvError::Overflowed(skipped))) => {
+                        // We fell behind and lost events, replay the whole store instead
+                        tracing::debug!(skipped, "shared stream subscriber lagged, resyncing from store");
+                        *received += skipped;
+                        *resyncs += 1;
+                        this.resync = this.reader.state().into();
+                        continue;
+                    }
+                    None | Some(Err(RecvError::Closed)) => return Poll::Ready(None),
+                },
+                Subscription::Coalesce(subscription) => {
+                    match ready!(subscription.queue.lock().poll_pop(cx)) {
+                        Some(obj_ref) => obj_ref,
+                        None => return Poll::Ready(None),
+                    }
+                }
+            };
+            // Objects that have since been deleted are skipped
+            if let Some(obj) = this.reader.get(&obj_ref) {
+                return Poll::Ready(Some(obj));
+            }
         }
     }
 }
@@ -147,6 +541,7 @@ where
 #[cfg(feature = "unstable-runtime-subscribe")]
 #[cfg(test)]
 pub(crate) mod test {
+    use super::{BackpressurePolicy, SubscriberMetrics};
     use crate::{
         WatchStreamExt,
         watcher::{Error, Event},
@@ -425,6 +820,123 @@ pub(crate) mod test {
         assert_eq!(poll!(subscriber_slow.next()), Poll::Ready(None));
     }
 
+    #[tokio::test]
+    async fn slow_readers_are_resynced_from_store() {
+        let foo = testpod("foo");
+        let bar = testpod("bar");
+        let baz = testpod("baz");
+        let st = stream::iter([
+            Ok(Event::Apply(foo.clone())),
+            Ok(Event::Apply(bar.clone())),
+            Ok(Event::Apply(baz.clone())),
+        ]);
+
+        let (reader, writer) = reflector::store_shared_with_policy(1, BackpressurePolicy::Resync);
+        let mut subscriber = pin!(writer.subscribe().unwrap());
+        let mut subscriber_slow = pin!(writer.subscribe().unwrap());
+        let mut reflect = pin!(st.reflect_shared(writer));
+
+        // The root stream never waits for the slow subscriber
+        for expected in [&foo, &bar, &baz] {
+            assert!(matches!(
+                poll!(reflect.next()),
+                Poll::Ready(Some(Ok(Event::Apply(_))))
+            ));
+            assert_eq!(
+                poll!(subscriber.next()),
+                Poll::Ready(Some(Arc::new(expected.clone())))
+            );
+        }
+        assert!(matches!(poll!(reflect.next()), Poll::Ready(None)));
+        assert_eq!(subscriber.metrics().lag, 0);
+        assert_eq!(subscriber_slow.metrics().lag, 3);
+
+        // The slow subscriber lost events, so it replays the whole store
+        let mut resynced = Vec::new();
+        while let Poll::Ready(Some(obj)) = poll!(subscriber_slow.next()) {
+            resynced.push(obj.metadata.name.clone().unwrap());
+        }
+        resynced.sort();
+        assert_eq!(resynced, ["bar", "baz", "baz", "foo"]);
+        assert_eq!(reader.len(), 3);
+        assert_eq!(subscriber_slow.metrics(), SubscriberMetrics {
+            lag: 0,
+            resyncs: 1,
+            coalesced: 0,
+        });
+    }
+
+    #[tokio::test]
+    async fn pending_events_are_coalesced() {
+        let foo = testpod("foo");
+        let bar = testpod("bar");
+        let st = stream::iter([
+            Ok(Event::Apply(foo.clone())),
+            Ok(Event::Apply(bar.clone())),
+            Ok(Event::Apply(foo.clone())),
+            Ok(Event::Apply(foo.clone())),
+        ]);
+
+        let (_, writer) = reflector::store_shared_with_policy(1, BackpressurePolicy::Coalesce);
+        let mut subscriber = pin!(writer.subscribe().unwrap());
+        let mut reflect = Box::pin(st.reflect_shared(writer));
+
+        assert_eq!(poll!(subscriber.next()), Poll::Pending);
+        for _ in 0..4 {
+            assert!(matches!(
+                poll!(reflect.next()),
+                Poll::Ready(Some(Ok(Event::Apply(_))))
+            ));
+        }
+        assert_eq!(subscriber.metrics(), SubscriberMetrics {
+            lag: 2,
+            resyncs: 0,
+            coalesced: 2,
+        });
+
+        // A clone starts out with the same pending events
+        let mut cloned = pin!(subscriber.clone());
+        for subscriber in [&mut subscriber, &mut cloned] {
+            assert_eq!(poll!(subscriber.next()), Poll::Ready(Some(Arc::new(foo.clone()))));
+            assert_eq!(poll!(subscriber.next()), Poll::Ready(Some(Arc::new(bar.clone()))));
+            assert_eq!(poll!(subscriber.next()), Poll::Pending);
+        }
+
+        // Subscribers terminate once the root stream is dropped
+        assert!(matches!(poll!(reflect.next()), Poll::Ready(None)));
+        drop(reflect);
+        assert_eq!(poll!(subscriber.next()), Poll::Ready(None));
+        assert_eq!(poll!(cloned.next()), Poll::Ready(None));
+    }
+
+    #[test]
+    fn coalesce_subscribers_can_be_cloned_while_events_are_pushed() {
+        let (_, writer) = reflector::store_shared_with_policy(1, BackpressurePolicy::Coalesce);
+        let subscriber = writer.subscribe().unwrap();
+        let events = (0..10_000).map(|i| Ok(Event::Apply(testpod(&format!("pod-{}", i % 100)))));
+        let reflect = stream::iter(events).reflect_shared(writer);
+
+        let (done_tx, done_rx) = std::sync::mpsc::channel();
+        let pusher_done = done_tx.clone();
+        std::thread::spawn(move || {
+            let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+            runtime.block_on(reflect.for_each(|_| std::future::ready(())));
+            pusher_done.send(()).unwrap();
+        });
+        std::thread::spawn(move || {
+            for _ in 0..10_000 {
+                drop(subscriber.clone());
+            }
+            done_tx.send(()).unwrap();
+        });
+
+        for _ in 0..2 {
+            done_rx
+                .recv_timeout(std::time::Duration::from_secs(30))
+                .expect("cloning deadlocked with pushing");
+        }
+    }
+
     // TODO (matei): tests around cloning subscribers once a watch stream has already
     // been established. This will depend on the interfaces & impl so are left
     // out for now.
@@ -0,0 +1,425 @@
+//! A metadata-only store that can fetch full objects on demand
+use super::{
+    Lookup, ObjectRef, reflector,
+    store::{Store, Writer},
+};
+use crate::watcher;
+use ahash::AHashMap;
+use futures::{Stream, TryStreamExt};
+use kube_client::{
+    Client, Error, Resource,
+    api::{GetParams, PartialObjectMeta},
+    core::Request,
+};
+use parking_lot::Mutex;
+use serde::de::DeserializeOwned;
+use std::{collections::BTreeMap, fmt::Debug, hash::Hash, sync::Arc};
+
+/// A [`Store`] of [`PartialObjectMeta`] that can fetch the full `K` objects on demand
+///
+/// This is intended to be populated by a [`reflector`](crate::reflector()) over a metadata-only
+/// [`watcher`](crate::watcher()) (using an `Api<PartialObjectMeta<K>>`), which keeps memory usage low for large kinds
+/// such as `Secret` or `ConfigMap`, while still allowing targeted reads of the full objects
+/// through [`MetadataStore::get_full`].
+///
+/// Full objects are kept in a least-recently-used cache of a fixed capacity. When the store is fed through
+/// [`MetadataStore::reflect`], a cached object is evicted as soon as a metadata update or deletion for it is observed,
+/// so stale copies of large objects are not kept around until their next access.
+/// A cached object is also only returned while its `resourceVersion` matches the metadata in the [`Store`].
+///
+/// Cloning will produce a new reference to the same metadata store and cache.
+///
+/// ```no_run
+/// use futures::StreamExt;
+/// use k8s_openapi::api::core::v1::Secret;
+/// use kube::{Api, Client, ResourceExt, core::PartialObjectMeta};
+/// use kube_runtime::{
+///     reflector::{self, MetadataStore, ObjectRef},
+///     watcher, WatchStreamExt,
+/// };
+/// # async fn wrapper() -> Result<(), Box<dyn std::error::Error>> {
+/// # let client: Client = todo!();
+/// let secrets: Api<PartialObjectMeta<Secret>> = Api::all(client.clone());
+/// let (reader, writer) = reflector::store();
+/// let store = MetadataStore::<Secret>::new(reader, client, 100);
+///
+/// tokio::spawn(
+///     store
+///         .reflect(writer, watcher(secrets, watcher::Config::default()))
+///         .for_each(|_| std::future::ready(())),
+/// );
+///
+/// store.metadata().wait_until_ready().await?;
+/// let tls = ObjectRef::new("tls").within("default");
+/// if let Some(secret) = store.get_full(&tls).await? {
+///     println!("{} has {} keys", secret.name_any(), secret.data.iter().flatten().count());
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct MetadataStore<K>
+where
+    K: Resource + 'static,
+    K::DynamicType: Eq + Hash,
+{
+    metadata: Store<PartialObjectMeta<K>>,
+    client: Client,
+    full: Arc<Mutex<FullObjectCache<K>>>,
+}
+
+impl<K> Clone for MetadataStore<K>
+where
+    K: Resource + 'static,
+    K::DynamicType: Eq + Hash,
+{
+    fn clone(&self) -> Self {
+        Self {
+            metadata: self.metadata.clone(),
+            client: self.client.clone(),
+            full: self.full.clone(),
+        }
+    }
+}
+
+impl<K> Debug for MetadataStore<K>
+where
+    K: Resource + 'static,
+    K::DynamicType: Eq + Hash,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MetadataStore").finish_non_exhaustive()
+    }
+}
+
+impl<K> MetadataStore<K>
+where
+    K: Resource + Clone + DeserializeOwned + Debug + 'static,
+    K::DynamicType: Eq + Hash + Clone,
+{
+    /// Creates a [`MetadataStore`] on top of a [`Store`] of [`PartialObjectMeta`]
+    ///
+    /// `client` is used to fetch the full objects, and at most `capacity` full objects are cached at a time.
+    #[must_use]
+    pub fn new(metadata: Store<PartialObjectMeta<K>>, client: Client, capacity: usize) -> Self {
+        Self {
+            metadata,
+            client,
+            full: Arc::new(Mutex::new(FullObjectCache::new(capacity))),
+        }
+    }
+
+    /// The underlying [`Store`] of [`PartialObjectMeta`]
+    #[must_use]
+    pub fn metadata(&self) -> &Store<PartialObjectMeta<K>> {
+        &self.metadata
+    }
+
+    /// Cache the metadata from a [`watcher()`](crate::watcher()) stream into `writer`, like [`reflector()`]
+    ///
+    /// `writer` must be the writer of the [`Store`] this [`MetadataStore`] was created with.
+    /// Cached full objects are evicted when an update or deletion of their metadata passes through the stream,
+    /// and the whole cache is dropped when the watcher restarts its initial listing.
+    pub fn reflect<W>(
+        &self,
+        writer: Writer<PartialObjectMeta<K>>,
+        stream: W,
+    ) -> impl Stream<Item = W::Item> + use<K, W>
+    where
+        W: Stream<Item = watcher::Result<watcher::Event<PartialObjectMeta<K>>>>,
+    {
+        let full = self.full.clone();
+        let dyntype = writer.dyntype().clone();
+        let stream = stream.inspect_ok(move |event| {
+            let mut full = full.lock();
+            match event {
+                watcher::Event::Apply(meta)
+                | watcher::Event::Delete(meta)
+                | watcher::Event::InitApply(meta) => {
+                    full.remove(
+                        &ObjectRef::from_obj_with(meta, dyntype.clone()).into_kind_unchecked(dyntype.clone()),
+                    );
+                }
+                watcher::Event::Init => full.clear(),
+                watcher::Event::InitDone => {}
+            }
+        });
+        reflector(writer, stream)
+    }
+
+    /// Retrieve the metadata of the object referred to by `key`, if it is in the store
+    ///
+    /// See [`Store::get`] for caveats.
+    #[must_use]
+    pub fn get_metadata(&self, key: &ObjectRef<K>) -> Option<Arc<PartialObjectMeta<K>>> {
+        self.metadata.get(&metadata_ref(key))
+    }
+
+    /// Retrieve the full object referred to by `key`
+    ///
+    /// The object is served from the cache if its `resourceVersion` matches the metadata in the store,
+    /// and is otherwise fetched from the apiserver with [`Api::get`](kube_client::Api::get) and cached.
+    ///
+    /// Returns `None` if the object is not in the metadata store, or if it no longer exists in the cluster.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the object could not be fetched from the apiserver.
+    pub async fn get_full(&self, key: &ObjectRef<K>) -> Result<Option<Arc<K>>, Error> {
+        let Some(meta) = self.get_metadata(key) else {
+            self.full.lock().remove(key);
+            return Ok(None);
+        };
+        let resource_version = meta.metadata.resource_version.as_deref();
+        if let Some(obj) = self.full.lock().get(key, resource_version) {
+            return Ok(Some(obj));
+        }
+
+        // `Api<K>` can only be scoped to a namespace for statically namespaced kinds, so build the request directly
+        let mut req = Request::new(K::url_path(&key.dyntype, key.namespace.as_deref()))
+            .get(&key.name, &GetParams::default())
+            .map_err(Error::BuildRequest)?;
+        req.extensions_mut().insert("get");
+        match self.client.request::<K>(req).await {
+            Ok(obj) => {
+                let obj = Arc::new(obj);
+                self.full.lock().insert(key.clone(), obj.clone());
+                Ok(Some(obj))
+            }
+            Err(Error::Api(status)) if status.is_not_found() => {
+                self.full.lock().remove(key);
+                Ok(None)
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Drop the cached full object referred to by `key`, if any
+    pub fn evict(&self, key: &ObjectRef<K>) {
+        self.full.lock().remove(key);
+    }
+
+    /// Return the number of full objects currently cached
+    #[must_use]
+    pub fn cached_len(&self) -> usize {
+        self.full.lock().entries.len()
+    }
+}
+
+fn metadata_ref<K>(key: &ObjectRef<K>) -> ObjectRef<PartialObjectMeta<K>>
+where
+    K: Resource,
+    K::DynamicType: Clone,
+{
+    key.clone().into_kind_unchecked(key.dyntype.clone())
+}
+
+struct CachedObject<K> {
+    obj: Arc<K>,
+    last_used: u64,
+}
+
+/// A least-recently-used cache of full objects
+struct FullObjectCache<K>
+where
+    K: Lookup,
+    K::DynamicType: Eq + Hash,
+{
+    entries: AHashMap<ObjectRef<K>, CachedObject<K>>,
+    /// The keys of `entries` by their last use, oldest first
+    recency: BTreeMap<u64, ObjectRef<K>>,
+    capacity: usize,
+    /// Monotonic counter used to track recency of use
+    clock: u64,
+}
+
+impl<K> FullObjectCache<K>
+where
+    K: Lookup,
+    K::DynamicType: Eq + Hash + Clone,
+{
+    fn new(capacity: usize) -> Self {
+        Self {
+            entries: AHashMap::new(),
+            recency: BTreeMap::new(),
+            capacity,
+            clock: 0,
+        }
+    }
+
+    /// Returns the cached object if it is at `resource_version`, and evicts it otherwise
+    fn get(&mut self, key: &ObjectRef<K>, resource_version: Option<&str>) -> Option<Arc<K>> {
+        let entry = self.entries.get_mut(key)?;
+        if entry.obj.resource_version().as_deref() == resource_version {
+            self.clock += 1;
+            let key = self.recency.remove(&entry.last_used)?;
+            entry.last_used = self.clock;
+            self.recency.insert(self.clock, key);
+            Some(entry.obj.clone())
+        } else {
+            self.remove(key);
+            None
+        }
+    }
+
+    fn insert(&mut self, key: ObjectRef<K>, obj: Arc<K>) {
+        if self.capacity == 0 {
+            return;
+        }
+        self.remove(&key);
+        if self.entries.len() >= self.capacity
+            && let Some((_, lru)) = self.recency.pop_first()
+        {
+            self.entries.remove(&lru);
+        }
+        self.clock += 1;
+        self.recency.insert(self.clock, key.clone());
+        self.entries.insert(key, CachedObject {
+            obj,
+            last_used: self.clock,
+        });
+    }
+
+    fn remove(&mut self, key: &ObjectRef<K>) {
+        if let Some(entry) = self.entries.remove(key) {
+            self.recency.remove(&entry.last_used);
+        }
+    }
+
+    fn clear(&mut self) {
+        self.entries.clear();
+        self.recency.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FullObjectCache, MetadataStore};
+    use crate::{reflector::ObjectRef, watcher};
+    use futures::StreamExt;
+    use http::{Request, Response};
+    use k8s_openapi::api::core::v1::Secret;
+    use kube_client::{
+        Client,
+        api::{ObjectMeta, PartialObjectMeta, PartialObjectMetaExt},
+        client::Body,
+    };
+    use std::sync::Arc;
+    use tower_test::mock;
+
+    fn secret(name: &str, resource_version: &str) -> Secret {
+        Secret {
+            metadata: ObjectMeta {
+                name: Some(name.to_string()),
+                namespace: Some("default".to_string()),
+                resource_version: Some(resource_version.to_string()),
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    fn secret_meta(name: &str, resource_version: &str) -> PartialObjectMeta<Secret> {
+        secret(name, resource_version).metadata.into_response_partial()
+    }
+
+    #[test]
+    fn full_object_cache_evicts_least_recently_used() {
+        let mut cache = FullObjectCache::<Secret>::new(2);
+        let (a, b, c) = (
+            ObjectRef::new("a").within("default"),
+            ObjectRef::new("b").within("default"),
+            ObjectRef::new("c").within("default"),
+        );
+        cache.insert(a.clone(), Arc::new(secret("a", "1")));
+        cache.insert(b.clone(), Arc::new(secret("b", "1")));
+        assert!(cache.get(&a, Some("1")).is_some());
+        cache.insert(c.clone(), Arc::new(secret("c", "1")));
+        assert!(cache.get(&a, Some("1")).is_some());
+        assert!(cache.get(&b, Some("1")).is_none());
+        assert!(cache.get(&c, Some("1")).is_some());
+        // A different resourceVersion evicts the entry
+        assert!(cache.get(&c, Some("2")).is_none());
+        assert!(cache.get(&c, Some("1")).is_none());
+    }
+
+    #[tokio::test]
+    async fn metadata_store_fetches_and_caches_full_objects() {
+        let (mock_service, handle) = mock::pair::<Request<Body>, Response<Body>>();
+        let client = Client::new(mock_service, "default");
+        let (reader, mut writer) = crate::reflector::store();
+        let store = MetadataStore::<Secret>::new(reader, client, 10);
+        let key = ObjectRef::new("tls").within("default");
+
+        let server = tokio::spawn(async move {
+            let mut handle = std::pin::pin!(handle);
+            for rv in ["1", "2"] {
+                let (request, send) = handle.next_request().await.expect("service not called");
+                assert_eq!(request.uri().path(), "/api/v1/namespaces/default/secrets/tls");
+                let body = serde_json::to_vec(&secret("tls", rv)).unwrap();
+                send.send_response(Response::builder().body(Body::from(body)).unwrap());
+            }
+        });
+
+        // Not in the metadata store, so nothing is fetched
+        assert!(store.get_full(&key).await.unwrap().is_none());
+
+        writer.apply_watcher_event(&watcher::Event::Apply(secret_meta("tls", "1")));
+        let first = store.get_full(&key).await.unwrap().unwrap();
+        assert_eq!(first.metadata.resource_version.as_deref(), Some("1"));
+        // Served from the cache
+        let cached = store.get_full(&key).await.unwrap().unwrap();
+        assert!(Arc::ptr_eq(&first, &cached));
+
+        // A metadata update invalidates the cached object
+        writer.apply_watcher_event(&watcher::Event::Apply(secret_meta("tls", "2")));
+        let second = store.get_full(&key).await.unwrap().unwrap();
+        assert_eq!(second.metadata.resource_version.as_deref(), Some("2"));
+        assert_eq!(store.cached_len(), 1);
+
+        writer.apply_watcher_event(&watcher::Event::Delete(secret_meta("tls", "3")));
+        assert!(store.get_full(&key).await.unwrap().is_none());
+        assert_eq!(store.cached_len(), 0);
+        server.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn metadata_store_evicts_full_objects_on_metadata_events() {
+        let (mock_service, handle) = mock::pair::<Request<Body>, Response<Body>>();
+        let client = Client::new(mock_service, "default");
+        let (reader, writer) = crate::reflector::store();
+        let store = MetadataStore::<Secret>::new(reader, client, 10);
+        let (tls, other) = (
+            ObjectRef::new("tls").within("default"),
+            ObjectRef::new("other").within("default"),
+        );
+
+        let server = tokio::spawn(async move {
+            let mut handle = std::pin::pin!(handle);
+            for name in ["tls", "other"] {
+                let (_, send) = handle.next_request().await.expect("service not called");
+                let body = serde_json::to_vec(&secret(name, "1")).unwrap();
+                send.send_response(Response::builder().body(Body::from(body)).unwrap());
+            }
+        });
+
+        let (events, rx) = futures::channel::mpsc::unbounded();
+        let mut reflected = std::pin::pin!(store.reflect(writer, rx));
+        let mut observe = async |event| {
+            events.unbounded_send(Ok(event)).unwrap();
+            reflected.next().await.unwrap().unwrap();
+        };
+        observe(watcher::Event::Apply(secret_meta("tls", "1"))).await;
+        observe(watcher::Event::Apply(secret_meta("other", "1"))).await;
+        store.get_full(&tls).await.unwrap().unwrap();
+        store.get_full(&other).await.unwrap().unwrap();
+        assert_eq!(store.cached_len(), 2);
+
+        // Evicted as soon as the update is observed, without another access
+        observe(watcher::Event::Apply(secret_meta("tls", "2"))).await;
+        assert_eq!(store.cached_len(), 1);
+        // A relist drops everything, since updates may have been missed
+        observe(watcher::Event::Init).await;
+        assert_eq!(store.cached_len(), 0);
+        server.await.unwrap();
+    }
+}
@@ -1,19 +1,24 @@
 //! Caches objects in memory
 
+mod derived;
 mod dispatcher;
+mod metadata_store;
 mod object_ref;
 pub mod store;
 
 pub use self::{
-    dispatcher::ReflectHandle,
+    derived::{DerivedChange, DerivedStore, DerivedView},
+    dispatcher::{BackpressurePolicy, ReflectHandle, SubscriberMetrics},
+    metadata_store::MetadataStore,
     object_ref::{Extra as ObjectRefExtra, Lookup, ObjectRef},
 };
 use crate::watcher;
 use async_stream::stream;
 use futures::{Stream, StreamExt};
 use std::hash::Hash;
-#[cfg(feature = "unstable-runtime-subscribe")] pub use store::store_shared;
 pub use store::{Store, store};
+#[cfg(feature = "unstable-runtime-subscribe")]
+pub use store::{store_shared, store_shared_with_policy};
 
 /// Cache objects from a [`watcher()`] stream into a local [`Store`]
 ///
@@ -1,7 +1,7 @@
 //! A reader/writer split store for reflectors
 use super::{Lookup, ObjectRef, dispatcher::Dispatcher};
 #[cfg(feature = "unstable-runtime-subscribe")]
-use crate::reflector::ReflectHandle;
+use crate::reflector::{BackpressurePolicy, ReflectHandle};
 use crate::{
     utils::delayed_init::{self, DelayedInit},
     watcher,
@@ -66,6 +66,20 @@ where
     /// `k8s_openapi` types) you can use `Default` instead.
     #[cfg(feature = "unstable-runtime-subscribe")]
     pub fn new_shared(buf_size: usize, dyntype: K::DynamicType) -> Self {
+        Self::new_shared_with_policy(buf_size, BackpressurePolicy::Block, dyntype)
+    }
+
+    /// Creates a new Writer with the specified dynamic type, buffer size and
+    /// [`BackpressurePolicy`].
+    ///
+    /// Like [`Writer::new_shared`], but `policy` decides what happens when
+    /// a subscriber falls behind, instead of always waiting for it.
+    #[cfg(feature = "unstable-runtime-subscribe")]
+    pub fn new_shared_with_policy(
+        buf_size: usize,
+        policy: BackpressurePolicy,
+        dyntype: K::DynamicType,
+    ) -> Self {
         let (ready_tx, ready_rx) = DelayedInit::new();
         Writer {
             store: Default::default(),
@@ -73,7 +87,7 @@ where
             dyntype,
             ready_tx: Some(ready_tx),
             ready_rx: Arc::new(ready_rx),
-            dispatcher: Some(Dispatcher::new(buf_size)),
+            dispatcher: Some(Dispatcher::new(buf_size, policy)),
         }
     }
 
@@ -89,6 +103,11 @@ where
         }
     }
 
+    /// The dynamic type of the objects in the store
+    pub(crate) fn dyntype(&self) -> &K::DynamicType {
+        &self.dyntype
+    }
+
     /// Return a handle to a subscriber
     ///
     /// Multiple subscribe handles may be obtained, by either calling
@@ -346,6 +365,22 @@ where
     (r, w)
 }
 
+/// Create a (Reader, Writer) for a `Store<K>` for a typed resource `K` with a [`BackpressurePolicy`]
+///
+/// Like [`store_shared`], but `policy` decides what happens when a subscriber
+/// cannot keep up with the root stream.
+#[must_use]
+#[cfg(feature = "unstable-runtime-subscribe")]
+pub fn store_shared_with_policy<K>(buf_size: usize, policy: BackpressurePolicy) -> (Store<K>, Writer<K>)
+where
+    K: Lookup + Clone + 'static,
+    K::DynamicType: Eq + Hash + Clone + Default,
+{
+    let w = Writer::<K>::new_shared_with_policy(buf_size, policy, Default::default());
+    let r = w.as_reader();
+    (r, w)
+}
+
 #[cfg(test)]
 mod tests {
     use super::{Writer, store};
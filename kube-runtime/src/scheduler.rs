@@ -2,11 +2,13 @@
 
 use futures::{Stream, StreamExt, stream::Fuse};
 use hashbrown::{HashMap, hash_map::RawEntryMut};
+use parking_lot::Mutex;
 use pin_project::pin_project;
 use std::{
     collections::HashSet,
     hash::Hash,
     pin::Pin,
+    sync::Arc,
     task::{Context, Poll},
     time::Duration,
 };
@@ -28,6 +30,30 @@ struct ScheduledEntry {
     queue_key: delay_queue::Key,
 }
 
+/// A shared view of the queue of a [`Scheduler`], which is updated every time it is polled
+///
+/// Used by the [`testing`](crate::testing) harness to tell when the scheduler has no more work to do.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct SchedulerProbe(Arc<Mutex<SchedulerState>>);
+
+/// The state of a [`Scheduler`] queue, as of its last poll
+#[derive(Clone, Copy, Debug, Default)]
+#[cfg_attr(not(feature = "unstable-runtime-testing"), allow(dead_code))]
+pub(crate) struct SchedulerState {
+    /// When the earliest scheduled message is due
+    pub(crate) next_run_at: Option<Instant>,
+    /// The number of messages that are due, but held by `hold_unless`
+    pub(crate) held: usize,
+}
+
+impl SchedulerProbe {
+    /// The state of the queue as of the last poll of the [`Scheduler`]
+    #[cfg(feature = "unstable-runtime-testing")]
+    pub(crate) fn state(&self) -> SchedulerState {
+        *self.0.lock()
+    }
+}
+
 /// A scheduler with all internal state
 ///
 /// Only expected to be constructed internally.
@@ -57,6 +83,8 @@ pub struct Scheduler<T, R> {
     /// for a request to be emitted, if the scheduler is "uninterrupted" for the configured
     /// debounce period. Its primary purpose to deduplicate requests that expire instantly.
     debounce: Duration,
+    /// Shared view of the queue, kept up to date after every poll
+    probe: Option<SchedulerProbe>,
 }
 
 impl<T, R: Stream> Scheduler<T, R> {
@@ -67,8 +95,15 @@ impl<T, R: Stream> Scheduler<T, R> {
             pending: HashSet::new(),
             requests: requests.fuse(),
             debounce,
+            probe: None,
         }
     }
+
+    /// Keep `probe` up to date with the state of the queue
+    pub(crate) fn with_probe(mut self, probe: Option<SchedulerProbe>) -> Self {
+        self.probe = probe;
+        self
+    }
 }
 
 impl<T: Hash + Eq + Clone, R> SchedulerProj<'_, T, R> {
@@ -138,6 +173,16 @@ impl<T: Hash + Eq + Clone, R> SchedulerProj<'_, T, R> {
         }
     }
 
+    /// Update the [`SchedulerProbe`], if any, with the current state of the queue
+    fn report(&self) {
+        if let Some(probe) = self.probe.as_ref() {
+            *probe.0.lock() = SchedulerState {
+                next_run_at: self.scheduled.values().map(|entry| entry.run_at).min(),
+                held: self.pending.len(),
+            };
+        }
+    }
+
     /// Attempt to retrieve a message from queue and mark it as pending.
     pub fn pop_queue_message_into_pending(&mut self, cx: &mut Context<'_>) {
         while let Poll::Ready(Some(msg)) = self.queue.poll_expired(cx) {
@@ -175,6 +220,7 @@ where
         }
 
         scheduler.pop_queue_message_into_pending(cx);
+        scheduler.report();
         Poll::Pending
     }
 }
@@ -206,7 +252,9 @@ where
             }
         }
 
-        match scheduler.poll_pop_queue_message(cx, can_take_message) {
+        let expired = scheduler.poll_pop_queue_message(cx, can_take_message);
+        scheduler.report();
+        match expired {
             Poll::Ready(expired) => Poll::Ready(Some(expired)),
             Poll::Pending => Poll::Pending,
         }
@@ -246,6 +294,21 @@ where
         Hold { scheduler: self }
     }
 
+    /// Removes all messages that are still scheduled or held pending, returning them
+    ///
+    /// This is intended to be used once `requests` has terminated, to find out which messages
+    /// were never emitted.
+    pub(crate) fn take_remaining(self: Pin<&mut Self>) -> Vec<T> {
+        let scheduler = self.project();
+        scheduler.queue.clear();
+        scheduler
+            .scheduled
+            .drain()
+            .map(|(msg, _)| msg)
+            .chain(scheduler.pending.drain())
+            .collect()
+    }
+
     /// Checks whether `msg` is currently a pending message (held by `hold_unless`)
     #[cfg(test)]
     pub fn contains_pending(&self, msg: &T) -> bool {
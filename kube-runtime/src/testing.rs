@@ -0,0 +1,613 @@
+//! A deterministic harness for testing controllers with simulated time
+//!
+//! The [`ControllerHarness`] runs an [`applier`] against an in-memory [`Store`] that is fed with
+//! injected [`watcher::Event`]s, and records everything the controller does: reconciliations and
+//! the [`Action`]s they return, [`error_policy`](crate::Controller::run) invocations, and the requests sent
+//! through [`ControllerHarness::client`].
+//!
+//! The controller is driven from the test task itself, and time only moves when the test calls
+//! [`ControllerHarness::advance`], so the same test always observes the same sequence of records.
+//! The harness tracks the reconciles in flight and the queue of the scheduler, so
+//! [`ControllerHarness::settle`] returns exactly once the controller has nothing left to do at the current time.
+//!
+//! The harness must be used on a current-thread runtime with paused time, such as
+//! `#[tokio::test(start_paused = true)]`.
+//!
+//! ```
+//! use k8s_openapi::api::core::v1::ConfigMap;
+//! use kube::api::{Api, Patch, PatchParams, ResourceExt};
+//! use kube_runtime::{
+//!     controller::Action,
+//!     reflector::ObjectRef,
+//!     testing::{ControllerHarness, Record},
+//! };
+//! use std::{sync::Arc, time::Duration};
+//!
+//! async fn reconcile(cm: Arc<ConfigMap>, api: Arc<Api<ConfigMap>>) -> Result<Action, kube::Error> {
+//!     let patch = serde_json::json!({ "metadata": { "labels": { "seen": "true" } } });
+//!     api.patch(&cm.name_any(), &PatchParams::default(), &Patch::Merge(patch)).await?;
+//!     Ok(Action::requeue(Duration::from_secs(60)))
+//! }
+//!
+//! fn error_policy(_cm: Arc<ConfigMap>, _err: &kube::Error, _api: Arc<Api<ConfigMap>>) -> Action {
+//!     Action::requeue(Duration::from_secs(5))
+//! }
+//!
+//! # #[tokio::main(flavor = "current_thread", start_paused = true)]
+//! # async fn main() {
+//! let mut harness = ControllerHarness::<ConfigMap>::new();
+//! let api = Api::<ConfigMap>::namespaced(harness.client(), "default");
+//! harness.start(reconcile, error_policy, Arc::new(api));
+//!
+//! let mut cm = ConfigMap::default();
+//! cm.metadata.name = Some("foo".to_string());
+//! cm.metadata.namespace = Some("default".to_string());
+//! harness.apply(cm);
+//! harness.settle().await;
+//!
+//! let records = harness.take_records();
+//! assert!(matches!(&records[0], Record::Request(req) if req.method == "PATCH"));
+//! assert_eq!(records[1], Record::Reconciled {
+//!     object: ObjectRef::new("foo").within("default"),
+//!     action: Action::requeue(Duration::from_secs(60)),
+//! });
+//!
+//! // Nothing happens until the requeue is due
+//! harness.advance(Duration::from_secs(59)).await;
+//! assert!(harness.take_records().is_empty());
+//! harness.advance(Duration::from_secs(1)).await;
+//! assert_eq!(harness.take_records().len(), 2);
+//! # }
+//! ```
+use crate::{
+    controller::{self, Action, Config},
+    reflector::{self, ObjectRef, Store, store::Writer},
+    scheduler::SchedulerProbe,
+    utils::WatchStreamExt,
+    watcher,
+};
+use educe::Educe;
+use futures::{
+    FutureExt, StreamExt, TryFuture, TryFutureExt,
+    channel::mpsc,
+    future::LocalBoxFuture,
+    stream::LocalBoxStream,
+    task::{ArcWake, AtomicWaker, waker_ref},
+};
+use kube_client::{Client, Resource, client::Body};
+use parking_lot::Mutex;
+use std::{
+    convert::Infallible,
+    fmt::Debug,
+    hash::Hash,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+    },
+    task::{Context, Poll},
+    time::Duration,
+};
+use tokio::time::Instant;
+
+/// A request that was sent through the [`ControllerHarness::client`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ApiRequest {
+    /// The HTTP method of the request
+    pub method: http::Method,
+    /// The path and query of the request URI
+    pub path: String,
+    /// The raw request body
+    pub body: Vec<u8>,
+}
+
+impl ApiRequest {
+    /// Deserialize the request body as JSON
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the body is not valid JSON for `T`.
+    pub fn json<T: serde::de::DeserializeOwned>(&self) -> serde_json::Result<T> {
+        serde_json::from_slice(&self.body)
+    }
+}
+
+/// Something that the controller did, in the order it happened
+#[derive(Educe)]
+#[educe(
+    Debug(bound("K::DynamicType: Debug")),
+    Clone(bound("K::DynamicType: Clone")),
+    PartialEq(bound("K::DynamicType: PartialEq"))
+)]
+pub enum Record<K: Resource> {
+    /// The reconciler finished successfully with an [`Action`]
+    Reconciled {
+        /// The object that was reconciled
+        object: ObjectRef<K>,
+        /// The action returned by the reconciler
+        action: Action,
+    },
+    /// The reconciler failed, and the error policy was invoked
+    ErrorPolicy {
+        /// The object that was reconciled
+        object: ObjectRef<K>,
+        /// The error returned by the reconciler, formatted with [`Display`](std::fmt::Display)
+        error: String,
+        /// The action returned by the error policy
+        action: Action,
+    },
+    /// A request was sent through the [`ControllerHarness::client`]
+    Request(ApiRequest),
+}
+
+type Records<K> = Arc<Mutex<Vec<Record<K>>>>;
+type Responder = Arc<dyn Fn(&ApiRequest) -> http::Response<Vec<u8>> + Send + Sync>;
+
+/// Runs a controller against an in-memory [`Store`] with simulated time
+///
+/// See the [module documentation](self) for an example.
+pub struct ControllerHarness<K>
+where
+    K: Resource + Clone + 'static,
+    K::DynamicType: Eq + Hash + Clone,
+{
+    reader: Store<K>,
+    writer: Option<Writer<K>>,
+    events_tx: mpsc::UnboundedSender<watcher::Event<K>>,
+    events_rx: Option<mpsc::UnboundedReceiver<watcher::Event<K>>>,
+    config: Config,
+    records: Records<K>,
+    responder: Arc<Mutex<Responder>>,
+    controller: Option<LocalBoxStream<'static, ()>>,
+    in_flight: Arc<AtomicUsize>,
+    scheduler: SchedulerProbe,
+    wake: Arc<WakeFlag>,
+}
+
+impl<K> Debug for ControllerHarness<K>
+where
+    K: Resource + Clone + 'static,
+    K::DynamicType: Eq + Hash + Clone,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ControllerHarness")
+            .field("started", &self.controller.is_some())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<K> Default for ControllerHarness<K>
+where
+    K: Resource + Clone + Debug + 'static,
+    K::DynamicType: Debug + Eq + Hash + Clone + Default + Unpin,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K> ControllerHarness<K>
+where
+    K: Resource + Clone + Debug + 'static,
+    K::DynamicType: Debug + Eq + Hash + Clone + Default + Unpin,
+{
+    /// Create a harness with an empty [`Store`]
+    ///
+    /// The [`Store`] is marked as ready straight away, as if the initial list returned no objects.
+    ///
+    /// By default, the [`ControllerHarness::client`] answers every request with a `200 OK` that echoes
+    /// the request body; use [`ControllerHarness::respond_with`] to change that.
+    #[must_use]
+    pub fn new() -> Self {
+        let (reader, writer) = reflector::store();
+        let (events_tx, events_rx) = mpsc::unbounded();
+        // Controllers hold off until the store is ready, so start out with an empty initial list
+        for event in [watcher::Event::Init, watcher::Event::InitDone] {
+            let _ = events_tx.unbounded_send(event);
+        }
+        Self {
+            reader,
+            writer: Some(writer),
+            events_tx,
+            events_rx: Some(events_rx),
+            config: Config::default(),
+            records: Arc::default(),
+            responder: Arc::new(Mutex::new(Arc::new(echo))),
+            controller: None,
+            in_flight: Arc::default(),
+            scheduler: SchedulerProbe::default(),
+            wake: Arc::default(),
+        }
+    }
+
+    /// Use `config` for the controller
+    ///
+    /// Must be called before [`ControllerHarness::start`] to take effect.
+    #[must_use]
+    pub fn with_config(mut self, config: Config) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Answer the requests sent through the [`ControllerHarness::client`] with `responder`
+    pub fn respond_with(
+        &self,
+        responder: impl Fn(&ApiRequest) -> http::Response<Vec<u8>> + Send + Sync + 'static,
+    ) {
+        *self.responder.lock() = Arc::new(responder);
+    }
+
+    /// A [`Client`] that records every request, and answers it through the responder
+    #[must_use]
+    pub fn client(&self) -> Client {
+        let records = self.records.clone();
+        let responder = self.responder.clone();
+        let service = tower::service_fn(move |req: http::Request<Body>| {
+            let records = records.clone();
+            let responder = responder.lock().clone();
+            async move {
+                let (parts, body) = req.into_parts();
+                let body = body.collect_bytes().await.map(|b| b.to_vec()).unwrap_or_default();
+                let request = ApiRequest {
+                    method: parts.method,
+                    path: parts
+                        .uri
+                        .path_and_query()
+                        .map_or_else(|| parts.uri.path().to_string(), ToString::to_string),
+                    body,
+                };
+                let response = responder(&request).map(Body::from);
+                records.lock().push(Record::Request(request));
+                Ok::<_, Infallible>(response)
+            }
+        });
+        Client::new(service, "default")
+    }
+
+    /// A read handle to the [`Store`] that the controller reads from
+    #[must_use]
+    pub fn store(&self) -> Store<K> {
+        self.reader.clone()
+    }
+
+    /// Start the controller
+    ///
+    /// The reconciler and error policy have the same signatures as for [`Controller::run`](crate::Controller::run),
+    /// but the reconciler futures do not need to be `Send`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the controller has already been started.
+    pub fn start<ReconcilerFut, Ctx>(
+        &mut self,
+        mut reconciler: impl FnMut(Arc<K>, Arc<Ctx>) -> ReconcilerFut + 'static,
+        error_policy: impl Fn(Arc<K>, &ReconcilerFut::Error, Arc<Ctx>) -> Action + 'static,
+        context: Arc<Ctx>,
+    ) where
+        ReconcilerFut: TryFuture<Ok = Action> + 'static,
+        ReconcilerFut::Error: std::error::Error + 'static,
+        Ctx: 'static,
+    {
+        let (Some(writer), Some(events_rx)) = (self.writer.take(), self.events_rx.take()) else {
+            panic!("ControllerHarness::start called twice");
+        };
+        let queue = controller::trigger_self(
+            reflector::reflector(writer, events_rx.map(Ok)).applied_objects(),
+            K::DynamicType::default(),
+        );
+
+        let reconcile_records = self.records.clone();
+        let in_flight = self.in_flight.clone();
+        let reconciler =
+            move |obj: Arc<K>, ctx| -> LocalBoxFuture<'static, Result<Action, ReconcilerFut::Error>> {
+                let object = ObjectRef::from_obj(&*obj);
+                let records = reconcile_records.clone();
+                let guard = InFlight::new(&in_flight);
+                TryFutureExt::into_future(reconciler(obj, ctx))
+                    .inspect(move |res| {
+                        drop(guard);
+                        if let Ok(action) = res {
+                            records.lock().push(Record::Reconciled {
+                                object,
+                                action: action.clone(),
+                            });
+                        }
+                    })
+                    .boxed_local()
+            };
+        let error_records = self.records.clone();
+        let error_policy = move |obj: Arc<K>, err: &ReconcilerFut::Error, ctx| {
+            let action = error_policy(obj.clone(), err, ctx);
+            error_records.lock().push(Record::ErrorPolicy {
+                object: ObjectRef::from_obj(&*obj),
+                error: err.to_string(),
+                action: action.clone(),
+            });
+            action
+        };
+
+        let controller = controller::applier_with_hooks(
+            reconciler,
+            error_policy,
+            context,
+            self.reader.clone(),
+            queue,
+            self.config.clone(),
+            None,
+            Some(self.scheduler.clone()),
+        )
+        .map(|_| ());
+        self.controller = Some(controller.boxed_local());
+    }
+
+    /// Inject a [`watcher::Event::Apply`] for `obj`
+    pub fn apply(&self, obj: K) {
+        self.send(watcher::Event::Apply(obj));
+    }
+
+    /// Inject a [`watcher::Event::Delete`] for `obj`
+    pub fn delete(&self, obj: K) {
+        self.send(watcher::Event::Delete(obj));
+    }
+
+    /// Inject a full relist of `objs`, replacing the contents of the [`Store`]
+    pub fn relist(&self, objs: impl IntoIterator<Item = K>) {
+        self.send(watcher::Event::Init);
+        for obj in objs {
+            self.send(watcher::Event::InitApply(obj));
+        }
+        self.send(watcher::Event::InitDone);
+    }
+
+    /// Inject an arbitrary [`watcher::Event`]
+    pub fn send(&self, event: watcher::Event<K>) {
+        // The receiver is only dropped along with the harness
+        let _ = self.events_tx.unbounded_send(event);
+    }
+
+    /// Run the controller until it has no more work to do at the current time
+    ///
+    /// Injected events are not processed until this (or [`ControllerHarness::advance`]) is called.
+    ///
+    /// Returns once no reconciles are in flight, the scheduler has no messages that are due or held,
+    /// and no timer that is due at the current time can wake the controller again.
+    ///
+    /// # Panics
+    ///
+    /// Panics if reconciles are still in flight once the controller is idle, since they are waiting
+    /// for timers that only fire after [`ControllerHarness::advance`] has moved the clock forward.
+    pub async fn settle(&mut self) {
+        self.run_until_idle().await;
+        let in_flight = self.in_flight();
+        assert!(
+            in_flight == 0,
+            "{in_flight} reconciles are waiting for the clock, use ControllerHarness::advance to move it forward"
+        );
+    }
+
+    /// Move the simulated clock forward by `duration`, and run the controller until it is idle again
+    ///
+    /// Requeues, debounces and other timers that are due by then will fire. Unlike
+    /// [`ControllerHarness::settle`], reconciles that are still waiting for a later timer stay
+    /// in flight, see [`ControllerHarness::in_flight`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if time is not paused.
+    pub async fn advance(&mut self, duration: Duration) {
+        self.run_until_idle().await;
+        tokio::time::advance(duration).await;
+        self.run_until_idle().await;
+    }
+
+    /// The number of reconciles that have started, but not finished yet
+    #[must_use]
+    pub fn in_flight(&self) -> usize {
+        self.in_flight.load(Ordering::SeqCst)
+    }
+
+    /// Poll the controller until nothing can wake it at the current time
+    ///
+    /// Requests made through the [`ControllerHarness::client`] are handled by a background task and
+    /// timers fire from the runtime's driver, so the test task yields to both whenever the
+    /// controller is pending, and only stops once that did not wake the controller.
+    async fn run_until_idle(&mut self) {
+        let Some(controller) = self.controller.as_mut() else {
+            return;
+        };
+        loop {
+            self.wake.woken.store(false, Ordering::SeqCst);
+            let poll = std::future::poll_fn(|cx| {
+                self.wake.waker.register(cx.waker());
+                let waker = waker_ref(&self.wake);
+                Poll::Ready(controller.poll_next_unpin(&mut Context::from_waker(&waker)))
+            })
+            .await;
+            match poll {
+                Poll::Ready(Some(())) => continue,
+                Poll::Ready(None) => {
+                    self.controller = None;
+                    return;
+                }
+                Poll::Pending => {}
+            }
+            if !self.wake.woken.load(Ordering::SeqCst) {
+                tokio::task::yield_now().await;
+            }
+            let woken = self.wake.woken.load(Ordering::SeqCst);
+            let scheduler = self.scheduler.state();
+            let due = scheduler
+                .next_run_at
+                .is_some_and(|run_at| run_at <= Instant::now());
+            if !woken && !due && (scheduler.held == 0 || self.in_flight.load(Ordering::SeqCst) > 0) {
+                return;
+            }
+        }
+    }
+
+    /// Return and clear everything recorded so far
+    #[must_use]
+    pub fn take_records(&self) -> Vec<Record<K>> {
+        std::mem::take(&mut *self.records.lock())
+    }
+}
+
+/// Decrements the count of reconciles in flight when the reconciler future finishes or is dropped
+struct InFlight(Arc<AtomicUsize>);
+
+impl InFlight {
+    fn new(count: &Arc<AtomicUsize>) -> Self {
+        count.fetch_add(1, Ordering::SeqCst);
+        Self(count.clone())
+    }
+}
+
+impl Drop for InFlight {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// Waker for the controller, which remembers whether it was woken before waking the test task
+#[derive(Default)]
+struct WakeFlag {
+    woken: AtomicBool,
+    waker: AtomicWaker,
+}
+
+impl ArcWake for WakeFlag {
+    fn wake_by_ref(arc_self: &Arc<Self>) {
+        arc_self.woken.store(true, Ordering::SeqCst);
+        arc_self.waker.wake();
+    }
+}
+
+/// The default responder, which echoes the request body back
+fn echo(request: &ApiRequest) -> http::Response<Vec<u8>> {
+    let body = if request.body.is_empty() {
+        b"{}".to_vec()
+    } else {
+        request.body.clone()
+    };
+    http::Response::new(body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ControllerHarness, Record};
+    use crate::{controller::Action, reflector::ObjectRef};
+    use k8s_openapi::api::core::v1::ConfigMap;
+    use kube_client::Api;
+    use std::{sync::Arc, time::Duration};
+
+    #[derive(Debug, thiserror::Error)]
+    #[error("boom")]
+    struct Boom;
+
+    fn configmap(name: &str, fail: bool) -> ConfigMap {
+        let mut cm = ConfigMap::default();
+        cm.metadata.name = Some(name.to_string());
+        cm.metadata.namespace = Some("ns".to_string());
+        if fail {
+            cm.data = Some([("fail".to_string(), String::new())].into());
+        }
+        cm
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn harness_records_reconciles_and_error_policy() {
+        let mut harness = ControllerHarness::<ConfigMap>::new();
+        harness.start(
+            |cm: Arc<ConfigMap>, _ctx: Arc<()>| async move {
+                if cm.data.is_some() {
+                    Err(Boom)
+                } else {
+                    Ok(Action::requeue(Duration::from_secs(10)))
+                }
+            },
+            |_, _, _| Action::requeue(Duration::from_secs(3)),
+            Arc::new(()),
+        );
+        let ok = ObjectRef::new("ok").within("ns");
+        let bad = ObjectRef::new("bad").within("ns");
+
+        harness.apply(configmap("ok", false));
+        harness.settle().await;
+        assert_eq!(harness.take_records(), [Record::Reconciled {
+            object: ok.clone(),
+            action: Action::requeue(Duration::from_secs(10)),
+        }]);
+
+        harness.apply(configmap("bad", true));
+        harness.settle().await;
+        assert_eq!(harness.take_records(), [Record::ErrorPolicy {
+            object: bad.clone(),
+            error: "boom".to_string(),
+            action: Action::requeue(Duration::from_secs(3)),
+        }]);
+
+        // Retries happen exactly when they are due
+        harness.advance(Duration::from_millis(2999)).await;
+        assert!(harness.take_records().is_empty());
+        harness.advance(Duration::from_millis(1)).await;
+        assert_eq!(harness.take_records().len(), 1);
+        harness.advance(Duration::from_secs(3)).await;
+        assert_eq!(harness.take_records().len(), 1);
+
+        // Deleted objects are no longer retried
+        harness.delete(configmap("bad", true));
+        harness.advance(Duration::from_secs(4)).await;
+        assert_eq!(harness.take_records(), [Record::Reconciled {
+            object: ok,
+            action: Action::requeue(Duration::from_secs(10)),
+        }]);
+    }
+
+    fn sleepy_harness() -> ControllerHarness<ConfigMap> {
+        let mut harness = ControllerHarness::<ConfigMap>::new();
+        let api = Api::<ConfigMap>::namespaced(harness.client(), "ns");
+        harness.start(
+            |cm: Arc<ConfigMap>, api: Arc<Api<ConfigMap>>| async move {
+                for _ in 0..3 {
+                    api.get(cm.metadata.name.as_deref().unwrap()).await.ok();
+                }
+                tokio::time::sleep(Duration::from_secs(5)).await;
+                Ok::<_, Boom>(Action::await_change())
+            },
+            |_, _, _| Action::await_change(),
+            Arc::new(api),
+        );
+        harness.apply(configmap("slow", false));
+        harness
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn harness_tracks_requests_and_reconciles_waiting_for_the_clock() {
+        let mut harness = sleepy_harness();
+
+        harness.advance(Duration::ZERO).await;
+        let records = harness.take_records();
+        assert_eq!(records.len(), 3);
+        assert!(
+            records
+                .iter()
+                .all(|r| matches!(r, Record::Request(req) if req.method == "GET"))
+        );
+        assert_eq!(harness.in_flight(), 1);
+
+        harness.advance(Duration::from_millis(4999)).await;
+        assert!(harness.take_records().is_empty());
+        harness.advance(Duration::from_millis(1)).await;
+        assert_eq!(harness.take_records(), [Record::Reconciled {
+            object: ObjectRef::new("slow").within("ns"),
+            action: Action::await_change(),
+        }]);
+        assert_eq!(harness.in_flight(), 0);
+        harness.settle().await;
+    }
+
+    #[tokio::test(start_paused = true)]
+    #[should_panic(expected = "1 reconciles are waiting for the clock")]
+    async fn harness_settle_rejects_reconciles_waiting_for_the_clock() {
+        sleepy_harness().settle().await;
+    }
+}
@@ -0,0 +1,270 @@
+use core::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+use std::{hash::Hash, time::Duration};
+
+use ahash::AHashMap;
+use futures::{Stream, TryStream};
+use pin_project::pin_project;
+use tokio::time::{Sleep, sleep};
+
+use crate::{
+    reflector::{Lookup, ObjectRef},
+    watcher::{Error, Event},
+};
+
+#[pin_project]
+/// Stream returned by the [`batch`](super::WatchStreamExt::batch) method.
+///
+/// Groups [`Event`]s into batches of at most `max_items` events, flushing a partial batch once
+/// `max_wait` has passed since its first event.
+pub struct Batch<St, K>
+where
+    K: Lookup,
+    K::DynamicType: Eq + Hash,
+{
+    #[pin]
+    stream: St,
+    max_items: usize,
+    max_wait: Duration,
+    #[pin]
+    deadline: Option<Sleep>,
+    dyntype: K::DynamicType,
+    pending: Vec<Event<K>>,
+    /// Index of the event for each object in `batch`
+    positions: AHashMap<ObjectRef<K>, usize>,
+    /// An error that was received while a batch was pending, emitted after the batch
+    error: Option<Error>,
+    /// Whether the inner stream is in the middle of a relist (between `Init` and `InitDone`)
+    relisting: bool,
+    done: bool,
+}
+
+impl<St, K> Batch<St, K>
+where
+    St: TryStream<Ok = Event<K>>,
+    K: Lookup,
+    K::DynamicType: Eq + Hash + Clone + Default,
+{
+    pub(super) fn new(stream: St, max_items: usize, max_wait: Duration) -> Batch<St, K> {
+        Self {
+            stream,
+            max_items: max_items.max(1),
+            max_wait,
+            deadline: None,
+            dyntype: K::DynamicType::default(),
+            pending: Vec::new(),
+            positions: AHashMap::new(),
+            error: None,
+            relisting: false,
+            done: false,
+        }
+    }
+}
+
+impl<St, K> Stream for Batch<St, K>
+where
+    St: Stream<Item = Result<Event<K>, Error>>,
+    K: Lookup,
+    K::DynamicType: Eq + Hash + Clone,
+{
+    type Item = Result<Vec<Event<K>>, Error>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut me = self.project();
+        if let Some(err) = me.error.take() {
+            return Poll::Ready(Some(Err(err)));
+        }
+        loop {
+            if *me.done {
+                return Poll::Ready(flush(me.pending, me.positions).map(Ok));
+            }
+            if let Some(deadline) = me.deadline.as_mut().as_pin_mut()
+                && deadline.poll(cx).is_ready()
+            {
+                me.deadline.set(None);
+                if let Some(batch) = flush(me.pending, me.positions) {
+                    return Poll::Ready(Some(Ok(batch)));
+                }
+            }
+            let event = match me.stream.as_mut().poll_next(cx) {
+                Poll::Ready(Some(Ok(event))) => event,
+                Poll::Ready(Some(Err(err))) => {
+                    // Keep the order of events and errors, the error follows the pending batch
+                    return Poll::Ready(Some(match flush(me.pending, me.positions) {
+                        Some(batch) => {
+                            me.deadline.set(None);
+                            *me.error = Some(err);
+                            Ok(batch)
+                        }
+                        None => Err(err),
+                    }));
+                }
+                Poll::Ready(None) => {
+                    *me.done = true;
+                    me.deadline.set(None);
+                    continue;
+                }
+                Poll::Pending => return Poll::Pending,
+            };
+
+            // Relists are batched separately from live events
+            let boundary = match &event {
+                Event::Init => {
+                    *me.relisting = true;
+                    !me.pending.is_empty()
+                }
+                Event::Apply(_) | Event::Delete(_) => *me.relisting,
+                Event::InitApply(_) | Event::InitDone => false,
+            };
+            let flushed = if boundary {
+                me.deadline.set(None);
+                flush(me.pending, me.positions)
+            } else {
+                None
+            };
+            if matches!(event, Event::InitDone) {
+                *me.relisting = false;
+            }
+
+            push(me.pending, me.positions, me.dyntype, event);
+            if me.deadline.is_none() {
+                me.deadline.set(Some(sleep(*me.max_wait)));
+            }
+
+            if let Some(batch) = flushed {
+                return Poll::Ready(Some(Ok(batch)));
+            }
+            if me.pending.len() >= *me.max_items || matches!(me.pending.last(), Some(Event::InitDone)) {
+                me.deadline.set(None);
+                return Poll::Ready(flush(me.pending, me.positions).map(Ok));
+            }
+        }
+    }
+}
+
+/// Add `event` to the batch, replacing any earlier event for the same object
+fn push<K>(
+    batch: &mut Vec<Event<K>>,
+    positions: &mut AHashMap<ObjectRef<K>, usize>,
+    dyntype: &K::DynamicType,
+    event: Event<K>,
+) where
+    K: Lookup,
+    K::DynamicType: Eq + Hash + Clone,
+{
+    let obj_ref = match &event {
+        Event::Apply(obj) | Event::Delete(obj) | Event::InitApply(obj) => obj.to_object_ref(dyntype.clone()),
+        Event::Init | Event::InitDone => {
+            batch.push(event);
+            return;
+        }
+    };
+    if let Some(&pos) = positions.get(&obj_ref) {
+        batch[pos] = event;
+    } else {
+        positions.insert(obj_ref, batch.len());
+        batch.push(event);
+    }
+}
+
+fn flush<K>(batch: &mut Vec<Event<K>>, positions: &mut AHashMap<ObjectRef<K>, usize>) -> Option<Vec<Event<K>>>
+where
+    K: Lookup,
+    K::DynamicType: Eq + Hash,
+{
+    positions.clear();
+    (!batch.is_empty()).then(|| std::mem::take(batch))
+}
+
+#[cfg(test)]
+pub(crate) mod test {
+    use std::{pin::pin, task::Poll, time::Duration};
+
+    use super::{Batch, Error, Event};
+    use futures::{SinkExt, StreamExt, channel::mpsc, poll, stream};
+    use k8s_openapi::api::core::v1::Pod;
+
+    fn testpod(name: &str, resource_version: &str) -> Pod {
+        let mut pod = Pod::default();
+        pod.metadata.name = Some(name.to_string());
+        pod.metadata.resource_version = Some(resource_version.to_string());
+        pod
+    }
+
+    fn describe(batch: &[Event<Pod>]) -> Vec<String> {
+        batch
+            .iter()
+            .map(|event| match event {
+                Event::Apply(pod) | Event::Delete(pod) | Event::InitApply(pod) => {
+                    let kind = match event {
+                        Event::Apply(_) => "apply",
+                        Event::Delete(_) => "delete",
+                        _ => "init_apply",
+                    };
+                    format!(
+                        "{kind} {}@{}",
+                        pod.metadata.name.as_ref().unwrap(),
+                        pod.metadata.resource_version.as_ref().unwrap()
+                    )
+                }
+                Event::Init => "init".to_string(),
+                Event::InitDone => "init_done".to_string(),
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn batch_deduplicates_and_splits_on_relists() {
+        let st = stream::iter(vec![
+            Ok(Event::Apply(testpod("a", "1"))),
+            Ok(Event::Apply(testpod("b", "1"))),
+            Ok(Event::Apply(testpod("a", "2"))),
+            Ok(Event::Init),
+            Ok(Event::InitApply(testpod("a", "3"))),
+            Ok(Event::InitDone),
+            Ok(Event::Delete(testpod("a", "4"))),
+            Err(Error::NoResourceVersion),
+            Ok(Event::Apply(testpod("b", "2"))),
+            Ok(Event::Apply(testpod("c", "1"))),
+            Ok(Event::Apply(testpod("d", "1"))),
+        ]);
+        let mut batches = pin!(Batch::new(st, 3, Duration::from_secs(10)));
+
+        let mut next = async || match batches.next().await {
+            Some(Ok(batch)) => Ok(describe(&batch)),
+            Some(Err(err)) => Err(err.to_string()),
+            None => Err("end".to_string()),
+        };
+        assert_eq!(next().await.unwrap(), ["apply a@2", "apply b@1"]);
+        assert_eq!(next().await.unwrap(), ["init", "init_apply a@3", "init_done"]);
+        assert_eq!(next().await.unwrap(), ["delete a@4"]);
+        assert!(next().await.is_err());
+        assert_eq!(next().await.unwrap(), ["apply b@2", "apply c@1", "apply d@1"]);
+        assert_eq!(next().await.unwrap_err(), "end");
+    }
+
+    #[tokio::test]
+    async fn batch_flushes_after_max_wait() {
+        tokio::time::pause();
+        let (mut tx, rx) = mpsc::unbounded();
+        let mut batches = pin!(Batch::new(rx.map(Ok), 10, Duration::from_secs(1)));
+
+        tx.send(Event::Apply(testpod("a", "1"))).await.unwrap();
+        assert!(poll!(batches.next()).is_pending());
+        tokio::time::advance(Duration::from_millis(500)).await;
+        tx.send(Event::Apply(testpod("b", "1"))).await.unwrap();
+        assert!(poll!(batches.next()).is_pending());
+
+        tokio::time::advance(Duration::from_millis(501)).await;
+        match poll!(batches.next()) {
+            Poll::Ready(Some(Ok(batch))) => assert_eq!(describe(&batch), ["apply a@1", "apply b@1"]),
+            other => panic!("expected a batch, got {other:?}"),
+        }
+        assert!(poll!(batches.next()).is_pending());
+
+        drop(tx);
+        assert!(matches!(poll!(batches.next()), Poll::Ready(None)));
+    }
+}
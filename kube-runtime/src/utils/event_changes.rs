@@ -0,0 +1,245 @@
+use core::{
+    pin::Pin,
+    task::{Context, Poll, ready},
+};
+use std::{collections::VecDeque, hash::Hash, sync::Arc};
+
+use ahash::AHashMap;
+use futures::{Stream, TryStream};
+use pin_project::pin_project;
+
+use crate::{
+    reflector::{Lookup, ObjectRef, Store, store::Writer},
+    watcher::{Error, Event},
+};
+
+/// A change to an object, as seen by the [`changes`](super::WatchStreamExt::changes) adapter
+///
+/// Similar to the `AddFunc`/`UpdateFunc`/`DeleteFunc` handlers of client-go informers.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ChangeEvent<K> {
+    /// An object was seen for the first time
+    Added(Arc<K>),
+    /// An object that was already known was changed
+    Modified {
+        /// The last known state of the object before the change
+        old: Arc<K>,
+        /// The new state of the object
+        new: Arc<K>,
+    },
+    /// An object was deleted
+    ///
+    /// Contains the last known state of the object.
+    Deleted(Arc<K>),
+}
+
+impl<K> ChangeEvent<K> {
+    /// The current state of the object, or the last known state if it was deleted
+    #[must_use]
+    pub fn object(&self) -> &Arc<K> {
+        match self {
+            ChangeEvent::Added(obj) | ChangeEvent::Modified { new: obj, .. } | ChangeEvent::Deleted(obj) => {
+                obj
+            }
+        }
+    }
+}
+
+#[pin_project]
+/// Stream returned by the [`changes`](super::WatchStreamExt::changes) method.
+///
+/// Reflects every [`Event`] into a [`Store`], and compares each object against
+/// the state that the [`Store`] had before the event was applied.
+pub struct Changes<St, K>
+where
+    K: Lookup + Clone + 'static,
+    K::DynamicType: Eq + Hash + Clone,
+{
+    #[pin]
+    stream: St,
+    writer: Writer<K>,
+    reader: Store<K>,
+    dyntype: K::DynamicType,
+    /// Changes that have been computed but not yet emitted (after a relist)
+    queue: VecDeque<ChangeEvent<K>>,
+}
+
+impl<St, K> Changes<St, K>
+where
+    St: TryStream<Ok = Event<K>>,
+    K: Lookup + Clone + 'static,
+    K::DynamicType: Eq + Hash + Clone,
+{
+    pub(super) fn new(stream: St, writer: Writer<K>) -> Changes<St, K> {
+        let reader = writer.as_reader();
+        let dyntype = writer.dyntype().clone();
+        Self {
+            stream,
+            writer,
+            reader,
+            dyntype,
+            queue: VecDeque::new(),
+        }
+    }
+}
+
+impl<St, K> Stream for Changes<St, K>
+where
+    St: Stream<Item = Result<Event<K>, Error>>,
+    K: Lookup + Clone + 'static,
+    K::DynamicType: Eq + Hash + Clone,
+{
+    type Item = Result<ChangeEvent<K>, Error>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut me = self.project();
+        loop {
+            if let Some(change) = me.queue.pop_front() {
+                return Poll::Ready(Some(Ok(change)));
+            }
+            let event = match ready!(me.stream.as_mut().poll_next(cx)) {
+                Some(Ok(event)) => event,
+                Some(Err(err)) => return Poll::Ready(Some(Err(err))),
+                None => return Poll::Ready(None),
+            };
+            match &event {
+                Event::Apply(obj) => {
+                    let key = obj.to_object_ref(me.dyntype.clone());
+                    let old = me.reader.get(&key);
+                    me.writer.apply_watcher_event(&event);
+                    let new = me
+                        .reader
+                        .get(&key)
+                        .expect("applied object was not found in the store");
+                    return Poll::Ready(Some(Ok(match old {
+                        Some(old) => ChangeEvent::Modified { old, new },
+                        None => ChangeEvent::Added(new),
+                    })));
+                }
+                Event::Delete(_) => {
+                    me.writer.apply_watcher_event(&event);
+                    let Event::Delete(obj) = event else { unreachable!() };
+                    return Poll::Ready(Some(Ok(ChangeEvent::Deleted(Arc::new(obj)))));
+                }
+                Event::Init | Event::InitApply(_) => me.writer.apply_watcher_event(&event),
+                Event::InitDone => {
+                    let mut old_state: AHashMap<ObjectRef<K>, Arc<K>> = me
+                        .reader
+                        .state()
+                        .into_iter()
+                        .map(|obj| (obj.to_object_ref(me.dyntype.clone()), obj))
+                        .collect();
+                    me.writer.apply_watcher_event(&event);
+                    for new in me.reader.state() {
+                        let key = new.to_object_ref(me.dyntype.clone());
+                        match old_state.remove(&key) {
+                            None => me.queue.push_back(ChangeEvent::Added(new)),
+                            // Objects that are relisted without changes are not reported
+                            Some(old) if old.resource_version() == new.resource_version() => {}
+                            Some(old) => me.queue.push_back(ChangeEvent::Modified { old, new }),
+                        }
+                    }
+                    // Anything that was not relisted must have been deleted while we weren't watching
+                    me.queue.extend(old_state.into_values().map(ChangeEvent::Deleted));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod test {
+    use std::{pin::pin, task::Poll};
+
+    use super::{ChangeEvent, Changes, Error, Event};
+    use crate::reflector;
+    use futures::{StreamExt, poll, stream};
+    use k8s_openapi::api::core::v1::Pod;
+
+    fn testpod(name: &str, resource_version: &str) -> Pod {
+        let mut pod = Pod::default();
+        pod.metadata.name = Some(name.to_string());
+        pod.metadata.resource_version = Some(resource_version.to_string());
+        pod
+    }
+
+    #[tokio::test]
+    async fn changes_include_previous_object() {
+        let st = stream::iter([
+            Ok(Event::Apply(testpod("foo", "1"))),
+            Ok(Event::Apply(testpod("foo", "2"))),
+            Err(Error::NoResourceVersion),
+            Ok(Event::Delete(testpod("foo", "3"))),
+        ]);
+        let (reader, writer) = reflector::store();
+        let mut changes = pin!(Changes::new(st, writer));
+
+        assert!(matches!(
+            poll!(changes.next()),
+            Poll::Ready(Some(Ok(ChangeEvent::Added(new)))) if new.metadata.resource_version.as_deref() == Some("1")
+        ));
+        assert!(matches!(
+            poll!(changes.next()),
+            Poll::Ready(Some(Ok(ChangeEvent::Modified { old, new })))
+                if old.metadata.resource_version.as_deref() == Some("1")
+                && new.metadata.resource_version.as_deref() == Some("2")
+        ));
+        assert_eq!(reader.len(), 1);
+        assert!(matches!(
+            poll!(changes.next()),
+            Poll::Ready(Some(Err(Error::NoResourceVersion)))
+        ));
+        assert!(matches!(
+            poll!(changes.next()),
+            Poll::Ready(Some(Ok(ChangeEvent::Deleted(last)))) if last.metadata.resource_version.as_deref() == Some("3")
+        ));
+        assert_eq!(reader.len(), 0);
+        assert!(matches!(poll!(changes.next()), Poll::Ready(None)));
+    }
+
+    #[tokio::test]
+    async fn changes_diff_relists_against_store() {
+        let st = stream::iter(vec![
+            Ok(Event::Init),
+            Ok(Event::InitApply(testpod("unchanged", "1"))),
+            Ok(Event::InitApply(testpod("changed", "1"))),
+            Ok(Event::InitApply(testpod("deleted", "1"))),
+            Ok(Event::InitDone),
+            Ok(Event::Init),
+            Ok(Event::InitApply(testpod("unchanged", "1"))),
+            Ok(Event::InitApply(testpod("changed", "2"))),
+            Ok(Event::InitApply(testpod("added", "1"))),
+            Ok(Event::InitDone),
+        ]);
+        let (reader, writer) = reflector::store();
+        let changes = Changes::new(st, writer);
+        let changes = changes.map(Result::unwrap).collect::<Vec<_>>().await;
+
+        let (initial, relist) = changes.split_at(3);
+        assert!(
+            initial
+                .iter()
+                .all(|change| matches!(change, ChangeEvent::Added(_)))
+        );
+        let mut relist = relist
+            .iter()
+            .map(|change| match change {
+                ChangeEvent::Added(new) => format!("added {}", new.metadata.name.as_ref().unwrap()),
+                ChangeEvent::Modified { old, new } => format!(
+                    "modified {} {}->{}",
+                    new.metadata.name.as_ref().unwrap(),
+                    old.metadata.resource_version.as_ref().unwrap(),
+                    new.metadata.resource_version.as_ref().unwrap()
+                ),
+                ChangeEvent::Deleted(last) => format!("deleted {}", last.metadata.name.as_ref().unwrap()),
+            })
+            .collect::<Vec<_>>();
+        relist.sort();
+        assert_eq!(relist, [
+            "added added",
+            "deleted deleted",
+            "modified changed 1->2"
+        ]);
+        assert_eq!(reader.len(), 3);
+    }
+}
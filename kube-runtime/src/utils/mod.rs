@@ -2,6 +2,8 @@
 
 mod backoff_reset_timer;
 pub(crate) mod delayed_init;
+mod event_batch;
+mod event_changes;
 mod event_decode;
 mod event_modify;
 mod predicate;
@@ -16,6 +18,8 @@ mod watch_ext;
 )]
 pub use EventDecode as EventFlatten;
 pub use backoff_reset_timer::{Backoff, ResetTimerBackoff};
+pub use event_batch::Batch;
+pub use event_changes::{ChangeEvent, Changes};
 pub use event_decode::EventDecode;
 pub use event_modify::EventModify;
 pub use predicate::{Config as PredicateConfig, Predicate, PredicateFilter, predicates};
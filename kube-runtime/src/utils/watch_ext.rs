@@ -1,5 +1,7 @@
 use crate::{
     utils::{
+        event_batch::Batch,
+        event_changes::Changes,
         event_decode::EventDecode,
         event_modify::EventModify,
         predicate::{Config as PredicateConfig, Predicate, PredicateFilter},
@@ -16,6 +18,7 @@ use crate::{
 
 use crate::watcher::DefaultBackoff;
 use futures::{Stream, TryStream};
+use std::time::Duration;
 
 /// Extension trait for streams returned by [`watcher`](watcher()) or [`reflector`](crate::reflector::reflector)
 pub trait WatchStreamExt: Stream {
@@ -186,6 +189,101 @@ pub trait WatchStreamExt: Stream {
         Reflect::new(self, writer)
     }
 
+    /// Reflect a [`watcher()`] stream into a [`Store`], and decode it into a stream of [`ChangeEvent`]s
+    ///
+    /// Every [`watcher::Event`] is passed through the [`Writer`] (like [`reflect`](Self::reflect)), and compared against the
+    /// previous state of the [`Store`] so that modifications carry both the old and the new object,
+    /// similar to the `UpdateFunc(old, new)` handler of client-go informers.
+    ///
+    /// Relists are diffed against the [`Store`] once they are complete, emitting `Added` for new objects,
+    /// `Modified` for objects whose `resourceVersion` has changed, and `Deleted` for objects that
+    /// disappeared while the watch was down. Objects that are relisted without changes are not emitted.
+    ///
+    /// NOTE: The [`Writer`] does not [dispatch](crate::reflector::store::Writer::subscribe) events to subscribers
+    /// when used through this adapter.
+    ///
+    /// ## Usage
+    /// ```no_run
+    /// # use futures::{StreamExt, TryStreamExt};
+    /// # use std::pin::pin;
+    /// use kube::{Api, ResourceExt};
+    /// use kube_runtime::{reflector, utils::ChangeEvent, watcher, WatchStreamExt};
+    /// use k8s_openapi::api::apps::v1::Deployment;
+    /// # async fn wrapper() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client: kube::Client = todo!();
+    ///
+    /// let deploys: Api<Deployment> = Api::default_namespaced(client);
+    /// let (reader, writer) = reflector::store::<Deployment>();
+    /// let mut changes = pin!(watcher(deploys, watcher::Config::default()).changes(writer));
+    ///
+    /// while let Some(change) = changes.try_next().await? {
+    ///     if let ChangeEvent::Modified { old, new } = change {
+    ///         if old.spec.as_ref().and_then(|s| s.replicas) != new.spec.as_ref().and_then(|s| s.replicas) {
+    ///             println!("{} was scaled", new.name_any());
+    ///         }
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`Store`]: crate::reflector::Store
+    /// [`ChangeEvent`]: crate::utils::ChangeEvent
+    fn changes<K>(self, writer: Writer<K>) -> Changes<Self, K>
+    where
+        Self: Stream<Item = watcher::Result<watcher::Event<K>>> + Sized,
+        K: Resource + Clone + 'static,
+        K::DynamicType: Eq + std::hash::Hash + Clone,
+    {
+        Changes::new(self, writer)
+    }
+
+    /// Group a [`watcher()`] stream into batches of [`watcher::Event`]s
+    ///
+    /// A batch is emitted once it holds `max_items` events, or once `max_wait` has passed since
+    /// its first event, whichever comes first. This is useful for writing to external systems in bulk.
+    ///
+    /// Within a batch, events are deduplicated by [`ObjectRef`], keeping the latest event for each
+    /// object at the position of its first occurrence.
+    ///
+    /// Relists are never mixed with live events: a batch is flushed before an
+    /// [`Init`](watcher::Event::Init), and the batch that contains an [`InitDone`](watcher::Event::InitDone)
+    /// is flushed straight away. Large relists may still be split over several batches of `max_items`.
+    ///
+    /// Errors are passed through after the batch that was pending when they occurred.
+    ///
+    /// ## Usage
+    /// ```no_run
+    /// # use futures::{StreamExt, TryStreamExt};
+    /// # use std::{pin::pin, time::Duration};
+    /// use kube::Api;
+    /// use kube_runtime::{watcher, WatchStreamExt};
+    /// use k8s_openapi::api::core::v1::Pod;
+    /// # async fn wrapper() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client: kube::Client = todo!();
+    ///
+    /// let pods: Api<Pod> = Api::default_namespaced(client);
+    /// let mut batches = pin!(watcher(pods, watcher::Config::default())
+    ///     .default_backoff()
+    ///     .batch(500, Duration::from_secs(5)));
+    ///
+    /// while let Some(batch) = batches.try_next().await? {
+    ///     println!("indexing {} events", batch.len());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`ObjectRef`]: crate::reflector::ObjectRef
+    fn batch<K>(self, max_items: usize, max_wait: Duration) -> Batch<Self, K>
+    where
+        Self: Stream<Item = watcher::Result<watcher::Event<K>>> + Sized,
+        K: Resource + 'static,
+        K::DynamicType: Eq + std::hash::Hash + Clone + Default,
+    {
+        Batch::new(self, max_items, max_wait)
+    }
+
     /// Reflect a shared [`watcher()`] stream into a [`Store`] through a [`Writer`]
     ///
     /// Returns the stream unmodified, but passes every [`watcher::Event`]
@@ -10,11 +10,12 @@ use futures::{Stream, StreamExt, stream::BoxStream};
 use kube_client::{
     Api, Error as ClientErr,
     api::{ListParams, Resource, ResourceExt, VersionMatch, WatchEvent, WatchParams},
-    core::{ObjectList, Selector, metadata::PartialObjectMeta},
+    client::ObjectListStream,
+    core::{Selector, metadata::PartialObjectMeta},
     error::Status,
 };
 use serde::de::DeserializeOwned;
-use std::{clone::Clone, collections::VecDeque, fmt::Debug, future, time::Duration};
+use std::{clone::Clone, fmt::Debug, future, time::Duration};
 use thiserror::Error;
 use tracing::{debug, error, warn};
 
@@ -121,7 +122,9 @@ enum State<K> {
     /// The Watcher is in the process of paginating through the initial LIST
     InitPage {
         continue_token: Option<String>,
-        objects: VecDeque<K>,
+        /// The page being decoded, if any
+        #[educe(Debug(ignore))]
+        page: Option<Box<ObjectListStream<K>>>,
         last_bookmark: Option<String>,
     },
     /// Kubernetes 1.27 Streaming Lists
@@ -148,9 +151,9 @@ enum State<K> {
 /// Used to control whether the watcher receives the full object, or only the
 /// metadata
 trait ApiMode {
-    type Value: Clone;
+    type Value: Clone + DeserializeOwned;
 
-    async fn list(&self, lp: &ListParams) -> kube_client::Result<ObjectList<Self::Value>>;
+    async fn list(&self, lp: &ListParams) -> kube_client::Result<ObjectListStream<Self::Value>>;
     async fn watch(
         &self,
         wp: &WatchParams,
@@ -437,8 +440,8 @@ where
 {
     type Value = K;
 
-    async fn list(&self, lp: &ListParams) -> kube_client::Result<ObjectList<Self::Value>> {
-        self.api.list(lp).await
+    async fn list(&self, lp: &ListParams) -> kube_client::Result<ObjectListStream<Self::Value>> {
+        self.api.list_items(lp).await
     }
 
     async fn watch(
@@ -462,8 +465,8 @@ where
 {
     type Value = PartialObjectMeta<K>;
 
-    async fn list(&self, lp: &ListParams) -> kube_client::Result<ObjectList<Self::Value>> {
-        self.api.list_metadata(lp).await
+    async fn list(&self, lp: &ListParams) -> kube_client::Result<ObjectListStream<Self::Value>> {
+        self.api.list_metadata_items(lp).await
     }
 
     async fn watch(
@@ -522,7 +525,7 @@ where
         State::Empty => match wc.initial_list_strategy {
             InitialListStrategy::ListWatch => (Some(Ok(Event::Init)), State::InitPage {
                 continue_token: None,
-                objects: VecDeque::default(),
+                page: None,
                 last_bookmark: None,
             }),
             InitialListStrategy::StreamingList => {
@@ -541,16 +544,38 @@ where
         },
         State::InitPage {
             continue_token,
-            mut objects,
+            page: Some(mut page),
             last_bookmark,
-        } => {
-            if let Some(next) = objects.pop_front() {
-                return (Some(Ok(Event::InitApply(next))), State::InitPage {
+        } => match page.next().await {
+            // Objects are passed on as they are decoded, without buffering the page
+            Some(Ok(next)) => (Some(Ok(Event::InitApply(next))), State::InitPage {
+                continue_token,
+                page: Some(page),
+                last_bookmark,
+            }),
+            Some(Err(err)) => {
+                debug!("watch list error: {err:?}");
+                (Some(Err(Error::InitialListFailed(err))), State::Empty)
+            }
+            None => {
+                let last_bookmark = page.metadata().resource_version.clone().filter(|s| !s.is_empty());
+                let continue_token = page.metadata().continue_.clone().filter(|s| !s.is_empty());
+                if last_bookmark.is_none() && continue_token.is_none() {
+                    return (Some(Err(Error::NoResourceVersion)), State::Empty);
+                }
+                // Return to this enum branch (State::InitPage) to fetch the next page, or finish the list
+                (None, State::InitPage {
                     continue_token,
-                    objects,
+                    page: None,
                     last_bookmark,
-                });
+                })
             }
+        },
+        State::InitPage {
+            continue_token,
+            page: None,
+            last_bookmark,
+        } => {
             // check if we need to perform more pages
             if continue_token.is_none()
                 && let Some(resource_version) = last_bookmark
@@ -561,20 +586,13 @@ where
             let mut lp = wc.to_list_params();
             lp.continue_token = continue_token;
             match api.list(&lp).await {
-                Ok(list) => {
-                    let last_bookmark = list.metadata.resource_version.filter(|s| !s.is_empty());
-                    let continue_token = list.metadata.continue_.filter(|s| !s.is_empty());
-                    if last_bookmark.is_none() && continue_token.is_none() {
-                        return (Some(Err(Error::NoResourceVersion)), State::Empty);
-                    }
-                    // Buffer page here, causing us to return to this enum branch (State::InitPage)
-                    // until the objects buffer has drained
-                    (None, State::InitPage {
-                        continue_token,
-                        objects: list.items.into_iter().collect(),
-                        last_bookmark,
-                    })
-                }
+                // Decode the page as it is consumed, returning to this enum branch (State::InitPage)
+                // until the page has been drained, which yields the next continue token and bookmark
+                Ok(page) => (None, State::InitPage {
+                    continue_token: None,
+                    page: Some(Box::new(page)),
+                    last_bookmark: None,
+                }),
                 Err(err) => {
                     if std::matches!(err, ClientErr::Api(ref status) if status.is_forbidden()) {
                         warn!("watch list error with 403: {err:?}");
@@ -1095,4 +1113,46 @@ mod tests {
         let result = next_with_idle_timeout(&mut stream, Some(290)).await;
         assert_eq!(result, None);
     }
+
+    #[tokio::test]
+    async fn initial_list_pages_are_applied_as_they_are_decoded() {
+        use http::{Request, Response};
+        use k8s_openapi::api::core::v1::ConfigMap;
+        use kube_client::{Client, client::Body};
+
+        let (mock_service, handle) = tower_test::mock::pair::<Request<Body>, Response<Body>>();
+        let server = tokio::spawn(async move {
+            let mut handle = std::pin::pin!(handle);
+            for (names, continue_token) in [(["a", "b"], Some("page2")), (["c", "d"], None)] {
+                let (request, send) = handle.next_request().await.expect("service not called");
+                let query = request.uri().query().unwrap_or_default();
+                assert_eq!(query.contains("continue=page2"), continue_token.is_none());
+                let items: Vec<_> = names
+                    .iter()
+                    .map(|name| serde_json::json!({ "metadata": { "name": name } }))
+                    .collect();
+                let list = serde_json::json!({
+                    "apiVersion": "v1",
+                    "kind": "ConfigMapList",
+                    "metadata": { "resourceVersion": "10", "continue": continue_token },
+                    "items": items,
+                });
+                send.send_response(Response::new(Body::from(serde_json::to_vec(&list).unwrap())));
+            }
+        });
+
+        let api = Api::<ConfigMap>::all(Client::new(mock_service, "default"));
+        let events: Vec<_> = watcher(api, Config::default().page_size(2))
+            .take(6)
+            .map(|event| match event.unwrap() {
+                Event::Init => "init".to_string(),
+                Event::InitApply(cm) => cm.name_any(),
+                Event::InitDone => "done".to_string(),
+                event => panic!("unexpected event {event:?}"),
+            })
+            .collect()
+            .await;
+        assert_eq!(events, ["init", "a", "b", "c", "d", "done"]);
+        server.await.unwrap();
+    }
 }
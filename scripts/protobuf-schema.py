@@ -0,0 +1,167 @@
+#!/usr/bin/env python3
+"""Generate kube-client/src/client/protobuf/schema.rs
+
+Usage: protobuf-schema.py <k8s-pb crate dir> <k8s-openapi crate dir> <k8s-openapi version module>
+
+The protobuf field numbers and types come from the prost bindings of k8s-pb, while the JSON names of the fields
+(and whether they are optional) come from k8s-openapi, as the prost bindings only have the snake case names.
+"""
+import os
+import re
+import sys
+
+# Module paths of the packages in the schema, by their short name
+PACKAGES = {
+    "api.core.v1": "core.v1",
+    "api.apps.v1": "apps.v1",
+    "api.batch.v1": "batch.v1",
+    "apimachinery.pkg.apis.meta.v1": "meta.v1",
+    "apimachinery.pkg.runtime": "runtime",
+    "apimachinery.pkg.api.resource": "resource",
+    "apimachinery.pkg.util.intstr": "intstr",
+}
+
+SCALARS = {"string": "String", "bool": "Bool", "int32": "Int32", "int64": "Int64", "bytes": "Bytes"}
+
+# Empty optional strings are omitted like in JSON, except for these pointers, where an empty string is meaningful
+POINTER_STRINGS = {
+    "core.v1.PersistentVolumeClaimSpec.storageClassName",
+    "core.v1.PersistentVolumeClaimSpec.volumeAttributesClassName",
+    "core.v1.PersistentVolumeClaimStatus.currentVolumeAttributesClassName",
+    "core.v1.PersistentVolumeSpec.volumeAttributesClassName",
+}
+
+
+def key(name):
+    """Field names comparable between k8s-pb (`pod_cid_rs`) and k8s-openapi (`pod_cidrs`)"""
+    return name.removeprefix("r#").replace("_", "")
+
+
+def camel(name):
+    head, *rest = name.removeprefix("r#").rstrip("_").split("_")
+    return head + "".join(part.title() for part in rest)
+
+
+def openapi_structs(root, version):
+    """The k8s-openapi structs: {(package, lowercase name): (name, {field key: (json name, optional)})}"""
+    structs = {}
+    for module, package in PACKAGES.items():
+        directory = os.path.join(root, "src", version, *module.split("."))
+        if not os.path.isdir(directory):
+            continue
+        for file in sorted(os.listdir(directory)):
+            if not file.endswith(".rs") or file == "mod.rs":
+                continue
+            source = open(os.path.join(directory, file)).read()
+            struct = re.search(r"pub struct (\w+) \{(.*?)\n\}", source, re.S)
+            if not struct:
+                continue
+            json = {key(field): name for name, field in re.findall(r'"(\w+)" => Field::Key_(\w+),', source)}
+            structs[(package, struct.group(1).lower())] = (struct.group(1), {
+                key(field): (json.get(key(field), camel(field)), ty.startswith(("Option<", "std::option::Option<")))
+                for field, ty in re.findall(r"^\s*pub (\w+): (.*),$", struct.group(2), re.M)
+            })
+    return structs
+
+
+def resolve(module, path):
+    """Resolve a (possibly relative) prost type path used in `module` to (package, prost name)"""
+    parts = [part for part in path.split("::") if part]
+    current = module.split(".")
+    while parts[0] == "super":
+        current.pop()
+        parts.pop(0)
+    target = ".".join(current + parts[:-1]) if len(parts) > 1 else module
+    if target not in PACKAGES:
+        sys.exit(f"{path} in {module} is outside the schema")
+    return PACKAGES[target], parts[-1]
+
+
+def prost_messages(root):
+    """The prost messages: {(package, prost name): [(tag, field, attribute, type, module)]}"""
+    messages = {}
+    for module, package in PACKAGES.items():
+        source = open(os.path.join(root, "src", *module.split("."), "mod.rs")).read()
+        for name, body in re.findall(r"pub struct (\w+) \{(.*?)\n\}", source, re.S):
+            fields = []
+            for declaration in body.split("#[prost(")[1:]:
+                attr, field, ty = re.match(r"(.*?)\)\]\s*pub ([\w#]+):\s*(.*?),\s*(?:///|$)", declaration, re.S).groups()
+                tag = int(re.search(r'tag = "(\d+)"', attr).group(1))
+                fields.append((tag, field, attr, re.sub(r"\s+", "", ty), module))
+            messages[(package, name)] = sorted(fields)
+    return messages
+
+
+def schema(messages, structs):
+    """The rendered fields of every message, by the name of the message in Kubernetes"""
+
+    def upstream(package, name):
+        # The name of a message in Kubernetes (like `PodIP`), rather than its prost name (like `PodIp`)
+        return f"{package}.{structs.get((package, name.lower()), (name, None))[0]}"
+
+    schemas = {}
+    for (package, name), fields in messages.items():
+        openapi = structs.get((package, name.lower()), (name, {}))[1]
+        rendered = []
+        for tag, field, attr, ty, module in fields:
+            json, optional = openapi.get(key(field), (camel(field), True))
+            flags = []
+            if map_types := re.match(r'(?:btree_)?map = "(\w+), (\w+)"', attr):
+                map_key, value = map_types.groups()
+                if map_key != "string":
+                    sys.exit(f"{package}.{name}.{field} has non string keys")
+                if value == "message":
+                    value = resolve(module, re.search(r",([\w:]+),?>$", ty).group(1))
+                    kind = f'Map(&Message("{upstream(*value)}"))'
+                else:
+                    kind = f"Map(&{SCALARS[value]})"
+            elif attr.startswith("message"):
+                inner = resolve(module, re.search(r"<([\w:]+)>+$", ty).group(1))
+                kind = f'Message("{upstream(*inner)}")'
+                # Embedded Go structs are fields in protobuf, but their fields are inlined in JSON
+                if openapi and key(field) not in openapi:
+                    if all(key(inner_field) in openapi for _, inner_field, *_ in messages[inner]):
+                        flags.append(".inline()")
+                    else:
+                        print(f"{package}.{name}.{field} is not in k8s-openapi", file=sys.stderr)
+            else:
+                scalar = attr.split(",")[0].split(" ")[0]
+                kind = SCALARS[scalar]
+                pointer = f"{upstream(package, name)}.{json}" in POINTER_STRINGS
+                if scalar == "string" and optional and not pointer and ", repeated" not in attr:
+                    flags.append(".omit_empty()")
+            if ", repeated" in attr:
+                flags.insert(0, ".repeated()")
+            rendered.append(f'Field::new({tag}, "{json}", {kind}){"".join(flags)}')
+        schemas[upstream(package, name)] = rendered
+    return schemas
+
+
+def render(schemas, versions):
+    body = []
+    for name in sorted(schemas):
+        body.append(f'    Schema::new("{name}", &[')
+        body.extend(f"        {field}," for field in schemas[name])
+        body.append("    ]),")
+    kinds = sorted({kind for kind in [*SCALARS.values(), "Map", "Message"] if re.search(rf"\b{kind}\b[(,)]", "\n".join(body))})
+    return "\n".join([
+        f"// Generated by scripts/protobuf-schema.py from {versions}, do not edit",
+        "use super::{",
+        "    Field,",
+        f"    Kind::{{{', '.join(kinds)}}},",
+        "    Schema,",
+        "};",
+        "",
+        "/// Messages of the built-in types, sorted by name",
+        "#[rustfmt::skip]",
+        "pub(super) static MESSAGES: &[Schema] = &[",
+        *body,
+        "];",
+    ]) + "\n"
+
+
+if __name__ == "__main__":
+    pb, openapi, version = sys.argv[1:4]
+    schemas = schema(prost_messages(pb), openapi_structs(openapi, version))
+    versions = f"{os.path.basename(os.path.abspath(pb))} and {os.path.basename(os.path.abspath(openapi))} ({version})"
+    sys.stdout.write(render(schemas, versions))